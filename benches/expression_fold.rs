@@ -0,0 +1,69 @@
+//! Allocations and time of building a 1e6-term expression by folding, with
+//! and without reserving its terms up front. Run with `cargo bench`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use exact_lp::{Expression, Model};
+
+const TERMS: usize = 1_000_000;
+
+/// System allocator counting allocations and reallocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs `build`, printing its allocations, reallocations and time.
+fn measure(label: &str, build: impl FnOnce() -> Expression<f64>) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let reallocations = REALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let e = black_box(build());
+    let elapsed = start.elapsed();
+    println!(
+        "{label}: {} terms, {} allocations, {} reallocations, {:?}",
+        e.num_terms(),
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        REALLOCATIONS.load(Ordering::Relaxed) - reallocations,
+        elapsed
+    );
+}
+
+fn main() {
+    let mut model = Model::<f64>::with_capacity(TERMS, 0);
+    let x = (0..TERMS)
+        .map(|i| model.add_var().name(format!("x{i}")).build())
+        .collect::<Vec<_>>();
+
+    measure("fold", || {
+        x.iter().fold(Expression::default(), |acc, v| acc + v * 2.0)
+    });
+    measure("fold with capacity", || {
+        x.iter()
+            .fold(Expression::with_capacity(TERMS), |acc, v| acc + v * 2.0)
+    });
+}
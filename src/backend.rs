@@ -0,0 +1,253 @@
+use num::{BigRational, Num};
+
+use crate::{
+    error::SolveError,
+    options::{ImportPolicy, SolveOptions},
+    scaling::Scaling,
+    solution::{parse_f64, parse_rational, Solution},
+    variable::Variable,
+    Model, VariableType,
+};
+
+/**
+ * Something that solves models, such as an external solver executable or a
+ * solver library linked into the program
+ *
+ * A backend can read the model through `Model::write_lp`,
+ * `Model::export_mps` and `Model::variables`, and report its result with
+ * `Solution::new`. The example below pretends every variable sits at its
+ * lower bound:
+ *
+ * ```
+ * use std::collections::BTreeMap;
+ *
+ * use exact_lp::{Backend, Model, Solution, SolveError, SolveOptions, SolveStatus};
+ *
+ * struct LowerBounds;
+ *
+ * impl Backend<f64> for LowerBounds {
+ *     fn solve(&self, model: &Model<f64>, _: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+ *         let values = model
+ *             .variables()
+ *             .map(|v| (v.name, v.lb.copied().unwrap_or(0.0)))
+ *             .collect::<BTreeMap<_, _>>();
+ *         Ok(Solution::new(SolveStatus::Unknown, None, values))
+ *     }
+ * }
+ *
+ * let mut model = Model::<f64>::new();
+ * let x = model.add_var().name("x").lb(2.0).build();
+ * let y = model.add_var().name("y").build();
+ * let solution = model.solve_using(&LowerBounds, &SolveOptions::default()).unwrap();
+ * assert_eq!(solution.get_value(&x * 3.0 + &y), 6.0);
+ * ```
+ */
+pub trait Backend<N>
+where
+    N: Num + Clone,
+{
+    fn solve(&self, model: &Model<N>, opts: &SolveOptions) -> Result<Solution<N>, SolveError>;
+}
+
+/**
+ * The `scip` executable, exact for `Model<BigRational>`. Used by
+ * `Model::solve` and `Model::solve_with`.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScipBackend;
+
+impl Backend<BigRational> for ScipBackend {
+    fn solve(
+        &self,
+        model: &Model<BigRational>,
+        opts: &SolveOptions,
+    ) -> Result<Solution<BigRational>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        if model.has_quadratic_objective() {
+            return Err(SolveError::UnsupportedQuadraticObjective(
+                "exact solves".to_string(),
+            ));
+        }
+        let (formulation, scaling) = model.integral_formulation()?;
+        let exact = model.solves_exactly();
+        // floating-point values are only ever approximations
+        let policy = if exact {
+            opts.import_policy
+        } else {
+            ImportPolicy::AcceptDecimals
+        };
+        let solution = model.solve_scip(
+            &formulation,
+            &scaling,
+            exact,
+            opts,
+            |f| Solution::<BigRational>::parse_with_policy(f, policy),
+            parse_rational,
+        )?;
+        Ok(solution.with_approximate(!exact))
+    }
+}
+
+impl Backend<f64> for ScipBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        model.solve_scip(
+            model,
+            &Scaling::identity(),
+            false,
+            opts,
+            Solution::<f64>::parse,
+            parse_f64,
+        )
+    }
+}
+
+/**
+ * The `highs` executable
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HighsBackend;
+
+impl Backend<f64> for HighsBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        model.solve_highs(opts)
+    }
+}
+
+/**
+ * The `cbc` executable
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CbcBackend;
+
+impl Backend<f64> for CbcBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        if model.has_quadratic_objective() {
+            return Err(SolveError::UnsupportedQuadraticObjective("CBC".to_string()));
+        }
+        model.solve_cbc(opts)
+    }
+}
+
+/**
+ * GLPK's `glpsol` executable
+ *
+ * GLPK solves in floating point, so there is no backend for
+ * `Model<BigRational>`. Its LP reader has no semi-continuous variables or
+ * indicator constraints, and models using them fail with
+ * `SolveError::SolverFailed`.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlpkBackend;
+
+impl Backend<f64> for GlpkBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        if model.has_quadratic_objective() {
+            return Err(SolveError::UnsupportedQuadraticObjective(
+                "GLPK".to_string(),
+            ));
+        }
+        model.solve_glpk(opts)
+    }
+}
+
+/**
+ * CPLEX's interactive optimizer `cplex`, which needs a license
+ *
+ * The model is read from an LP file and the solution written in CPLEX's XML
+ * format. Infeasible and unbounded models write no solution, and their
+ * status is taken from the log.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CplexBackend;
+
+impl Backend<f64> for CplexBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        model.solve_cplex(opts)
+    }
+}
+
+/**
+ * Gurobi's `gurobi_cl`, which needs a license
+ *
+ * The model is read from an LP file. Gurobi's solution files have no status,
+ * so it is taken from the log.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GurobiBackend;
+
+impl Backend<f64> for GurobiBackend {
+    fn solve(&self, model: &Model<f64>, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        model.validate().map_err(SolveError::InvalidModel)?;
+        if let Some(solution) = model.trivial_solution(opts) {
+            return Ok(solution);
+        }
+        model.solve_gurobi(opts)
+    }
+}
+
+/**
+ * A variable of a model as listed by `Model::variables`
+ */
+pub struct VariableInfo<'a, N>
+where
+    N: Num + Clone,
+{
+    pub variable: Variable<N>,
+    /// Name as written to model and solution files.
+    pub name: String,
+    pub variable_type: VariableType,
+    pub lb: Option<&'a N>,
+    pub ub: Option<&'a N>,
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Solves the model with `backend`, giving substituted variables the
+    /// value of their replacement.
+    pub fn solve_using(
+        &self,
+        backend: &impl Backend<N>,
+        opts: &SolveOptions,
+    ) -> Result<Solution<N>, SolveError> {
+        let mut solution = backend.solve(self, opts)?;
+        self.add_substituted_values(&mut solution);
+        Ok(solution)
+    }
+
+    /// The variables of the model in the order they were added, leaving
+    /// out substituted ones.
+    pub fn variables(&self) -> impl Iterator<Item = VariableInfo<'_, N>> {
+        self.live_variables().map(|(i, v)| VariableInfo {
+            variable: Variable::new(i, v.name.clone()),
+            name: self.variable_name(i),
+            variable_type: v.v_type,
+            lb: v.lb.finite(),
+            ub: v.ub.finite(),
+        })
+    }
+}
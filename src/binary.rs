@@ -0,0 +1,766 @@
+//! Compact binary encoding of exact models, for caching generated models.
+//!
+//! An encoded model starts with `MAGIC` and `FORMAT_VERSION`, followed by
+//! every string the model uses, once, and then the model, which refers to
+//! strings and variables by index. Integers are LEB128 varints, rationals a
+//! sign byte and the little-endian bytes of the numerator and denominator,
+//! each prefixed with its length.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use num::{
+    bigint::{BigInt, BigUint, Sign},
+    BigRational, Zero,
+};
+
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    error::DecodeError,
+    expression::Expression,
+    objectives::Objective,
+    params::{Aggressiveness, Emphasis, Param},
+    quadratic::QuadExpression,
+    variable::Variable,
+    Bound, InternalConstraint, InternalVariable, Model, OptimizationDirection, VariableType,
+};
+
+const MAGIC: &[u8; 4] = b"ELPM";
+
+/// Version of the encoding written by `Model::to_bytes`, to be increased
+/// whenever the layout changes.
+pub(crate) const FORMAT_VERSION: u8 = 2;
+
+// codes of fieldless enums are their positions here
+
+const VARIABLE_TYPES: [VariableType; 5] = [
+    VariableType::Binary,
+    VariableType::Integer,
+    VariableType::Continuous,
+    VariableType::SemiContinuous,
+    VariableType::SemiInteger,
+];
+
+const DIRECTIONS: [OptimizationDirection; 2] = [
+    OptimizationDirection::Maximize,
+    OptimizationDirection::Minimize,
+];
+
+const EMPHASES: [Emphasis; 5] = [
+    Emphasis::Default,
+    Emphasis::Feasibility,
+    Emphasis::Optimality,
+    Emphasis::EasyCip,
+    Emphasis::HardLp,
+];
+
+const AGGRESSIVENESSES: [Aggressiveness; 4] = [
+    Aggressiveness::Default,
+    Aggressiveness::Aggressive,
+    Aggressiveness::Fast,
+    Aggressiveness::Off,
+];
+
+/// Position of `value` in `values`, which lists every value of its type.
+fn code<T: PartialEq>(values: &[T], value: &T) -> u8 {
+    values
+        .iter()
+        .position(|v| v == value)
+        .expect("every value is listed") as u8
+}
+
+#[derive(Default)]
+struct Encoder {
+    strings: Vec<String>,
+    indices: BTreeMap<String, u64>,
+    body: Vec<u8>,
+}
+
+impl Encoder {
+    fn byte(&mut self, byte: u8) {
+        self.body.push(byte);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.byte(u8::from(value));
+    }
+
+    fn uint(&mut self, mut n: u64) {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                self.body.push(byte);
+                return;
+            }
+            self.body.push(byte | 0x80);
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.uint(bytes.len() as u64);
+        self.body.extend_from_slice(bytes);
+    }
+
+    /// Index of `s` in the string table, adding it if it is new.
+    fn intern(&mut self, s: &str) -> u64 {
+        if let Some(&i) = self.indices.get(s) {
+            return i;
+        }
+        let i = self.strings.len() as u64;
+        self.strings.push(s.to_string());
+        self.indices.insert(s.to_string(), i);
+        i
+    }
+
+    fn string(&mut self, s: &str) {
+        let i = self.intern(s);
+        self.uint(i);
+    }
+
+    /// Zero for `None`, one more than the string index otherwise.
+    fn optional_string(&mut self, s: Option<&str>) {
+        let i = s.map_or(0, |s| self.intern(s) + 1);
+        self.uint(i);
+    }
+
+    fn rational(&mut self, r: &BigRational) {
+        self.byte(match r.numer().sign() {
+            Sign::NoSign => 0,
+            Sign::Plus => 1,
+            Sign::Minus => 2,
+        });
+        self.bytes(&r.numer().magnitude().to_bytes_le());
+        self.bytes(&r.denom().magnitude().to_bytes_le());
+    }
+
+    fn bound(&mut self, b: &Bound<BigRational>) {
+        match b {
+            Bound::NegInf => self.byte(0),
+            Bound::Finite(b) => {
+                self.byte(1);
+                self.rational(b);
+            }
+            Bound::PosInf => self.byte(2),
+        }
+    }
+
+    /// Zero for constants, one more than the variable id otherwise.
+    fn expression(&mut self, e: &Expression<BigRational>) {
+        self.uint(e.0.len() as u64);
+        for (w, v) in &e.0 {
+            self.uint(v.as_ref().map_or(0, |v| v.id() as u64 + 1));
+            self.rational(w);
+        }
+    }
+
+    fn param(&mut self, param: &Param) {
+        match param {
+            Param::TimeLimit(time_limit) => {
+                self.byte(0);
+                self.uint(time_limit.as_secs());
+                self.uint(time_limit.subsec_nanos().into());
+            }
+            Param::Gap(gap) => {
+                self.byte(1);
+                self.body.extend_from_slice(&gap.to_bits().to_le_bytes());
+            }
+            Param::NodeLimit(nodes) => {
+                self.byte(2);
+                self.uint(*nodes);
+            }
+            Param::Threads(threads) => {
+                self.byte(3);
+                self.uint((*threads).into());
+            }
+            Param::RandomSeed(seed) => {
+                self.byte(4);
+                self.uint(*seed);
+            }
+            Param::Presolve(on) => {
+                self.byte(5);
+                self.bool(*on);
+            }
+            Param::Emphasis(emphasis) => {
+                self.byte(6);
+                self.byte(code(&EMPHASES, emphasis));
+            }
+            Param::Heuristics(aggressiveness) => {
+                self.byte(7);
+                self.byte(code(&AGGRESSIVENESSES, aggressiveness));
+            }
+        }
+    }
+
+    /// Header, string table and body.
+    fn finish(self) -> Vec<u8> {
+        let mut header = Encoder::default();
+        header.body.extend_from_slice(MAGIC);
+        header.byte(FORMAT_VERSION);
+        header.uint(self.strings.len() as u64);
+        for s in &self.strings {
+            header.bytes(s.as_bytes());
+        }
+        header.body.extend(self.body);
+        header.body
+    }
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+    strings: Vec<String>,
+    /// Names of the variables read so far, by id.
+    variables: Vec<Option<String>>,
+}
+
+fn invalid(message: impl Into<String>) -> DecodeError {
+    DecodeError::Invalid(message.into())
+}
+
+impl<'a> Decoder<'a> {
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let (&byte, rest) = self.input.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        self.input = rest;
+        Ok(byte)
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeError> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(invalid(format!("{byte} is not a boolean"))),
+        }
+    }
+
+    fn uint(&mut self) -> Result<u64, DecodeError> {
+        let mut n = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            if shift == 63 && byte > 1 {
+                break;
+            }
+            n |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(invalid("integer out of range"))
+    }
+
+    fn usize(&mut self) -> Result<usize, DecodeError> {
+        usize::try_from(self.uint()?).map_err(|_| invalid("integer out of range"))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = self.usize()?;
+        if len > self.input.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let i = self.usize()?;
+        self.strings
+            .get(i)
+            .cloned()
+            .ok_or_else(|| invalid(format!("no string {i}")))
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, DecodeError> {
+        match self.usize()? {
+            0 => Ok(None),
+            i => self
+                .strings
+                .get(i - 1)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| invalid(format!("no string {}", i - 1))),
+        }
+    }
+
+    /// Value of a fieldless enum listed in `values`.
+    fn code<T: Copy>(&mut self, values: &[T], what: &str) -> Result<T, DecodeError> {
+        let byte = self.byte()?;
+        values
+            .get(usize::from(byte))
+            .copied()
+            .ok_or_else(|| invalid(format!("{byte} is not a {what}")))
+    }
+
+    fn rational(&mut self) -> Result<BigRational, DecodeError> {
+        let sign = match self.byte()? {
+            0 => Sign::NoSign,
+            1 => Sign::Plus,
+            2 => Sign::Minus,
+            byte => return Err(invalid(format!("{byte} is not a sign"))),
+        };
+        let numer = BigUint::from_bytes_le(self.bytes()?);
+        let denom = BigUint::from_bytes_le(self.bytes()?);
+        if (sign == Sign::NoSign) != numer.is_zero() {
+            return Err(invalid("sign does not match the numerator"));
+        }
+        if denom.is_zero() {
+            return Err(invalid("zero denominator"));
+        }
+        Ok(BigRational::new(
+            BigInt::from_biguint(sign, numer),
+            BigInt::from_biguint(Sign::Plus, denom),
+        ))
+    }
+
+    fn bound(&mut self) -> Result<Bound<BigRational>, DecodeError> {
+        match self.byte()? {
+            0 => Ok(Bound::NegInf),
+            1 => Ok(Bound::Finite(self.rational()?)),
+            2 => Ok(Bound::PosInf),
+            byte => Err(invalid(format!("{byte} is not a bound"))),
+        }
+    }
+
+    fn variable_at(&self, i: usize) -> Result<Variable<BigRational>, DecodeError> {
+        let name = self
+            .variables
+            .get(i)
+            .ok_or_else(|| invalid(format!("no variable {i}")))?;
+        Ok(Variable::new(i, name.clone()))
+    }
+
+    fn variable(&mut self) -> Result<Variable<BigRational>, DecodeError> {
+        let i = self.usize()?;
+        self.variable_at(i)
+    }
+
+    fn expression(&mut self) -> Result<Expression<BigRational>, DecodeError> {
+        let len = self.usize()?;
+        let mut e = Expression::default();
+        for _ in 0..len {
+            let v = match self.usize()? {
+                0 => None,
+                i => Some(self.variable_at(i - 1)?),
+            };
+            e.0.push((self.rational()?, v));
+        }
+        Ok(e)
+    }
+
+    fn param(&mut self) -> Result<Param, DecodeError> {
+        Ok(match self.byte()? {
+            0 => {
+                let secs = self.uint()?;
+                let nanos = u32::try_from(self.uint()?)
+                    .ok()
+                    .filter(|&nanos| nanos < 1_000_000_000)
+                    .ok_or_else(|| invalid("nanoseconds out of range"))?;
+                Param::TimeLimit(Duration::new(secs, nanos))
+            }
+            1 => {
+                let mut bits = [0; 8];
+                for byte in &mut bits {
+                    *byte = self.byte()?;
+                }
+                Param::Gap(f64::from_bits(u64::from_le_bytes(bits)))
+            }
+            2 => Param::NodeLimit(self.uint()?),
+            3 => Param::Threads(
+                u32::try_from(self.uint()?).map_err(|_| invalid("thread count out of range"))?,
+            ),
+            4 => Param::RandomSeed(self.uint()?),
+            5 => Param::Presolve(self.bool()?),
+            6 => Param::Emphasis(self.code(&EMPHASES, "search emphasis")?),
+            7 => Param::Heuristics(self.code(&AGGRESSIVENESSES, "heuristics setting")?),
+            byte => return Err(invalid(format!("{byte} is not a parameter"))),
+        })
+    }
+
+    fn constraint(&mut self) -> Result<InternalConstraint<BigRational>, DecodeError> {
+        let name = self.optional_string()?;
+        let sense = match self.byte()? {
+            0 => ConstraintSense::Le,
+            1 => ConstraintSense::Eq,
+            2 => ConstraintSense::Ge,
+            3 => ConstraintSense::Range {
+                lb: self.rational()?,
+                ub: self.rational()?,
+            },
+            byte => return Err(invalid(format!("{byte} is not a constraint sense"))),
+        };
+        let lhs = self.expression()?;
+        let rhs = self.expression()?;
+        let indicator = match self.bool()? {
+            false => None,
+            true => Some((self.variable()?, self.bool()?)),
+        };
+        Ok(InternalConstraint {
+            name,
+            constraint: Constraint { lhs, sense, rhs },
+            indicator,
+            group: self.optional_string()?,
+        })
+    }
+}
+
+impl Model<BigRational> {
+    /// The model in a compact binary format read by `from_bytes`, e.g. to
+    /// cache a model that takes long to generate. Unlike the LP format it
+    /// keeps everything but the solver path and arguments: removed
+    /// constraints, groups, further objectives, the start, commands and
+    /// parameters. The format is versioned, and `from_bytes` rejects other
+    /// versions instead of misreading them.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut e = Encoder::default();
+        e.uint(self.commands.len() as u64);
+        for command in &self.commands {
+            e.string(command);
+        }
+        e.uint(self.params.len() as u64);
+        for param in &self.params {
+            e.param(param);
+        }
+        e.byte(code(&DIRECTIONS, &self.direction));
+
+        e.uint(self.variables.len() as u64);
+        for v in &self.variables {
+            e.byte(code(&VARIABLE_TYPES, &v.v_type));
+            e.optional_string(v.name.as_deref());
+            e.bound(&v.lb);
+            e.bound(&v.ub);
+            e.bool(v.eliminated);
+            e.bool(v.obj.is_some());
+            if let Some(obj) = &v.obj {
+                e.rational(obj);
+            }
+        }
+
+        e.expression(&self.objective);
+        e.uint(self.quadratic_objective.len() as u64);
+        for (w, a, b) in self.full_objective_terms().quadratic_terms() {
+            e.rational(w);
+            e.uint(a.id() as u64);
+            e.uint(b.id() as u64);
+        }
+        e.uint(self.objectives.len() as u64);
+        for o in &self.objectives {
+            e.expression(&o.expression);
+            e.byte(code(&DIRECTIONS, &o.direction));
+            e.uint(o.priority.into());
+        }
+
+        e.uint(self.constraints.len() as u64);
+        for c in &self.constraints {
+            let Some(c) = c else {
+                e.bool(false);
+                continue;
+            };
+            e.bool(true);
+            e.optional_string(c.name.as_deref());
+            match &c.constraint.sense {
+                ConstraintSense::Le => e.byte(0),
+                ConstraintSense::Eq => e.byte(1),
+                ConstraintSense::Ge => e.byte(2),
+                ConstraintSense::Range { lb, ub } => {
+                    e.byte(3);
+                    e.rational(lb);
+                    e.rational(ub);
+                }
+            }
+            e.expression(&c.constraint.lhs);
+            e.expression(&c.constraint.rhs);
+            e.bool(c.indicator.is_some());
+            if let Some((indicator, active_when)) = &c.indicator {
+                e.uint(indicator.id() as u64);
+                e.bool(*active_when);
+            }
+            e.optional_string(c.group.as_deref());
+        }
+
+        e.bool(self.start.is_some());
+        if let Some(start) = &self.start {
+            e.uint(start.len() as u64);
+            for (name, value) in start {
+                e.string(name);
+                e.rational(value);
+            }
+        }
+        e.uint(self.auxiliaries as u64);
+        e.byte(match self.exact {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+        e.optional_string(self.current_group.as_deref());
+        e.uint(self.disabled_groups.len() as u64);
+        for group in &self.disabled_groups {
+            e.string(group);
+        }
+        e.uint(self.substitutions.len() as u64);
+        for (v, replacement) in &self.substitutions {
+            e.uint(v.id() as u64);
+            e.expression(replacement);
+        }
+        e.finish()
+    }
+
+    /// Reads a model written by `to_bytes`, failing on input that is cut
+    /// short, corrupted or written in another format version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Model<BigRational>, DecodeError> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(DecodeError::NotAModel)?;
+        let mut d = Decoder {
+            input: rest,
+            strings: vec![],
+            variables: vec![],
+        };
+        let version = d.byte()?;
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        for _ in 0..d.usize()? {
+            let s = std::str::from_utf8(d.bytes()?).map_err(|_| invalid("string is not UTF-8"))?;
+            d.strings.push(s.to_string());
+        }
+
+        let mut model = Model::new();
+        for _ in 0..d.usize()? {
+            model.commands.push(d.string()?);
+        }
+        for _ in 0..d.usize()? {
+            model.set_param(d.param()?);
+        }
+        model.direction = d.code(&DIRECTIONS, "direction")?;
+
+        for _ in 0..d.usize()? {
+            let v_type = d.code(&VARIABLE_TYPES, "variable type")?;
+            let name = d.optional_string()?;
+            let lb = d.bound()?;
+            let ub = d.bound()?;
+            let eliminated = d.bool()?;
+            let obj = match d.bool()? {
+                false => None,
+                true => Some(d.rational()?),
+            };
+            if let Some(name) = &name {
+                model.variable_names.insert(name.clone());
+            }
+            d.variables.push(name.clone());
+            model.variables.push(InternalVariable {
+                v_type,
+                name,
+                lb,
+                ub,
+                eliminated,
+                obj,
+            });
+        }
+
+        model.objective = d.expression()?;
+        let mut quadratic = QuadExpression::new();
+        for _ in 0..d.usize()? {
+            let w = d.rational()?;
+            let a = d.variable()?;
+            let b = d.variable()?;
+            quadratic.add_term(w, &a, &b);
+        }
+        model.quadratic_objective = quadratic.quadratic;
+        for _ in 0..d.usize()? {
+            let expression = d.expression()?;
+            let direction = d.code(&DIRECTIONS, "direction")?;
+            let priority =
+                u32::try_from(d.uint()?).map_err(|_| invalid("priority out of range"))?;
+            model.objectives.push(Objective {
+                expression,
+                direction,
+                priority,
+            });
+        }
+
+        for _ in 0..d.usize()? {
+            let c = match d.bool()? {
+                false => None,
+                true => Some(d.constraint()?),
+            };
+            model.constraints.push(c);
+        }
+
+        if d.bool()? {
+            let mut start = BTreeMap::new();
+            for _ in 0..d.usize()? {
+                let name = d.string()?;
+                start.insert(name, d.rational()?);
+            }
+            model.start = Some(start);
+        }
+        model.auxiliaries = d.usize()?;
+        model.exact = match d.byte()? {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            byte => return Err(invalid(format!("{byte} is not an exactness setting"))),
+        };
+        model.current_group = d.optional_string()?;
+        let mut disabled_groups = BTreeSet::new();
+        for _ in 0..d.usize()? {
+            disabled_groups.insert(d.string()?);
+        }
+        model.disabled_groups = disabled_groups;
+        for _ in 0..d.usize()? {
+            let v = d.variable()?;
+            model.substitutions.push((v, d.expression()?));
+        }
+
+        if !d.input.is_empty() {
+            return Err(invalid("trailing bytes after the model"));
+        }
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{
+        c, rat_frac, Aggressiveness, DecodeError, Expression, Model, OptimizationDirection, Param,
+        Solution, SolveStatus,
+    };
+
+    fn model() -> Model<BigRational> {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(rat_frac(-1, 3)).build();
+        let n = model.add_var().integer().ub(c(7).0).obj(c(2).0).build();
+        let b = model.add_var().name("b").binary().build();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .lb(c(1).0)
+            .ub(c(5).0)
+            .build();
+        let t = model
+            .add_var()
+            .name("t")
+            .semi_integer()
+            .lb(c(2).0)
+            .ub(c(9).0)
+            .build();
+        let f = model.add_var().name("f").free().build();
+        let big = BigRational::new(
+            "-123456789012345678901234567890".parse().unwrap(),
+            "98765432109876543210".parse().unwrap(),
+        );
+        model.maximize();
+        model.set_objective(big.clone() * &x + &n + c(3) * &s - &t + &f + rat_frac(1, 2));
+        model.add_objective(&x + &b, OptimizationDirection::Minimize, 2);
+        model
+            .add_const_named("cap", (&x + c(2) * &n).le(c(10).0))
+            .unwrap();
+        let removed = model.add_const((&s + &t).ge(c(1).0));
+        model.remove_const(removed);
+        model.begin_group("links");
+        model.add_const((&s - &t).between(c(-4).0, big));
+        model.add_indicator(b, false, (&x - &f).eq(c(0).0)).unwrap();
+        model.end_group();
+        model.set_group_enabled("links", false);
+        model.add_command("set display verblevel 0");
+        model.set_param(Param::TimeLimit(std::time::Duration::from_millis(1500)));
+        model.set_param(Param::Gap(0.05));
+        model.set_param(Param::Heuristics(Aggressiveness::Fast));
+        model.set_exact(true);
+        model.set_start(&Solution::new(
+            SolveStatus::Unknown,
+            None,
+            [
+                ("x".to_string(), rat_frac(2, 3)),
+                ("v1".to_string(), c(4).0),
+            ]
+            .into(),
+        ));
+        model.substitute_variable(&f, &(&x - c(1).0)).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let model = model();
+        let bytes = model.to_bytes();
+        let restored = Model::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(
+            restored.to_lp_string().unwrap(),
+            model.to_lp_string().unwrap()
+        );
+        assert_eq!(restored.commands, model.commands);
+        assert_eq!(restored.params, model.params);
+        assert_eq!(restored.variable_names, model.variable_names);
+        assert_eq!(restored.groups(), model.groups());
+        assert!(!restored.is_group_enabled("links"));
+        assert_eq!(restored.start, model.start);
+        assert_eq!(restored.num_objectives(), 2);
+        assert_eq!(restored.exact, Some(true));
+        assert_eq!(
+            restored
+                .substitutions
+                .iter()
+                .map(|(v, e)| (v.name(), e.to_string()))
+                .collect::<Vec<_>>(),
+            [("f".to_string(), "x - 1".to_string())]
+        );
+        // names are interned, so a repeated name is stored once
+        assert_eq!(bytes.windows(5).filter(|w| w == b"links").count(), 1);
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.set_quadratic_objective(&x * &y + (c(2) * &x) * x.clone() + Expression::from(&y));
+        let restored = Model::from_bytes(&model.to_bytes()).unwrap();
+        assert_eq!(
+            restored.to_lp_string().unwrap(),
+            model.to_lp_string().unwrap()
+        );
+        assert_eq!(
+            Model::from_bytes(&Model::<BigRational>::new().to_bytes())
+                .unwrap()
+                .to_lp_string()
+                .unwrap(),
+            Model::<BigRational>::new().to_lp_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_corrupted_bytes() {
+        let bytes = model().to_bytes();
+        for len in 0..bytes.len() {
+            assert!(Model::from_bytes(&bytes[..len]).is_err(), "{len}");
+        }
+        // no corruption of a single byte may panic
+        for i in 0..bytes.len() {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 0xff;
+            let _ = Model::from_bytes(&corrupted);
+        }
+
+        assert_eq!(
+            Model::from_bytes(b"x - y <= 1").err(),
+            Some(DecodeError::NotAModel)
+        );
+        let mut newer = bytes.clone();
+        newer[4] = 99;
+        let error = Model::from_bytes(&newer).err().unwrap();
+        assert_eq!(error, DecodeError::UnsupportedVersion(99));
+        assert_eq!(
+            error.to_string(),
+            "unsupported model format version 99, expected 2"
+        );
+        let mut longer = bytes;
+        longer.push(0);
+        assert_eq!(
+            Model::from_bytes(&longer).err(),
+            Some(DecodeError::Invalid(
+                "trailing bytes after the model".to_string()
+            ))
+        );
+    }
+}
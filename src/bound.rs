@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use num::Num;
+
+/**
+ * Lower or upper bound of a variable
+ *
+ * Variables are at least zero and unbounded above unless given other bounds,
+ * as in the LP format, which `Bound::default_lower` and
+ * `Bound::default_upper` spell out.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Bound<N> {
+    NegInf,
+    Finite(N),
+    PosInf,
+}
+
+impl<N> Bound<N>
+where
+    N: Num,
+{
+    /// Lower bound of variables not given one, zero.
+    pub fn default_lower() -> Self {
+        Bound::Finite(N::zero())
+    }
+
+    /// Upper bound of variables not given one, `+inf`.
+    pub fn default_upper() -> Self {
+        Bound::PosInf
+    }
+
+    /// Lower bound `lb`, `-inf` for `None`.
+    pub fn lower(lb: Option<N>) -> Self {
+        lb.map_or(Bound::NegInf, Bound::Finite)
+    }
+
+    /// Upper bound `ub`, `+inf` for `None`.
+    pub fn upper(ub: Option<N>) -> Self {
+        ub.map_or(Bound::PosInf, Bound::Finite)
+    }
+}
+
+impl<N> Bound<N> {
+    /// The value of a finite bound.
+    pub fn finite(&self) -> Option<&N> {
+        match self {
+            Bound::Finite(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// `finite` by value.
+    pub fn into_finite(self) -> Option<N> {
+        match self {
+            Bound::Finite(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> Bound<&N> {
+        match self {
+            Bound::NegInf => Bound::NegInf,
+            Bound::Finite(b) => Bound::Finite(b),
+            Bound::PosInf => Bound::PosInf,
+        }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        matches!(self, Bound::Finite(_))
+    }
+
+    /// Bound with the finite value mapped by `f`.
+    pub fn map<M>(self, f: impl FnOnce(N) -> M) -> Bound<M> {
+        match self {
+            Bound::NegInf => Bound::NegInf,
+            Bound::Finite(b) => Bound::Finite(f(b)),
+            Bound::PosInf => Bound::PosInf,
+        }
+    }
+}
+
+/// `-inf`, `+inf` or the value, as in the LP format.
+impl<N> Display for Bound<N>
+where
+    N: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bound::NegInf => f.write_str("-inf"),
+            Bound::Finite(b) => b.fmt(f),
+            Bound::PosInf => f.write_str("+inf"),
+        }
+    }
+}
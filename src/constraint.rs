@@ -1,35 +1,299 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
 use num::{Num, Signed};
 
-use crate::expression::Expression;
+use crate::{
+    expression::Expression, solution::Solution, validate::is_infinite, variable::Variable,
+};
+
+/**
+ * Relation between the two sides of a constraint
+ */
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+pub enum ConstraintSense<N>
+where
+    N: Num + Clone,
+{
+    Le,
+    Eq,
+    Ge,
+    /// `lb <= lhs <= ub`, the right-hand side is unused.
+    Range {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialization::coefficient"))]
+        lb: N,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialization::coefficient"))]
+        ub: N,
+    },
+}
+
+/**
+ * Handle to a constraint added to a model, which stays valid when other
+ * constraints are removed
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstraintId(pub(crate) usize);
+
+impl ConstraintId {
+    /// Position of the constraint in the order constraints were added, also
+    /// used for `Solution::dual_value` and default `c{index}` names.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
 
 /**
  * Contraint of an integer program
  */
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
 pub struct Constraint<N>
 where
     N: Num + Clone,
 {
     pub(crate) lhs: Expression<N>,
-    pub(crate) ord: std::cmp::Ordering,
+    pub(crate) sense: ConstraintSense<N>,
     pub(crate) rhs: Expression<N>,
 }
 
+impl<N> Constraint<N>
+where
+    N: Num + Clone,
+{
+    /// Left-hand side as built, constants included.
+    pub fn lhs(&self) -> &Expression<N> {
+        &self.lhs
+    }
+
+    /// Right-hand side as built, empty for ranges.
+    pub fn rhs(&self) -> &Expression<N> {
+        &self.rhs
+    }
+
+    /// How the left-hand side relates to the right-hand side, or the range
+    /// it lies in.
+    pub fn sense(&self) -> &ConstraintSense<N> {
+        &self.sense
+    }
+
+    /// Coefficient of `v` once every term is moved to the left-hand side,
+    /// as in `normalized`, e.g. `1` for `x` in `3 x <= 2 x + 4`.
+    pub fn coefficient(&self, v: &Variable<N>) -> N {
+        self.lhs.coefficient(v) - self.rhs.coefficient(v)
+    }
+
+    /// Canonicalizes both sides, see `Expression::canonicalize`.
+    pub fn canonicalize(&mut self) {
+        self.lhs.canonicalize();
+        self.rhs.canonicalize();
+    }
+
+    /// Copy of the constraint with `var` replaced by `replacement` on both
+    /// sides.
+    pub fn substitute(&self, var: &Variable<N>, replacement: &Expression<N>) -> Constraint<N> {
+        Constraint {
+            lhs: self.lhs.substitute(var, replacement),
+            sense: self.sense.clone(),
+            rhs: self.rhs.substitute(var, replacement),
+        }
+    }
+
+    /// Copy of the constraint with every variable replaced by `f` of it.
+    pub(crate) fn map_variables(&self, f: &impl Fn(&Variable<N>) -> Variable<N>) -> Constraint<N> {
+        Constraint {
+            lhs: self.lhs.map_variables(f),
+            sense: self.sense.clone(),
+            rhs: self.rhs.map_variables(f),
+        }
+    }
+
+    /// Copy of the constraint with every coefficient converted by `f`.
+    pub(crate) fn map_coefficients<M>(&self, f: &impl Fn(&N) -> M) -> Constraint<M>
+    where
+        M: Num + Clone,
+    {
+        Constraint {
+            lhs: self.lhs.map_coefficients(f),
+            sense: match &self.sense {
+                ConstraintSense::Le => ConstraintSense::Le,
+                ConstraintSense::Eq => ConstraintSense::Eq,
+                ConstraintSense::Ge => ConstraintSense::Ge,
+                ConstraintSense::Range { lb, ub } => ConstraintSense::Range {
+                    lb: f(lb),
+                    ub: f(ub),
+                },
+            },
+            rhs: self.rhs.map_coefficients(f),
+        }
+    }
+}
+
 impl<N> Constraint<N>
 where
     N: Num + Clone + std::fmt::Display + Signed,
 {
+    /// Moves every constant to the right-hand side, or into the range, and
+    /// merges the terms of each variable, dropping zero ones. An infinite
+    /// bound of a range is no bound, so such a range becomes a one-sided
+    /// constraint, or `0 <= 0` without either bound.
     pub fn to_normalized(self) -> Constraint<N> {
-        let Constraint { lhs, ord, rhs } = self;
-
-        let new_lhs = lhs - rhs;
-        let v = new_lhs.0.clone().into_iter().filter(|e| e.1.is_some()).collect::<Vec<_>>();
-        let c = new_lhs.0.into_iter().filter(|e| e.1.is_none()).fold(N::zero(), |r, (b, _)| r + b);
-        Self {
-            lhs: Expression(v),
-            ord,
-            rhs: Expression::from(-c)
+        self.normalized()
+    }
+
+    /// Like `to_normalized`, but copying only the coefficients, so that
+    /// exports need not clone every constraint first.
+    pub fn normalized(&self) -> Constraint<N> {
+        let mut positions = BTreeMap::<usize, usize>::new();
+        let mut terms: Vec<(N, Option<Variable<N>>)> =
+            Vec::with_capacity(self.lhs.0.len() + self.rhs.0.len());
+        let mut constant = N::zero();
+        let lhs = self.lhs.0.iter().map(|(w, v)| (w.clone(), v));
+        let rhs = self.rhs.0.iter().map(|(w, v)| (-w.clone(), v));
+        for (w, v) in lhs.chain(rhs) {
+            let Some(v) = v else {
+                constant = constant + w;
+                continue;
+            };
+            match positions.entry(v.id()) {
+                Entry::Occupied(i) => {
+                    let sum = &mut terms[*i.get()].0;
+                    *sum = std::mem::replace(sum, N::zero()) + w;
+                }
+                Entry::Vacant(i) => {
+                    i.insert(terms.len());
+                    terms.push((w, Some(v.clone())));
+                }
+            }
+        }
+        terms.retain(|(w, _)| !w.is_zero());
+        match &self.sense {
+            ConstraintSense::Range { lb, ub } => {
+                let no_lb = is_infinite(lb) && lb.is_negative();
+                let no_ub = is_infinite(ub) && ub.is_positive();
+                let (terms, sense, rhs) = match (no_lb, no_ub) {
+                    (true, true) => (Vec::new(), ConstraintSense::Le, N::zero()),
+                    (true, false) => (terms, ConstraintSense::Le, ub.clone() - constant),
+                    (false, true) => (terms, ConstraintSense::Ge, lb.clone() - constant),
+                    (false, false) => {
+                        let sense = ConstraintSense::Range {
+                            lb: lb.clone() - constant.clone(),
+                            ub: ub.clone() - constant,
+                        };
+                        return Self {
+                            lhs: Expression(terms),
+                            sense,
+                            rhs: Expression::default(),
+                        };
+                    }
+                };
+                Self {
+                    lhs: Expression(terms),
+                    sense,
+                    rhs: Expression::from(rhs),
+                }
+            }
+            sense => Self {
+                lhs: Expression(terms),
+                sense: sense.clone(),
+                rhs: Expression::from(-constant),
+            },
+        }
+    }
+
+    /// Whether the constraint holds whatever the variables are, or `None`
+    /// if a variable is left once terms of the same variable are merged,
+    /// e.g. `Some(true)` for `x - x <= 4` and `Some(false)` for `0 <= -1`.
+    pub fn trivially_holds(&self) -> Option<bool> {
+        self.normalized().normalized_trivially_holds()
+    }
+
+    /// `trivially_holds` for a constraint that is normalized already.
+    pub(crate) fn normalized_trivially_holds(&self) -> Option<bool> {
+        if !self.lhs.0.is_empty() {
+            return None;
         }
+        let rhs = self.rhs.constant();
+        Some(match &self.sense {
+            ConstraintSense::Le => !rhs.is_negative(),
+            ConstraintSense::Ge => !rhs.is_positive(),
+            ConstraintSense::Eq => rhs.is_zero(),
+            ConstraintSense::Range { lb, ub } => !lb.is_positive() && !ub.is_negative(),
+        })
+    }
+}
+
+impl<N> Constraint<N>
+where
+    N: Num + Clone + Signed + PartialOrd,
+{
+    /// By how much `sol` violates the constraint, zero when it is satisfied.
+    pub fn violation(&self, sol: &Solution<N>) -> N {
+        let lhs = sol.get_value(self.lhs.clone());
+        let rhs = sol.get_value(self.rhs.clone());
+        let excess = match &self.sense {
+            ConstraintSense::Le => lhs - rhs,
+            ConstraintSense::Ge => rhs - lhs,
+            ConstraintSense::Eq => (lhs - rhs).abs(),
+            ConstraintSense::Range { lb, ub } => {
+                let below = lb.clone() - lhs.clone();
+                let above = lhs - ub.clone();
+                if below > above {
+                    below
+                } else {
+                    above
+                }
+            }
+        };
+        if excess.is_positive() {
+            excess
+        } else {
+            N::zero()
+        }
+    }
+
+    /// Whether `sol` satisfies the constraint exactly.
+    pub fn is_satisfied_by(&self, sol: &Solution<N>) -> bool {
+        self.violation(sol).is_zero()
+    }
+
+    /// Whether `sol` violates the constraint by at most `epsilon`.
+    pub fn is_satisfied_within(&self, sol: &Solution<N>, epsilon: N) -> bool {
+        self.violation(sol) <= epsilon
+    }
+}
+
+impl<N> Constraint<N>
+where
+    N: Num + Clone + std::fmt::Display + Signed,
+{
+    /// The constraint as `Display` writes it, split between terms like
+    /// `Expression::lp_terms`, with the relation and bounds kept with the
+    /// term next to them so that wrapped rows read back as one.
+    pub(crate) fn lp_terms(&self) -> Vec<String> {
+        let mut tokens = self.lhs.lp_terms();
+        let relation = match &self.sense {
+            ConstraintSense::Le => "<=",
+            ConstraintSense::Eq => "=",
+            ConstraintSense::Ge => ">=",
+            ConstraintSense::Range { lb, ub } => {
+                tokens[0] = format!("{} <= {}", lb, tokens[0]);
+                tokens.push(format!("<= {}", ub));
+                return tokens;
+            }
+        };
+        let mut rhs = self.rhs.lp_terms();
+        rhs[0] = format!("{relation} {}", rhs[0]);
+        tokens.extend(rhs);
+        tokens
     }
 }
 
@@ -38,12 +302,45 @@ where
     N: Num + Clone + std::fmt::Display + Signed,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.lhs))?;
-        match self.ord {
-            std::cmp::Ordering::Greater => f.write_str(" >= ")?,
-            std::cmp::Ordering::Equal => f.write_str(" = ")?,
-            std::cmp::Ordering::Less => f.write_str(" <= ")?,
-        }
-        f.write_fmt(format_args!("{}", self.rhs))
+        f.write_str(&self.lp_terms().join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, ConstraintSense, Model};
+
+    #[test]
+    fn test_accessors() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let constraint = (c(3) * &x + c(1).0 + &y).le(c(2) * &x - &y + c(4).0);
+        assert_eq!(constraint.lhs().to_string(), "3 x + 1 + y");
+        assert_eq!(constraint.rhs().to_string(), "2 x - y + 4");
+        assert_eq!(constraint.sense(), &ConstraintSense::Le);
+        assert_eq!(constraint.coefficient(&x), c(1).0);
+        assert_eq!(constraint.coefficient(&y), c(2).0);
+
+        let normalized = constraint.normalized();
+        assert_eq!(normalized.lhs().to_string(), "x + 2 y");
+        assert_eq!(normalized.rhs().constant(), c(3).0);
+        assert_eq!(normalized.coefficient(&x), c(1).0);
+        assert_eq!(normalized.coefficient(&y), c(2).0);
+
+        let range = (&x + &x + c(1).0).between(c(0).0, c(5).0);
+        assert_eq!(range.rhs().num_terms(), 0);
+        assert_eq!(range.coefficient(&x), c(2).0);
+        assert_eq!(range.coefficient(&y), c(0).0);
+        assert_eq!(
+            range.normalized().sense(),
+            &ConstraintSense::Range {
+                lb: c(-1).0,
+                ub: c(4).0
+            }
+        );
     }
 }
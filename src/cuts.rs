@@ -0,0 +1,97 @@
+use num::Num;
+
+use crate::{
+    backend::{Backend, ScipBackend},
+    constraint::Constraint,
+    error::SolveError,
+    options::SolveOptions,
+    solution::Solution,
+    Model,
+};
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+    ScipBackend: Backend<N>,
+{
+    /// Solves the model, passes the solution to `separate` and adds the
+    /// constraints it returns, repeating until it returns none, e.g. to add
+    /// subtour elimination constraints only once they are violated. Also
+    /// stops after `SolveOptions::cut_rounds` rounds without adding the
+    /// last cuts, and at a solve that found no solution, which `separate`
+    /// is not called with. The cuts stay in the model, and the solution
+    /// reports how many rounds were added and whether the limit stopped
+    /// the loop. Fails with `SolveError::InvalidModel` if a cut cannot be
+    /// added, e.g. because of a non-finite coefficient.
+    pub fn solve_with_cuts(
+        &mut self,
+        opts: &SolveOptions,
+        mut separate: impl FnMut(&Solution<N>) -> Vec<Constraint<N>>,
+    ) -> Result<Solution<N>, SolveError> {
+        let mut rounds = 0;
+        loop {
+            let solution = self.solve_using(&ScipBackend, opts)?;
+            if solution.is_empty() {
+                return Ok(solution.with_cut_rounds(rounds, false));
+            }
+            let cuts = separate(&solution);
+            if cuts.is_empty() {
+                return Ok(solution.with_cut_rounds(rounds, false));
+            }
+            if opts.cut_rounds.is_some_and(|limit| rounds >= limit) {
+                return Ok(solution.with_cut_rounds(rounds, true));
+            }
+            for cut in cuts {
+                self.try_add_const(cut).map_err(SolveError::InvalidModel)?;
+            }
+            rounds += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, SolveOptions};
+
+    #[test]
+    fn test_parity_cuts() {
+        let mut model = Model::<BigRational>::new();
+        let x = model
+            .add_var()
+            .name("x")
+            .lb(c(0).0)
+            .ub(c(3).0)
+            .integer()
+            .build();
+        let y = model
+            .add_var()
+            .name("y")
+            .lb(c(0).0)
+            .ub(c(3).0)
+            .integer()
+            .build();
+        model.maximize();
+        model.set_objective(&x + &y);
+        model.add_const((&x + &y).le(c(5).0));
+
+        let mut separated = vec![];
+        let solution = model
+            .solve_with_cuts(&SolveOptions::default(), |solution| {
+                let sum = solution.get_value(&x + &y);
+                separated.push(sum.clone());
+                if sum.to_integer() % 2 == 0.into() {
+                    vec![]
+                } else {
+                    vec![(&x + &y).le(sum - c(1).0)]
+                }
+            })
+            .unwrap();
+        assert_eq!(separated, vec![c(5).0, c(4).0]);
+        assert_eq!(solution.objective_value(), Some(&c(4).0));
+        assert_eq!(solution.cut_rounds(), 1);
+        assert!(!solution.cut_limit_reached());
+        assert_eq!(model.num_constraints(), 2);
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use num::{Num, Signed};
+
+use crate::{
+    constraint::{ConstraintId, ConstraintSense},
+    Model,
+};
+
+/// Bounds a row puts on its left-hand side, `None` where it has none.
+type Interval<N> = (Option<N>, Option<N>);
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// Removes every constraint that is the same as an earlier one once both
+    /// are normalized and scaled so that the coefficient of the variable with
+    /// the lowest id is one, e.g. `2 x + 2 y <= 4` after `x + y <= 2`.
+    /// Indicator constraints and rows without variables are kept, and only
+    /// constraints of the same group are duplicates, so that turning a group
+    /// off never drops a row of another. Returns how many constraints were
+    /// removed.
+    pub fn dedup_constraints(&mut self) -> usize {
+        let mut removed = 0;
+        for rows in self.parallel_rows() {
+            for (k, (i, bounds)) in rows.iter().enumerate() {
+                let group = &self.constraints[*i].as_ref().expect("row is live").group;
+                let duplicate = rows[..k].iter().any(|(j, earlier)| {
+                    earlier == bounds
+                        && self.constraints[*j]
+                            .as_ref()
+                            .is_some_and(|c| c.group == *group)
+                });
+                if duplicate {
+                    self.constraints[*i] = None;
+                    removed += 1;
+                }
+            }
+        }
+        removed
+    }
+
+    /// Pairs of constraints, the earlier one first, whose rows are multiples
+    /// of each other but which no values satisfy together, e.g. `x + y <= 1`
+    /// and `2 x + 2 y >= 4`, so that the model is infeasible. Constraints of
+    /// disabled groups are left out.
+    pub fn conflicting_constraints(&self) -> Vec<(ConstraintId, ConstraintId)> {
+        let enabled = self
+            .enabled_constraints()
+            .map(|(i, _)| i)
+            .collect::<BTreeSet<_>>();
+        let mut pairs = vec![];
+        for rows in self.parallel_rows() {
+            let rows = rows
+                .into_iter()
+                .filter(|(i, _)| enabled.contains(i))
+                .collect::<Vec<_>>();
+            for (k, (j, b)) in rows.iter().enumerate() {
+                for (i, a) in &rows[..k] {
+                    if disjoint(a, b) {
+                        pairs.push((ConstraintId(*i), ConstraintId(*j)));
+                    }
+                }
+            }
+        }
+        pairs.sort();
+        pairs
+    }
+
+    /// Constraints without indicator whose scaled rows have the same
+    /// variable terms, with the bounds on those terms, in sets of at least
+    /// two in the order the constraints were added.
+    fn parallel_rows(&self) -> Vec<Vec<(usize, Interval<N>)>> {
+        let mut sets = BTreeMap::<Vec<(usize, String)>, Vec<_>>::new();
+        for (i, c) in self.live_constraints() {
+            if c.indicator.is_some() {
+                continue;
+            }
+            let row = c.constraint.normalized();
+            let mut lhs = row.lhs;
+            lhs.canonicalize();
+            let Some(scale) = lhs.0.first().map(|(w, _)| w.clone()) else {
+                continue;
+            };
+            let rhs = row.rhs.constant();
+            let (lb, ub) = match row.sense {
+                ConstraintSense::Le => (None, Some(rhs)),
+                ConstraintSense::Ge => (Some(rhs), None),
+                ConstraintSense::Eq => (Some(rhs.clone()), Some(rhs)),
+                ConstraintSense::Range { lb, ub } => (Some(lb), Some(ub)),
+            };
+            let scaled = |b: Option<N>| b.map(|b| b / scale.clone());
+            // dividing by a negative coefficient swaps the bounds
+            let bounds = if scale.is_negative() {
+                (scaled(ub), scaled(lb))
+            } else {
+                (scaled(lb), scaled(ub))
+            };
+            let key = lhs
+                .terms()
+                .map(|(w, v)| (v.id(), (w.clone() / scale.clone()).to_string()))
+                .collect();
+            sets.entry(key).or_default().push((i, bounds));
+        }
+        sets.into_values().filter(|rows| rows.len() > 1).collect()
+    }
+}
+
+/// Whether no value lies within both `a` and `b`.
+fn disjoint<N>(a: &Interval<N>, b: &Interval<N>) -> bool
+where
+    N: PartialOrd,
+{
+    let above =
+        |lb: &Option<N>, ub: &Option<N>| matches!((lb, ub), (Some(lb), Some(ub)) if lb > ub);
+    above(&a.0, &b.1) || above(&b.0, &a.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, rat_frac, ConstraintId, Model};
+
+    #[test]
+    fn test_dedup_constraints() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let b = model.add_var().name("b").binary().build();
+
+        let first = model.add_const((&x + &y).le(c(2).0));
+        model.add_const((&y + &x).le(c(2).0));
+        model.add_const((c(2) * &x + c(2) * &y).le(c(4).0));
+        model.add_const((-&x - &y).ge(c(-2).0));
+        let weaker = model.add_const((&x + &y).le(c(3).0));
+        let range = model.add_const((c(3) * &x + c(3) * &y + c(1).0).between(c(1).0, c(7).0));
+        model.add_const((&x + &y).between(c(0).0, c(2).0));
+        let other = model.add_const((&x - &y).le(c(2).0));
+        model
+            .add_indicator(b.clone(), true, (&x + &y).le(c(2).0))
+            .unwrap();
+        model.add_const((&x - &x).le(c(2).0));
+        model.add_const((&x - &x).le(c(2).0));
+
+        assert_eq!(model.conflicting_constraints(), []);
+        assert_eq!(model.dedup_constraints(), 4);
+        assert_eq!(model.num_constraints(), 7);
+        let kept = model.live_constraints().map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(
+            kept[..4],
+            [first, weaker, range, other].map(|id| id.index())
+        );
+        assert_eq!(model.dedup_constraints(), 0);
+    }
+
+    #[test]
+    fn test_dedup_groups() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let plain = model.add_const((&x + &y).le(c(2).0));
+        model.begin_group("a");
+        let first = model.add_const((&x + &y).le(c(2).0));
+        let doubled = model.add_const((c(2) * &x + c(2) * &y).le(c(4).0));
+        let conflict = model.add_const((&x + &y).ge(c(3).0));
+        model.end_group();
+        model.begin_group("b");
+        let other = model.add_const((&y + &x).le(c(2).0));
+        model.end_group();
+
+        assert_eq!(
+            model.conflicting_constraints(),
+            [
+                (plain, conflict),
+                (first, conflict),
+                (doubled, conflict),
+                (conflict, other)
+            ]
+        );
+        model.set_group_enabled("a", false);
+        assert_eq!(model.conflicting_constraints(), []);
+        assert_eq!(model.dedup_constraints(), 1);
+        assert_eq!(model.group_constraints("a"), [first, conflict]);
+        assert!(model.get_const(doubled).is_none());
+        assert_eq!(model.group_constraints("b"), [other]);
+        assert!(model.get_const(plain).is_some());
+    }
+
+    #[test]
+    fn test_conflicting_constraints() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let upper = model.add_const((&x + &y).le(c(1).0));
+        model.add_const((&x - &y).ge(c(5).0));
+        let lower = model.add_const((c(2) * &x + c(2) * &y).ge(c(4).0));
+        let fixed = model.add_const((c(-3) * &x - c(3) * &y).eq(c(-3).0));
+        let range = model.add_const((&x + &y).between(rat_frac(1, 2), c(1).0));
+
+        assert_eq!(
+            model.conflicting_constraints(),
+            [(upper, lower), (lower, fixed), (lower, range)]
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        model.add_const((x.clone() * 2.0).le(1.0));
+        model.add_const((x.clone() * 4.0).ge(3.0));
+        assert_eq!(
+            model.conflicting_constraints(),
+            [(ConstraintId(0), ConstraintId(1))]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::Display;
+
+use num::{Num, Signed};
+
+use crate::{constraint::ConstraintSense, Model, OptimizationDirection, VariableType};
+
+impl<N> Display for Model<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Writes the model for reading rather than for a solver, e.g.
+    ///
+    /// ```text
+    /// maximize 2 x + 5 y
+    /// Subject to:
+    ///   c0:    x + 4 y <= 24
+    ///   limit: x - y   in [-1, 5]
+    /// Variables:
+    ///   x continuous [0, +inf)
+    ///   y integer    [0, 10]
+    /// ```
+    ///
+    /// Names and left-hand sides are padded so the senses line up.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut objective = self.full_objective_terms();
+        objective.linear.0.push((self.objective_constant(), None));
+        objective.linear.simplify();
+        match self.direction {
+            OptimizationDirection::Maximize => writeln!(f, "maximize {}", objective)?,
+            OptimizationDirection::Minimize => writeln!(f, "minimize {}", objective)?,
+        }
+
+        let rows = self
+            .live_constraints()
+            .map(|(i, c)| {
+                let constraint = c.constraint.normalized();
+                let mut lhs = constraint.lhs.to_string();
+                if let Some((indicator, active_when)) = &c.indicator {
+                    lhs = format!("{} = {} -> {}", indicator, u8::from(*active_when), lhs);
+                }
+                let rest = match &constraint.sense {
+                    ConstraintSense::Le => format!("<= {}", constraint.rhs),
+                    ConstraintSense::Ge => format!(">= {}", constraint.rhs),
+                    ConstraintSense::Eq => format!("= {}", constraint.rhs),
+                    ConstraintSense::Range { lb, ub } => format!("in [{}, {}]", lb, ub),
+                };
+                (format!("{}:", self.constraint_name(i)), lhs, rest)
+            })
+            .collect::<Vec<_>>();
+        if !rows.is_empty() {
+            writeln!(f, "Subject to:")?;
+            let name_width = column_width(rows.iter().map(|r| &r.0));
+            let lhs_width = column_width(rows.iter().map(|r| &r.1));
+            for (name, lhs, rest) in rows {
+                writeln!(f, "  {:<name_width$} {:<lhs_width$} {}", name, lhs, rest)?;
+            }
+        }
+
+        let rows = self
+            .live_variables()
+            .map(|(i, v)| {
+                let (v_type, default_lb, default_ub) = match v.v_type {
+                    VariableType::Binary => ("binary", Some(N::zero()), Some(N::one())),
+                    VariableType::Integer => ("integer", None, None),
+                    VariableType::Continuous => ("continuous", None, None),
+                    VariableType::SemiContinuous => ("semi-continuous", None, None),
+                    VariableType::SemiInteger => ("semi-integer", None, None),
+                };
+                let lb = match v.lb.clone().into_finite().or(default_lb) {
+                    Some(lb) => format!("[{}", lb),
+                    None => "(-inf".to_string(),
+                };
+                let ub = match v.ub.clone().into_finite().or(default_ub) {
+                    Some(ub) => format!("{}]", ub),
+                    None => "+inf)".to_string(),
+                };
+                (self.variable_name(i), v_type, format!("{}, {}", lb, ub))
+            })
+            .collect::<Vec<_>>();
+        if !rows.is_empty() {
+            writeln!(f, "Variables:")?;
+            let name_width = column_width(rows.iter().map(|r| &r.0));
+            let type_width = column_width(rows.iter().map(|r| r.1));
+            for (name, v_type, bounds) in rows {
+                writeln!(
+                    f,
+                    "  {:<name_width$} {:<type_width$} {}",
+                    name, v_type, bounds
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Width in characters of the widest entry.
+fn column_width<S: AsRef<str>>(entries: impl Iterator<Item = S>) -> usize {
+    entries
+        .map(|s| s.as_ref().chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model};
+
+    #[test]
+    fn test_display() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model
+            .add_var()
+            .integer()
+            .lb(BigRational::new(1.into(), 3.into()))
+            .ub(c(10).0)
+            .build();
+        let b = model.add_var().name("b").binary().build();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .lb(c(2).0)
+            .ub(c(5).0)
+            .build();
+        model.maximize();
+        model.set_objective(
+            c(2) * &x + BigRational::new(3.into(), 7.into()) * &y - c(1).0 + &s * c(0).0,
+        );
+        model.add_const((&x + c(4) * &y).le(c(24).0));
+        model
+            .add_const_named("range", (&x - &b).between(c(-1).0, c(5).0))
+            .unwrap();
+        model
+            .add_indicator(b.clone(), true, (&x + &s).ge(c(3).0))
+            .unwrap();
+        assert_eq!(
+            model.to_string(),
+            "maximize 2 x + 3/7 v1 - 1\n\
+             Subject to:\n  \
+             c0:    x + 4 v1       <= 24\n  \
+             range: x - b          in [-1, 5]\n  \
+             c2:    b = 1 -> x + s >= 3\n\
+             Variables:\n  \
+             x  continuous      [0, +inf)\n  \
+             v1 integer         [1/3, 10]\n  \
+             b  binary          [0, 1]\n  \
+             s  semi-continuous [2, 5]\n"
+        );
+        assert_eq!(Model::<f64>::new().to_string(), "minimize 0\n");
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt::Display;
+
+use num::{Num, Signed};
+
+use crate::{
+    constraint::ConstraintSense, error::DualError, expression::Expression,
+    is_reserved_constraint_name, variable::Variable, Bound, Model, OptimizationDirection,
+    VariableType,
+};
+
+/// Row `terms >= rhs`, `terms <= rhs` or `terms = rhs` of the primal, with
+/// terms by variable index.
+struct Row<N>
+where
+    N: Num + Clone,
+{
+    name: String,
+    terms: Vec<(N, usize)>,
+    sense: ConstraintSense<N>,
+    rhs: N,
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// The LP dual of the model, which has the same optimal objective value
+    /// if either has one. Ranges become two rows, and so do bounds apart
+    /// from `x >= 0` and `x <= 0`, which restrict the sign instead. The dual
+    /// variable of the constraint `c` is called `y_c`, those of the two rows
+    /// of a range or of the bounds of `x` `y_c_lb`, `y_c_ub`, `y_x_lb` and
+    /// `y_x_ub`, and the dual constraint of `x` is called `x`, unless that
+    /// is reserved for unnamed constraints, see `add_const_named`. Objectives
+    /// added with `add_objective` and constraints in disabled groups are
+    /// left out.
+    pub fn dual(&self) -> Result<Model<N>, DualError> {
+        if self.has_quadratic_objective() {
+            return Err(DualError::QuadraticObjective);
+        }
+        let minimize = self.direction == OptimizationDirection::Minimize;
+        let mut rows = vec![];
+        for (i, c) in self.enabled_constraints() {
+            let name = self.constraint_name(i);
+            if c.indicator.is_some() {
+                return Err(DualError::IndicatorConstraint(name));
+            }
+            let row = c.constraint.normalized();
+            let terms = row
+                .lhs
+                .terms()
+                .map(|(w, v)| (w.clone(), v.id()))
+                .collect::<Vec<_>>();
+            match row.sense {
+                ConstraintSense::Range { lb, ub } => {
+                    rows.push(Row {
+                        name: format!("{name}_lb"),
+                        terms: terms.clone(),
+                        sense: ConstraintSense::Ge,
+                        rhs: lb,
+                    });
+                    rows.push(Row {
+                        name: format!("{name}_ub"),
+                        terms,
+                        sense: ConstraintSense::Le,
+                        rhs: ub,
+                    });
+                }
+                sense => rows.push(Row {
+                    name,
+                    terms,
+                    sense,
+                    rhs: row.rhs.constant(),
+                }),
+            }
+        }
+
+        // `Ge` for variables that are at least zero, `Le` for those that are
+        // at most zero and `Eq` for free ones
+        let mut signs: Vec<(usize, String, ConstraintSense<N>)> = vec![];
+        for (i, v) in self.live_variables() {
+            let name = self.variable_name(i);
+            if !matches!(v.v_type, VariableType::Continuous) {
+                return Err(DualError::NotContinuous(name));
+            }
+            let is_zero = |b: &Bound<N>| b.finite().is_some_and(N::is_zero);
+            let (lb, ub) = (v.lb.finite().cloned(), v.ub.finite().cloned());
+            let (sign, lb, ub) = if is_zero(&v.lb) {
+                (ConstraintSense::Ge, None, ub)
+            } else if lb.is_none() && is_zero(&v.ub) {
+                (ConstraintSense::Le, None, None)
+            } else {
+                (ConstraintSense::Eq, lb, ub)
+            };
+            for (bound, sense, suffix) in [
+                (lb, ConstraintSense::Ge, "lb"),
+                (ub, ConstraintSense::Le, "ub"),
+            ] {
+                if let Some(bound) = bound {
+                    rows.push(Row {
+                        name: format!("{name}_{suffix}"),
+                        terms: vec![(N::one(), i)],
+                        sense,
+                        rhs: bound,
+                    });
+                }
+            }
+            signs.push((i, name, sign));
+        }
+
+        let mut dual = Model::new();
+        if minimize {
+            dual.maximize();
+        }
+        let mut columns = vec![Expression::default(); self.variables.len()];
+        let mut objective = Expression::from(self.objective_constant());
+        for row in rows {
+            let y = dual.add_var().name(format!("y_{}", row.name)).free();
+            // rows that bound the objective from the side it is optimized
+            // towards get nonnegative multipliers
+            let y = match (row.sense, minimize) {
+                (ConstraintSense::Ge, true) | (ConstraintSense::Le, false) => y.lb(N::zero()),
+                (ConstraintSense::Le, true) | (ConstraintSense::Ge, false) => y.ub(N::zero()),
+                _ => y,
+            }
+            .build();
+            objective += y.clone() * row.rhs;
+            for (w, j) in row.terms {
+                columns[j] += y.clone() * w;
+            }
+        }
+        dual.set_objective(objective);
+
+        let objective = self.objective_terms();
+        for (j, name, sign) in signs {
+            let cost = objective.coefficient(&Variable::new(j, None));
+            let column = std::mem::take(&mut columns[j]);
+            let constraint = match (sign, minimize) {
+                (ConstraintSense::Ge, true) | (ConstraintSense::Le, false) => column.le(cost),
+                (ConstraintSense::Le, true) | (ConstraintSense::Ge, false) => column.ge(cost),
+                _ => column.eq(cost),
+            };
+            if is_reserved_constraint_name(&name) {
+                dual.add_const(constraint);
+            } else {
+                dual.add_const_named(name, constraint)
+                    .expect("variable names are valid and unique");
+            }
+        }
+        Ok(dual)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, DebugArtifacts, DualError, Model};
+
+    #[test]
+    fn test_dual() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        model.maximize();
+        model.set_objective(c(2) * &x + c(5) * &y + c(1).0);
+        model
+            .add_const_named("a", (&x + c(4) * &y).le(c(24).0))
+            .unwrap();
+        model
+            .add_const_named("b", (c(3) * &x + &y).le(c(21).0))
+            .unwrap();
+        model.add_const_named("c", (&x + &y).le(c(9).0)).unwrap();
+
+        let dual = model.dual().unwrap();
+        assert_eq!(
+            dual.to_lp_string().unwrap(),
+            "Minimize\n obj: 24 y_a + 21 y_b + 9 y_c\n\
+             Subject To\n x: y_a + 3 y_b + y_c >= 2\n y: 4 y_a + y_b + y_c >= 5\n\
+             Bounds\n 0 <= y_a <= +inf\n 0 <= y_b <= +inf\n 0 <= y_c <= +inf\nGeneral\nBinary\nEnd\n"
+        );
+
+        let primal = model.solve(DebugArtifacts::None).unwrap();
+        let solution = dual.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(primal.objective_value(), Some(&c(34).0));
+        assert_eq!(solution.objective_value(), primal.objective_value());
+    }
+
+    #[test]
+    fn test_dual_bounds_and_ranges() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(-1).0).ub(c(3).0).build();
+        let y = model.add_var().name("y").lb_neg_inf().ub(c(0).0).build();
+        let z = model.add_var().name("z").lb(c(0).0).ub(c(2).0).build();
+        model.set_objective(&x - &y + c(3) * &z);
+        model
+            .add_const_named("r", (&x + &y + &z + c(1).0).between(c(0).0, c(5).0))
+            .unwrap();
+        model.add_const_named("e", (&x - &z).eq(c(1).0)).unwrap();
+
+        let lp = model.dual().unwrap().to_lp_string().unwrap();
+        assert!(
+            lp.starts_with(
+                "Maximize\n obj: -y_r_lb + 4 y_r_ub + y_e - y_x_lb + 3 y_x_ub + 2 y_z_ub\n"
+            ),
+            "{lp}"
+        );
+        assert!(
+            lp.contains(
+                " x: y_r_lb + y_r_ub + y_e + y_x_lb + y_x_ub = 1\n \
+                 y: y_r_lb + y_r_ub >= -1\n \
+                 z: y_r_lb + y_r_ub - y_e + y_z_ub <= 3\n"
+            ),
+            "{lp}"
+        );
+        assert!(
+            lp.contains(" 0 <= y_r_lb <= +inf\n -inf <= y_r_ub <= 0\n y_e free\n"),
+            "{lp}"
+        );
+    }
+
+    #[test]
+    fn test_dual_errors() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        model.add_var().name("b").binary().build();
+        model.add_const_named("on", x.clone().le(c(1).0)).unwrap();
+        assert_eq!(
+            model.dual().err(),
+            Some(DualError::NotContinuous("b".to_string()))
+        );
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let b = model.add_var().name("b").binary().build();
+        model.add_indicator(b, true, x.le(c(1).0)).unwrap();
+        assert!(matches!(
+            model.dual(),
+            Err(DualError::IndicatorConstraint(_))
+        ));
+    }
+}
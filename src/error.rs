@@ -0,0 +1,475 @@
+use std::{path::PathBuf, process::ExitStatus};
+
+use crate::options::Solver;
+
+/**
+ * Error produced while solving a model
+ */
+#[derive(Debug)]
+pub enum SolveError {
+    /// The solver executable could not be found.
+    SolverNotFound(PathBuf),
+    Io(std::io::Error),
+    /// The solver exited with a failure status.
+    SolverFailed {
+        status: ExitStatus,
+        stderr: String,
+    },
+    /// The solver exited successfully without writing a solution file.
+    /// Holds the last status line of its log, if any.
+    NoSolutionFile {
+        status_line: Option<String>,
+    },
+    SolutionParse(String),
+    /// Duals were requested for a model with integer or binary variables,
+    /// named here.
+    DualsRequireLp(String),
+    /// A parameter set with `Model::set_param` has no equivalent in the
+    /// solver.
+    UnsupportedParameter {
+        solver: Solver,
+        param: String,
+    },
+    /// An exact model has a semi-continuous variable, named here, with a
+    /// bound that is not an integer, which the LP format cannot express.
+    FractionalBound(String),
+    /// The solve was cancelled through its `SolveHandle` and the solver
+    /// stopped without a result.
+    Cancelled,
+    /// The model has a quadratic objective, which the named kind of solve
+    /// does not support.
+    UnsupportedQuadraticObjective(String),
+    /// `Model::validate` found a problem before the solver was started.
+    InvalidModel(ModelError),
+    /// SCIP did not accept `set exact enabled TRUE`, as it was built without
+    /// exact solving. `Model::set_exact` allows solving in floating point.
+    ExactModeUnavailable,
+    /// A number of the solution, given here, does not fit the coefficient
+    /// type of the model, e.g. a value of a `Model<Rational64>`.
+    Overflow(String),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::SolverNotFound(solver) => f.write_fmt(format_args!(
+                "solver executable `{}` not found",
+                solver.display()
+            )),
+            SolveError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            SolveError::SolverFailed { status, stderr } => {
+                f.write_fmt(format_args!("solver failed with {}", status))?;
+                if !stderr.is_empty() {
+                    f.write_fmt(format_args!(": {}", stderr.trim_end()))?;
+                }
+                Ok(())
+            }
+            SolveError::NoSolutionFile { status_line } => {
+                f.write_str("solver exited successfully but produced no solution file")?;
+                match status_line {
+                    Some(line) => f.write_fmt(format_args!("; status line was `{}`", line)),
+                    None => Ok(()),
+                }
+            }
+            SolveError::SolutionParse(line) => {
+                f.write_fmt(format_args!("could not parse solution line `{}`", line))
+            }
+            SolveError::DualsRequireLp(name) => f.write_fmt(format_args!(
+                "duals are only available for LPs, but `{}` is integral",
+                name
+            )),
+            SolveError::UnsupportedParameter { solver, param } => f.write_fmt(format_args!(
+                "parameter `{}` is not supported by {:?}",
+                param, solver
+            )),
+            SolveError::FractionalBound(name) => f.write_fmt(format_args!(
+                "bound of semi-continuous variable `{}` is not an integer",
+                name
+            )),
+            SolveError::Cancelled => f.write_str("solve was cancelled"),
+            SolveError::UnsupportedQuadraticObjective(solve) => f.write_fmt(format_args!(
+                "quadratic objectives are not supported by {}",
+                solve
+            )),
+            SolveError::InvalidModel(e) => f.write_fmt(format_args!("invalid model: {}", e)),
+            SolveError::ExactModeUnavailable => {
+                f.write_str("the SCIP executable does not support exact solving")
+            }
+            SolveError::Overflow(value) => f.write_fmt(format_args!(
+                "solution value `{}` does not fit the coefficient type",
+                value
+            )),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SolveError::Io(e) => Some(e),
+            SolveError::InvalidModel(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SolveError {
+    fn from(e: std::io::Error) -> Self {
+        SolveError::Io(e)
+    }
+}
+
+/**
+ * Error produced while building a model
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum ModelError {
+    /// The name is not a legal identifier in the LP format.
+    InvalidName(String),
+    DuplicateName(String),
+    /// The constraint name is `obj` or `c` followed by digits, which model
+    /// files use for the objective and unnamed constraints.
+    ReservedName(String),
+    /// An indicator variable was not declared binary.
+    NotBinary(String),
+    /// Ranged constraints cannot be used as indicator constraints.
+    RangeNotSupported,
+    /// A semi-continuous or semi-integer variable has no upper bound.
+    MissingUpperBound(String),
+    /// The constraint with this index was removed.
+    RemovedConstraint(usize),
+    /// A big-M could not be derived because the variable is unbounded.
+    UnboundedVariable(String),
+    /// The variable occurs in its replacement, is an indicator, or is
+    /// integral or semi-continuous.
+    CannotSubstitute(String),
+    /// A binary variable was given a bound other than 0 or 1.
+    InvalidBinaryValue(String),
+    /// No variable with this index belongs to the model.
+    UnknownVariable(usize),
+    /// A product of the two named variables is left where a linear
+    /// expression is needed.
+    Nonlinear(String),
+    /// The expression written here was divided by zero.
+    DivisionByZero(String),
+    /// The lower bound of the named variable exceeds its upper bound.
+    InvertedBounds(String),
+    /// Coefficients that are infinite or NaN, in the objective, a
+    /// constraint or the bounds of a variable as `row` says.
+    NonFiniteCoefficients {
+        row: String,
+        terms: Vec<String>,
+    },
+    /// The breakpoints of a piecewise-linear function cannot be used, for
+    /// the reason given.
+    InvalidBreakpoints(String),
+    /// The named constraint has no variables left and can never hold, which
+    /// model files cannot express.
+    InfeasibleConstraint(String),
+    /// A coefficient, right-hand side or bound is the fraction written
+    /// here, which MPS files cannot hold.
+    FractionalNumber(String),
+}
+
+impl std::fmt::Display for ModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::InvalidName(name) => {
+                f.write_fmt(format_args!("`{}` is not a valid LP name", name))
+            }
+            ModelError::DuplicateName(name) => {
+                f.write_fmt(format_args!("name `{}` is already in use", name))
+            }
+            ModelError::ReservedName(name) => f.write_fmt(format_args!(
+                "name `{}` is reserved for the objective or unnamed constraints",
+                name
+            )),
+            ModelError::NotBinary(name) => {
+                f.write_fmt(format_args!("variable `{}` is not binary", name))
+            }
+            ModelError::RangeNotSupported => {
+                f.write_str("ranged constraints are not supported here")
+            }
+            ModelError::MissingUpperBound(name) => {
+                f.write_fmt(format_args!("variable `{}` needs an upper bound", name))
+            }
+            ModelError::RemovedConstraint(index) => {
+                f.write_fmt(format_args!("constraint {} was removed", index))
+            }
+            ModelError::UnboundedVariable(name) => {
+                f.write_fmt(format_args!("variable `{}` needs finite bounds", name))
+            }
+            ModelError::CannotSubstitute(name) => {
+                f.write_fmt(format_args!("variable `{}` cannot be substituted", name))
+            }
+            ModelError::InvalidBinaryValue(name) => f.write_fmt(format_args!(
+                "bounds of binary variable `{}` can only be 0 or 1",
+                name
+            )),
+            ModelError::UnknownVariable(index) => f.write_fmt(format_args!(
+                "no variable with index {} in the model",
+                index
+            )),
+            ModelError::Nonlinear(product) => {
+                f.write_fmt(format_args!("`{}` is not linear", product))
+            }
+            ModelError::DivisionByZero(expr) => {
+                f.write_fmt(format_args!("division of `{}` by zero", expr))
+            }
+            ModelError::InvertedBounds(name) => f.write_fmt(format_args!(
+                "lower bound of `{}` exceeds its upper bound",
+                name
+            )),
+            ModelError::NonFiniteCoefficients { row, terms } => f.write_fmt(format_args!(
+                "non-finite coefficients in {}: {}",
+                row,
+                terms.join(", ")
+            )),
+            ModelError::InvalidBreakpoints(reason) => {
+                f.write_fmt(format_args!("invalid breakpoints: {}", reason))
+            }
+            ModelError::InfeasibleConstraint(name) => {
+                f.write_fmt(format_args!("constraint `{}` can never hold", name))
+            }
+            ModelError::FractionalNumber(number) => f.write_fmt(format_args!(
+                "`{}` is not an integer or decimal number",
+                number
+            )),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+/// Model files fail to export with an `InvalidInput` error holding the
+/// `ModelError`, which `std::io::Error::get_ref` gives back.
+impl From<ModelError> for std::io::Error {
+    fn from(e: ModelError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+    }
+}
+
+/**
+ * Error produced while evaluating an expression
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum EvaluationError {
+    /// No value was given for the named variable.
+    MissingValue(String),
+}
+
+impl std::fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvaluationError::MissingValue(name) => {
+                f.write_fmt(format_args!("no value for variable `{}`", name))
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/**
+ * Reason `Model::dual` cannot build the dual of a model, which has to be an
+ * LP
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum DualError {
+    /// The named variable is integer, binary, semi-continuous or
+    /// semi-integer.
+    NotContinuous(String),
+    /// The named constraint has an indicator.
+    IndicatorConstraint(String),
+    QuadraticObjective,
+}
+
+impl std::fmt::Display for DualError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DualError::NotContinuous(name) => f.write_fmt(format_args!(
+                "variable `{}` is not continuous, so the model is no LP",
+                name
+            )),
+            DualError::IndicatorConstraint(name) => f.write_fmt(format_args!(
+                "constraint `{}` has an indicator, so the model is no LP",
+                name
+            )),
+            DualError::QuadraticObjective => {
+                f.write_str("the objective is quadratic, so the model is no LP")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DualError {}
+
+/**
+ * Variables without a value in a solution, named in order of appearance
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub struct MissingVariables(pub Vec<String>);
+
+impl std::fmt::Display for MissingVariables {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("no value for ")?;
+        for (i, name) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("`{}`", name))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MissingVariables {}
+
+/**
+ * Value of an integer, binary or semi-integer variable that is not within
+ * the tolerance of an integer
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegralityError {
+    pub variable: String,
+    pub value: f64,
+}
+
+impl std::fmt::Display for IntegralityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "integral variable `{}` has value {}",
+            self.variable, self.value
+        ))
+    }
+}
+
+impl std::error::Error for IntegralityError {}
+
+/**
+ * Error produced while reading a model in the LP format
+ */
+#[derive(Debug)]
+pub enum LpParseError {
+    Io(std::io::Error),
+    /// What is wrong with the given line, counting from 1.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for LpParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LpParseError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            LpParseError::Syntax { line, message } => {
+                f.write_fmt(format_args!("line {}: {}", line, message))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LpParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LpParseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LpParseError {
+    fn from(e: std::io::Error) -> Self {
+        LpParseError::Io(e)
+    }
+}
+
+/**
+ * Error produced while reading a model written by `Model::to_bytes`
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input does not start with the header of an encoded model.
+    NotAModel,
+    /// The input was written in this format version, which this version of
+    /// the crate cannot read.
+    UnsupportedVersion(u8),
+    /// The input ends in the middle of the model.
+    UnexpectedEnd,
+    /// The input holds something no model is encoded as, described here.
+    Invalid(String),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::NotAModel => f.write_str("not an encoded model"),
+            DecodeError::UnsupportedVersion(version) => f.write_fmt(format_args!(
+                "unsupported model format version {}, expected {}",
+                version,
+                crate::binary::FORMAT_VERSION
+            )),
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of the encoded model"),
+            DecodeError::Invalid(message) => {
+                f.write_fmt(format_args!("invalid encoded model: {}", message))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/**
+ * Error produced while parsing an expression or constraint from a string.
+ * Positions are byte offsets into the input.
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedToken {
+        position: usize,
+        token: String,
+    },
+    /// The input ended where a term was expected.
+    UnexpectedEnd,
+    InvalidNumber {
+        position: usize,
+        token: String,
+    },
+    /// The model has no variable by this name.
+    UnknownVariable {
+        position: usize,
+        name: String,
+    },
+    /// A variable to be created cannot have this name.
+    InvalidName {
+        position: usize,
+        name: String,
+    },
+    /// A constraint without `<=`, `>=` or `=`.
+    MissingRelation,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { position, token } => {
+                f.write_fmt(format_args!("unexpected `{}` at {}", token, position))
+            }
+            ParseError::UnexpectedEnd => f.write_str("unexpected end of input"),
+            ParseError::InvalidNumber { position, token } => {
+                f.write_fmt(format_args!("`{}` at {} is not a number", token, position))
+            }
+            ParseError::UnknownVariable { position, name } => {
+                f.write_fmt(format_args!("unknown variable `{}` at {}", name, position))
+            }
+            ParseError::InvalidName { position, name } => f.write_fmt(format_args!(
+                "`{}` at {} is not a valid variable name",
+                name, position
+            )),
+            ParseError::MissingRelation => f.write_str("expected `<=`, `>=` or `=`"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
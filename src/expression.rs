@@ -1,9 +1,30 @@
-use crate::{constraint::Constraint, variable::Variable};
-use num::{Num, Signed};
-use std::{fmt::Display, ops::*};
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    error::{EvaluationError, ModelError},
+    variable::Variable,
+};
+use num::{
+    rational::{Rational32, Rational64},
+    BigInt, BigRational, Num, Signed,
+};
+use std::{collections::BTreeMap, fmt::Display, iter::Sum, ops::*};
 
-#[derive(Clone)]
-pub struct Expression<N>(pub(crate) Vec<(N, Option<Variable<N>>)>)
+/**
+ * Sum of variables times coefficients and constants
+ *
+ * Expressions are compared and hashed term by term, so two of them are only
+ * equal as functions once both are canonicalized.
+ */
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+pub struct Expression<N>(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::terms"))]
+    pub(crate)  Vec<(N, Option<Variable<N>>)>,
+)
 where
     N: Num + Clone;
 
@@ -11,10 +32,32 @@ impl<N> Expression<N>
 where
     N: Num + Clone,
 {
+    /// Empty expression with room for `terms` terms, e.g. to build a long
+    /// sum by folding without reallocating.
+    pub fn with_capacity(terms: usize) -> Self {
+        Expression(Vec::with_capacity(terms))
+    }
+
+    /// Makes room for at least `additional` more terms.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Appends the terms of `rhs`, keeping whichever of the two term
+    /// vectors has the larger capacity.
+    fn append(&mut self, mut rhs: Expression<N>) {
+        if rhs.0.capacity() > self.0.capacity() {
+            let terms = std::mem::replace(&mut self.0, rhs.0);
+            self.0.splice(0..0, terms);
+        } else {
+            self.0.append(&mut rhs.0);
+        }
+    }
+
     pub fn le(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
         Constraint {
             lhs: self,
-            ord: std::cmp::Ordering::Less,
+            sense: ConstraintSense::Le,
             rhs: expr.into(),
         }
     }
@@ -22,7 +65,7 @@ where
     pub fn eq(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
         Constraint {
             lhs: self,
-            ord: std::cmp::Ordering::Equal,
+            sense: ConstraintSense::Eq,
             rhs: expr.into(),
         }
     }
@@ -30,36 +73,193 @@ where
     pub fn ge(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
         Constraint {
             lhs: self,
-            ord: std::cmp::Ordering::Greater,
+            sense: ConstraintSense::Ge,
             rhs: expr.into(),
         }
     }
-}
 
-impl<N> std::fmt::Display for Expression<N>
-where
-    N: Num + Clone + Display + Signed,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut iter = self.0.iter().peekable();
+    /// Constraint `lb <= self <= ub`, exported as a single ranged row.
+    pub fn between(self, lb: N, ub: N) -> Constraint<N> {
+        Constraint {
+            lhs: self,
+            sense: ConstraintSense::Range { lb, ub },
+            rhs: Expression::default(),
+        }
+    }
+
+    /// Value of the expression with every variable replaced by its entry in
+    /// `values`, keyed by variable name.
+    pub fn try_evaluate(&self, values: &BTreeMap<String, N>) -> Result<N, EvaluationError> {
+        self.0.iter().try_fold(N::zero(), |acc, (w, v)| {
+            let value = match v {
+                Some(v) => values
+                    .get(v.name_ref().as_ref())
+                    .ok_or_else(|| EvaluationError::MissingValue(v.name()))?,
+                None => return Ok(acc + w.clone()),
+            };
+            Ok(acc + w.clone() * value.clone())
+        })
+    }
+
+    /// Like `try_evaluate`, but variables without a value count as zero.
+    pub fn evaluate_or_zero(&self, values: &BTreeMap<String, N>) -> N {
+        self.0.iter().fold(N::zero(), |acc, (w, v)| match v {
+            Some(v) => match values.get(v.name_ref().as_ref()) {
+                Some(value) => acc + w.clone() * value.clone(),
+                None => acc,
+            },
+            None => acc + w.clone(),
+        })
+    }
+
+    /// Variable terms in the order they were added. A variable can appear
+    /// more than once until the expression is simplified.
+    pub fn terms(&self) -> impl Iterator<Item = (&N, &Variable<N>)> {
+        self.0
+            .iter()
+            .filter_map(|(w, v)| v.as_ref().map(|v| (w, v)))
+    }
+
+    /// Number of variable terms, counting repeated variables separately.
+    pub fn num_terms(&self) -> usize {
+        self.terms().count()
+    }
 
-        let Some(e) = iter.next() else { return Ok(()) };
+    /// Sum of the constant terms.
+    pub fn constant(&self) -> N {
+        self.0
+            .iter()
+            .filter(|(_, v)| v.is_none())
+            .fold(N::zero(), |acc, (w, _)| acc + w.clone())
+    }
 
-        f.write_fmt(format_args!("{:.64}", e.0))?;
-        if let Some(v) = &e.1 {
-            f.write_fmt(format_args!(" {}", v))?;
+    /// Total coefficient of `v`, summed over all of its terms.
+    pub fn coefficient(&self, v: &Variable<N>) -> N {
+        self.terms()
+            .filter(|(_, u)| u.id() == v.id())
+            .fold(N::zero(), |acc, (w, _)| acc + w.clone())
+    }
+
+    /// Copy of the expression with every term of `var` replaced by
+    /// `replacement` scaled by the term's coefficient.
+    pub fn substitute(&self, var: &Variable<N>, replacement: &Expression<N>) -> Expression<N> {
+        let mut result = Expression::default();
+        for (w, v) in self.0.iter() {
+            match v {
+                Some(v) if v.id() == var.id() => result += replacement.clone() * w.clone(),
+                _ => result.0.push((w.clone(), v.clone())),
+            }
         }
-        for e in iter {
-            if e.0 == e.0.abs() {
-                f.write_fmt(format_args!(" + {:.64}", e.0))?;
+        result
+    }
+
+    /// Copy of the expression with every variable replaced by `f` of it.
+    pub(crate) fn map_variables(&self, f: &impl Fn(&Variable<N>) -> Variable<N>) -> Expression<N> {
+        Expression(
+            self.0
+                .iter()
+                .map(|(w, v)| (w.clone(), v.as_ref().map(f)))
+                .collect(),
+        )
+    }
+
+    /// Copy of the expression with every coefficient converted by `f`.
+    pub(crate) fn map_coefficients<M>(&self, f: &impl Fn(&N) -> M) -> Expression<M>
+    where
+        M: Num + Clone,
+    {
+        Expression(
+            self.0
+                .iter()
+                .map(|(w, v)| (f(w), v.as_ref().map(Variable::cast)))
+                .collect(),
+        )
+    }
+
+    /// Merges the coefficients of repeated variables and constants and drops
+    /// zero terms, keeping the order in which terms first appeared.
+    pub fn simplify(&mut self) {
+        let mut positions = BTreeMap::<Option<usize>, usize>::new();
+        let mut terms: Vec<(N, Option<Variable<N>>)> = Vec::with_capacity(self.0.len());
+        for (w, v) in std::mem::take(&mut self.0) {
+            let key = v.as_ref().map(Variable::id);
+            if let Some(&i) = positions.get(&key) {
+                terms[i].0 = terms[i].0.clone() + w;
             } else {
-                f.write_fmt(format_args!(" - {:.64}", e.0.abs()))?;
-            }
-            if let Some(v) = &e.1 {
-                f.write_fmt(format_args!(" {}", v))?;
+                positions.insert(key, terms.len());
+                terms.push((w, v));
             }
         }
-        Ok(())
+        terms.retain(|(w, _)| !w.is_zero());
+        self.0 = terms;
+    }
+
+    /// Simplifies the expression and orders the terms by variable id, with
+    /// the constant last, so that expressions with the same coefficients
+    /// have the same terms, e.g. to compare or hash them.
+    pub fn canonicalize(&mut self) {
+        self.simplify();
+        self.0
+            .sort_by_key(|(_, v)| v.as_ref().map_or(usize::MAX, Variable::id));
+    }
+}
+
+impl<N> Expression<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Every term divided by `rhs`, failing if `rhs` is zero.
+    pub fn try_div(self, rhs: N) -> Result<Expression<N>, ModelError> {
+        if rhs.is_zero() {
+            return Err(ModelError::DivisionByZero(self.to_string()));
+        }
+        Ok(self * (N::one() / rhs))
+    }
+
+    /// The terms as `Display` writes them, e.g. `-x`, `+ 2 y` and `- 3` for
+    /// `-x + 2 y - 3`, for writers that break long lines between terms.
+    pub(crate) fn lp_terms(&self) -> Vec<String> {
+        let mut terms = self.0.iter().filter(|(w, _)| !w.is_zero()).peekable();
+        if terms.peek().is_none() {
+            return vec!["0".to_string()];
+        }
+        terms
+            .enumerate()
+            .map(|(i, (w, v))| {
+                let sign = lp_sign(i, w);
+                let magnitude = w.abs();
+                match v {
+                    Some(v) if magnitude.is_one() => format!("{sign}{v}"),
+                    Some(v) => format!("{sign}{magnitude:.64} {v}"),
+                    None => format!("{sign}{magnitude:.64}"),
+                }
+            })
+            .collect()
+    }
+}
+
+/// What the `i`th term with coefficient `w` starts with in LP syntax, where
+/// only a negative first term has a sign.
+pub(crate) fn lp_sign<N>(i: usize, w: &N) -> &'static str
+where
+    N: Signed,
+{
+    match (i, w.is_negative()) {
+        (0, false) => "",
+        (0, true) => "-",
+        (_, false) => "+ ",
+        (_, true) => "- ",
+    }
+}
+
+impl<N> std::fmt::Display for Expression<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Writes the terms in LP syntax, e.g. `-x + 2 y - 3`, leaving out zero
+    /// terms and unit coefficients. An expression without terms is `0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lp_terms().join(" "))
     }
 }
 
@@ -90,6 +290,24 @@ where
     }
 }
 
+impl<N> From<&Variable<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    fn from(v: &Variable<N>) -> Self {
+        Expression::from(v.clone())
+    }
+}
+
+impl<N> From<&Expression<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    fn from(e: &Expression<N>) -> Self {
+        e.clone()
+    }
+}
+
 impl<IntoExpression, N> Add<IntoExpression> for Expression<N>
 where
     N: Num + Clone,
@@ -97,7 +315,7 @@ where
 {
     type Output = Self;
     fn add(mut self, rhs: IntoExpression) -> Self::Output {
-        self.0.extend(rhs.into().0);
+        self.append(rhs.into());
         self
     }
 }
@@ -113,6 +331,46 @@ where
     }
 }
 
+impl<IntoExpression, N> AddAssign<IntoExpression> for Expression<N>
+where
+    N: Num + Clone,
+    IntoExpression: Into<Expression<N>>,
+{
+    fn add_assign(&mut self, rhs: IntoExpression) {
+        self.append(rhs.into());
+    }
+}
+
+impl<IntoExpression, N> SubAssign<IntoExpression> for Expression<N>
+where
+    N: Num + Clone,
+    IntoExpression: Into<Expression<N>>,
+{
+    fn sub_assign(&mut self, rhs: IntoExpression) {
+        self.add_assign(rhs.into().neg());
+    }
+}
+
+impl<N> Sum<Expression<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    fn sum<I: Iterator<Item = Expression<N>>>(iter: I) -> Self {
+        let mut result = iter.fold(Expression::default(), Add::add);
+        result.simplify();
+        result
+    }
+}
+
+impl<N> Sum<Variable<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    fn sum<I: Iterator<Item = Variable<N>>>(iter: I) -> Self {
+        iter.map(Expression::from).sum()
+    }
+}
+
 impl<N> Neg for Expression<N>
 where
     N: Num + Clone,
@@ -141,7 +399,275 @@ where
     N: Num + Clone,
 {
     type Output = Self;
+    /// Divides every term by `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero, see `try_div`.
     fn div(self, rhs: N) -> Self::Output {
+        if rhs.is_zero() {
+            // coefficients need not be printable, so name the variables only
+            let variables: Vec<_> = self
+                .0
+                .iter()
+                .flat_map(|(_, v)| v)
+                .map(Variable::to_string)
+                .collect();
+            panic!(
+                "division of an expression in `{}` by zero",
+                variables.join(", ")
+            );
+        }
         self * (N::one() / rhs)
     }
 }
+
+impl<IntoExpression, N> Add<IntoExpression> for &Expression<N>
+where
+    N: Num + Clone,
+    IntoExpression: Into<Expression<N>>,
+{
+    type Output = Expression<N>;
+    fn add(self, rhs: IntoExpression) -> Self::Output {
+        self.clone().add(rhs)
+    }
+}
+
+impl<IntoExpression, N> Sub<IntoExpression> for &Expression<N>
+where
+    N: Num + Clone,
+    IntoExpression: Into<Expression<N>>,
+{
+    type Output = Expression<N>;
+    fn sub(self, rhs: IntoExpression) -> Self::Output {
+        self.clone().sub(rhs)
+    }
+}
+
+impl<N> Neg for &Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn neg(self) -> Self::Output {
+        self.clone().neg()
+    }
+}
+
+impl<N> Mul<N> for &Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn mul(self, rhs: N) -> Self::Output {
+        self.clone().mul(rhs)
+    }
+}
+
+impl<N> Div<N> for &Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn div(self, rhs: N) -> Self::Output {
+        self.clone().div(rhs)
+    }
+}
+
+/// Implements `scalar * Variable` and `scalar * Expression` for coefficient
+/// types, which the orphan rule prevents from being written generically.
+macro_rules! impl_scalar_mul {
+    ($($n:ty),*) => {$(
+        impl Mul<Expression<$n>> for $n {
+            type Output = Expression<$n>;
+            fn mul(self, rhs: Expression<$n>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl Mul<Variable<$n>> for $n {
+            type Output = Expression<$n>;
+            fn mul(self, rhs: Variable<$n>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl Mul<&Expression<$n>> for $n {
+            type Output = Expression<$n>;
+            fn mul(self, rhs: &Expression<$n>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl Mul<&Variable<$n>> for $n {
+            type Output = Expression<$n>;
+            fn mul(self, rhs: &Variable<$n>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
+}
+
+impl_scalar_mul!(BigRational, Rational32, Rational64, f64);
+
+/// Exact integer coefficients for `BigRational` models, so `x * 3` and
+/// `3 * x` need no conversion. Only `i64` is covered, otherwise integer
+/// literals would be ambiguous.
+impl Mul<i64> for Expression<BigRational> {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self::Output {
+        self * BigRational::from(BigInt::from(rhs))
+    }
+}
+
+impl Mul<i64> for Variable<BigRational> {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Expression::from(self) * rhs
+    }
+}
+
+impl Mul<Expression<BigRational>> for i64 {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: Expression<BigRational>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<Variable<BigRational>> for i64 {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: Variable<BigRational>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<i64> for &Expression<BigRational> {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.clone() * rhs
+    }
+}
+
+impl Mul<i64> for &Variable<BigRational> {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.clone() * rhs
+    }
+}
+
+impl Mul<&Expression<BigRational>> for i64 {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: &Expression<BigRational>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<&Variable<BigRational>> for i64 {
+    type Output = Expression<BigRational>;
+    fn mul(self, rhs: &Variable<BigRational>) -> Self::Output {
+        rhs * self
+    }
+}
+
+/// Integer constants for `BigRational` models, so `x.le(24)` and `x + 1`
+/// need no conversion.
+impl From<i64> for Expression<BigRational> {
+    fn from(bias: i64) -> Self {
+        Expression::from(BigRational::from(BigInt::from(bias)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, rat, rat_frac, Expression, Model};
+
+    #[test]
+    fn test_accessors() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let z = model.add_var().name("z").build();
+
+        let e = c(2) * &x + c(3).0 + &y - c(5) * &x - c(1).0 + c(0) * &y;
+        assert_eq!(e.num_terms(), 4);
+        assert_eq!(
+            e.terms()
+                .map(|(w, v)| format!("{} {}", w, v))
+                .collect::<Vec<_>>(),
+            ["2 x", "1 y", "-5 x", "0 y"]
+        );
+        assert_eq!(e.constant(), c(2).0);
+        assert_eq!(e.coefficient(&x), c(-3).0);
+        assert_eq!(e.coefficient(&y), c(1).0);
+        assert_eq!(e.coefficient(&z), c(0).0);
+
+        let e = Expression::from(c(4).0);
+        assert_eq!(e.num_terms(), 0);
+        assert_eq!(e.constant(), c(4).0);
+        assert_eq!(Expression::<BigRational>::default().constant(), c(0).0);
+
+        let e = &x * 2 + 1;
+        assert_eq!(e.constant(), rat(1));
+        assert_eq!(y.le(24).to_string(), "y <= 24");
+        assert_eq!(rat_frac(2, -6).to_string(), "-1/3");
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let mut a = &y + c(2).0 + c(3) * &x - &y + &y - c(3) * &x + &x;
+        let mut b = &x + &y + c(2).0;
+        assert_ne!(a, b);
+        a.canonicalize();
+        b.canonicalize();
+        assert_eq!(a, b);
+        assert_eq!(a.to_string(), "x + y + 2");
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut model = Model::<BigRational>::with_capacity(1, 1);
+        model.reserve_vars(1);
+        model.reserve_constraints(1);
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let sum = (0..100).fold(Expression::with_capacity(100), |acc, _| acc + &x);
+        assert_eq!(sum.0.capacity(), 100);
+
+        // the larger buffer is kept, with the terms of the left side first
+        let mut long = Expression::with_capacity(16);
+        long += c(2) * &x;
+        let buffer = long.0.as_ptr();
+        let e = &y + rat(1) + long;
+        assert_eq!(e.0.as_ptr(), buffer);
+        assert_eq!(e.to_string(), "y + 1 + 2 x");
+
+        let mut e = Expression::from(&y);
+        let mut long = Expression::from(&x);
+        long.reserve(8);
+        let buffer = long.0.as_ptr();
+        e -= long;
+        assert_eq!(e.0.as_ptr(), buffer);
+        assert_eq!(e.to_string(), "y - x");
+    }
+
+    #[test]
+    fn test_substitute() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let e = c(3) * &y + &x - &y + c(1).0;
+        let e = e.substitute(&y, &(c(2) * &x + c(1).0));
+        assert_eq!(e.to_string(), "6 x + 3 + x - 2 x - 1 + 1");
+        assert_eq!(e.coefficient(&x), c(5).0);
+        assert_eq!(e.coefficient(&y), c(0).0);
+        assert_eq!(e.constant(), c(3).0);
+    }
+}
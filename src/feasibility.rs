@@ -0,0 +1,214 @@
+use num::{Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    solution::Solution,
+    variable::Variable,
+    InternalConstraint, Model,
+};
+
+/**
+ * Constraint or bound not satisfied by a solution
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation<N> {
+    Constraint {
+        index: usize,
+        name: String,
+        amount: N,
+    },
+    Bound {
+        index: usize,
+        name: String,
+        amount: N,
+    },
+}
+
+impl<N> Solution<N>
+where
+    N: Num + Clone + Signed + PartialOrd,
+{
+    /// How far `c` is from binding at this solution, negative when it is
+    /// violated: `rhs - lhs` for `<=` and `=`, `lhs - rhs` for `>=`, and the
+    /// smaller of the two `range_slacks` for ranges.
+    pub fn slack(&self, c: &Constraint<N>) -> N {
+        if let Some((below, above)) = self.range_slacks(c) {
+            return if below < above { below } else { above };
+        }
+        let lhs = self.get_value(c.lhs.clone());
+        let rhs = self.get_value(c.rhs.clone());
+        match c.sense {
+            ConstraintSense::Ge => lhs - rhs,
+            _ => rhs - lhs,
+        }
+    }
+
+    /// Slacks `lhs - lb` and `ub - lhs` to both ends of a range
+    /// constraint, `None` for other constraints.
+    pub fn range_slacks(&self, c: &Constraint<N>) -> Option<(N, N)> {
+        let ConstraintSense::Range { lb, ub } = &c.sense else {
+            return None;
+        };
+        let lhs = self.get_value(c.lhs.clone()) - self.get_value(c.rhs.clone());
+        Some((lhs.clone() - lb.clone(), ub.clone() - lhs))
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Signed + PartialOrd,
+{
+    /// Whether `c` is enforced at `sol`, which indicator constraints are
+    /// only when their indicator takes the value they are active for.
+    fn is_enforced(c: &InternalConstraint<N>, sol: &Solution<N>) -> bool {
+        match &c.indicator {
+            Some((indicator, active_when)) => {
+                let active = if *active_when { N::one() } else { N::zero() };
+                sol.get_value(indicator.clone()) == active
+            }
+            None => true,
+        }
+    }
+
+    /// Indices of the constraints enforced at `sol` whose slack is at most
+    /// `eps` away from zero; pass zero for exact models. Equality
+    /// constraints bind whenever they hold, ranges when either end does.
+    pub fn binding_constraints(&self, sol: &Solution<N>, eps: N) -> Vec<usize> {
+        self.enabled_constraints()
+            .filter(|(_, c)| Self::is_enforced(c, sol))
+            .filter(|(_, c)| sol.slack(&c.constraint).abs() <= eps)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Every constraint and variable bound `sol` violates, with the amount of
+    /// the violation, leaving out constraints in disabled groups. Variables missing from `sol` count as zero.
+    pub fn check_feasibility(&self, sol: &Solution<N>) -> Vec<Violation<N>> {
+        let mut violations = vec![];
+        for (index, c) in self.enabled_constraints() {
+            if !Self::is_enforced(c, sol) {
+                continue;
+            }
+            let amount = c.constraint.violation(sol);
+            if !amount.is_zero() {
+                violations.push(Violation::Constraint {
+                    index,
+                    name: self.constraint_name(index),
+                    amount,
+                });
+            }
+        }
+        for (i, v) in self.live_variables() {
+            let value = sol.get_value(Variable::new(i, v.name.clone()));
+            if v.v_type.is_semi() && value.is_zero() {
+                continue;
+            }
+            let amount = match (v.lb.finite(), v.ub.finite()) {
+                (Some(lb), _) if value < *lb => lb.clone() - value,
+                (_, Some(ub)) if value > *ub => value - ub.clone(),
+                _ => continue,
+            };
+            violations.push(Violation::Bound {
+                index: i,
+                name: self.variable_name(i),
+                amount,
+            });
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use super::Violation;
+    use crate::{c, Model, Solution};
+
+    #[test]
+    fn test_check_feasibility() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).ub(c(4).0).build();
+        model.add_const((x.clone() + c(4) * y.clone()).le(c(24).0));
+        model
+            .add_const_named("sum", (x.clone() + y.clone()).between(c(1).0, c(12).0))
+            .unwrap();
+        let balance = x.clone().eq(y.clone() * c(2).0);
+
+        let optimum =
+            Solution::<BigRational>::parse("x 8 (obj:2)\ny 4 (obj:5)\n".as_bytes()).unwrap();
+        assert!(model.check_feasibility(&optimum).is_empty());
+        assert!(balance.is_satisfied_by(&optimum));
+
+        let violated =
+            Solution::<BigRational>::parse("x 10 (obj:2)\ny 5 (obj:5)\n".as_bytes()).unwrap();
+        assert_eq!(
+            model.check_feasibility(&violated),
+            vec![
+                Violation::Constraint {
+                    index: 0,
+                    name: "c0".to_string(),
+                    amount: c(6).0
+                },
+                Violation::Constraint {
+                    index: 1,
+                    name: "sum".to_string(),
+                    amount: c(3).0
+                },
+                Violation::Bound {
+                    index: 1,
+                    name: "y".to_string(),
+                    amount: c(1).0
+                },
+            ]
+        );
+        assert!(balance.is_satisfied_by(&violated));
+        assert!(!x.clone().le(c(9).0).is_satisfied_by(&violated));
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let approx = Solution::<f64>::parse("x 1.0000001 (obj:0)\n".as_bytes()).unwrap();
+        let constraint = x.eq(1.0);
+        assert!(!constraint.is_satisfied_by(&approx));
+        assert!(constraint.is_satisfied_within(&approx, 1e-6));
+        assert!(!constraint.is_satisfied_within(&approx, 1e-8));
+    }
+
+    #[test]
+    fn test_binding_constraints() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        let b = model.add_var().name("b").binary().build();
+        model.add_const((&x + c(4) * &y).le(c(24).0));
+        model.add_const((c(3) * &x + &y).le(c(21).0));
+        model.add_const((&x + &y).le(c(9).0));
+        let range = (&x - &y).between(c(-1).0, c(3).0);
+        model.add_const(range.clone());
+        model
+            .add_indicator(b.clone(), true, x.clone().ge(c(4).0))
+            .unwrap();
+
+        let optimum = Solution::<BigRational>::parse("x 4\ny 5\nb 0\n".as_bytes()).unwrap();
+        assert_eq!(model.binding_constraints(&optimum, c(0).0), vec![0, 2, 3]);
+        let c1 = (c(3) * &x + &y).le(c(21).0);
+        assert_eq!(optimum.slack(&c1), c(4).0);
+        assert_eq!(optimum.slack(&(&x + &y).ge(c(10).0)), c(-1).0);
+        assert_eq!(optimum.slack(&x.clone().eq(c(3).0)), c(-1).0);
+        assert_eq!(optimum.range_slacks(&c1), None);
+        assert_eq!(optimum.range_slacks(&range), Some((c(0).0, c(4).0)));
+        assert_eq!(optimum.slack(&range), c(0).0);
+
+        let active = Solution::<BigRational>::parse("x 4\ny 5\nb 1\n".as_bytes()).unwrap();
+        assert_eq!(model.binding_constraints(&active, c(0).0), vec![0, 2, 3, 4]);
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        model.add_const(x.clone().le(1.0));
+        model.add_const(x.clone().eq(2.0));
+        let approx = Solution::<f64>::parse("x 0.9999999\n".as_bytes()).unwrap();
+        assert_eq!(model.binding_constraints(&approx, 1e-6), vec![0]);
+        assert!(approx.slack(&x.clone().le(1.0)) > 0.0);
+    }
+}
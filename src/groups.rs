@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use num::Num;
+
+use crate::{constraint::ConstraintId, Model};
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Adds the constraints added from now on, also by the modelling
+    /// helpers, to the group `label`, until `end_group` or the next
+    /// `begin_group`.
+    pub fn begin_group(&mut self, label: &str) {
+        self.current_group = Some(label.to_string());
+    }
+
+    /// Stops adding constraints to the group of `begin_group`.
+    pub fn end_group(&mut self) {
+        self.current_group = None;
+    }
+
+    /// Turns the constraints of the group `label` on or off. Disabled
+    /// constraints stay in the model but are not exported, solved or
+    /// checked by `check_feasibility`, e.g. to find the block of
+    /// constraints that makes a model infeasible.
+    pub fn set_group_enabled(&mut self, label: &str, enabled: bool) {
+        if enabled {
+            self.disabled_groups.remove(label);
+        } else {
+            self.disabled_groups.insert(label.to_string());
+        }
+    }
+
+    pub fn is_group_enabled(&self, label: &str) -> bool {
+        !self.disabled_groups.contains(label)
+    }
+
+    /// The constraints of the group `label` that have not been removed,
+    /// whether the group is enabled or not.
+    pub fn group_constraints(&self, label: &str) -> Vec<ConstraintId> {
+        self.live_constraints()
+            .filter(|(_, c)| c.group.as_deref() == Some(label))
+            .map(|(i, _)| ConstraintId(i))
+            .collect()
+    }
+
+    /// Label and number of constraints of every group with a constraint
+    /// that has not been removed, by label.
+    pub fn groups(&self) -> Vec<(String, usize)> {
+        let mut groups = BTreeMap::<&str, usize>::new();
+        for (_, c) in self.live_constraints() {
+            if let Some(group) = &c.group {
+                *groups.entry(group).or_default() += 1;
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(label, count)| (label.to_string(), count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, DebugArtifacts, Model};
+
+    #[test]
+    fn test_groups() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.maximize();
+        model.set_objective(&x + &y);
+        model.begin_group("capacity");
+        model.add_const((&x + &y).le(c(4).0));
+        model.add_const(x.clone().le(c(3).0));
+        model.begin_group("cut");
+        let cut = model.add_const((&x + &y).le(c(2).0));
+        model.end_group();
+        model.add_const(y.clone().le(c(10).0));
+
+        assert_eq!(
+            model.groups(),
+            [("capacity".to_string(), 2), ("cut".to_string(), 1)]
+        );
+        assert_eq!(model.group_constraints("cut"), [cut]);
+        assert_eq!(model.export_rows().unwrap().len(), 4);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(2).0));
+
+        model.set_group_enabled("cut", false);
+        assert!(!model.is_group_enabled("cut"));
+        assert_eq!(model.export_rows().unwrap().len(), 3);
+        assert!(!model.to_lp_string().unwrap().contains(" c2:"));
+        assert_eq!(model.group_constraints("cut"), [cut]);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(4).0));
+
+        model.set_group_enabled("cut", true);
+        assert_eq!(model.export_rows().unwrap().len(), 4);
+    }
+}
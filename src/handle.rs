@@ -0,0 +1,307 @@
+use std::{
+    process::{Child, ExitStatus},
+    sync::{Arc, Mutex, MutexGuard},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use num::{BigRational, Num};
+
+use crate::{error::SolveError, options::SolveOptions, solution::Solution, Model};
+
+/// How long a cancelled solver may take to write its best solution and exit
+/// after being interrupted before it is killed.
+const CANCEL_GRACE: Duration = Duration::from_secs(3);
+
+/// How often the exit of a solver that closed its output is checked.
+const EXIT_POLL: Duration = Duration::from_millis(5);
+
+/**
+ * Solve running on a background thread, started with `Model::solve_handle`
+ *
+ * Dropping the handle lets the solve run to completion unobserved; call
+ * `cancel` first to stop the solver.
+ */
+pub struct SolveHandle<N>
+where
+    N: Num + Clone,
+{
+    thread: JoinHandle<Result<Solution<N>, SolveError>>,
+    cancellation: Cancellation,
+}
+
+impl<N> SolveHandle<N>
+where
+    N: Num + Clone,
+{
+    /// Interrupts the solver, which for SCIP stops the search and still
+    /// writes the best solution found, reported with status `Interrupted`.
+    /// A solver that has not exited shortly after is killed and `join`
+    /// returns `SolveError::Cancelled`, as do solver runs started after
+    /// the cancel, e.g. later stages of `solve_lexicographic`.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether the solve is done, so `join` does not block.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Waits for the solve to end and returns its result. Its temporary
+    /// files are removed by then, also after a cancel.
+    pub fn join(self) -> Result<Solution<N>, SolveError> {
+        self.thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+}
+
+/**
+ * Link between a `SolveHandle` and the solver processes of its solve
+ */
+#[derive(Clone, Default)]
+pub(crate) struct Cancellation(Arc<Mutex<CancelState>>);
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: bool,
+    /// Running solver, removed once it has been waited for.
+    child: Option<Child>,
+}
+
+impl Cancellation {
+    fn lock(&self) -> MutexGuard<'_, CancelState> {
+        // the state stays consistent even if a holder panicked
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn cancel(&self) {
+        let mut state = self.lock();
+        if state.cancelled {
+            return;
+        }
+        state.cancelled = true;
+        let Some(child) = &mut state.child else {
+            return;
+        };
+        interrupt(child);
+        drop(state);
+        let cancellation = self.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(CANCEL_GRACE);
+            if let Some(child) = &mut cancellation.lock().child {
+                // fails only if the child has exited meanwhile
+                let _ = child.kill();
+            }
+        });
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.lock().cancelled
+    }
+
+    /// Takes over the running solver `child`, whose output pipes have been
+    /// taken, killing it right away if the solve was cancelled already.
+    pub(crate) fn register(&self, mut child: Child) {
+        let mut state = self.lock();
+        if state.cancelled {
+            let _ = child.kill();
+        }
+        state.child = Some(child);
+    }
+
+    /// Waits for the registered solver to exit, which is soon once it has
+    /// closed its output. The lock is only held briefly so `cancel` can
+    /// still get at the child.
+    pub(crate) fn wait(&self) -> std::io::Result<ExitStatus> {
+        loop {
+            {
+                let mut state = self.lock();
+                let child = state.child.as_mut().expect("a solver is registered");
+                if let Some(status) = child.try_wait()? {
+                    state.child = None;
+                    return Ok(status);
+                }
+            }
+            std::thread::sleep(EXIT_POLL);
+        }
+    }
+}
+
+/// Asks `child` to stop, with SIGINT where there are signals, which SCIP
+/// handles by ending the search and running its remaining commands.
+#[cfg(unix)]
+fn interrupt(child: &mut Child) {
+    if let Ok(None) = child.try_wait() {
+        // SAFETY: `kill` has no memory effects, and the child has not been
+        // reaped, so its id still refers to it
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGINT);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn interrupt(child: &mut Child) {
+    let _ = child.kill();
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Send + 'static,
+{
+    /// Runs `solve` on a copy of the model on a new thread.
+    fn spawn_solve(
+        &self,
+        opts: &SolveOptions,
+        solve: fn(&Model<N>, &SolveOptions) -> Result<Solution<N>, SolveError>,
+    ) -> SolveHandle<N> {
+        let cancellation = Cancellation::default();
+        let mut model = self.clone();
+        model.cancellation = Some(cancellation.clone());
+        let opts = opts.clone();
+        SolveHandle {
+            thread: std::thread::spawn(move || solve(&model, &opts)),
+            cancellation,
+        }
+    }
+}
+
+impl Model<BigRational> {
+    /// Starts `solve_with` on a background thread, returning a handle to
+    /// wait for or cancel it.
+    pub fn solve_handle(&self, opts: &SolveOptions) -> SolveHandle<BigRational> {
+        self.spawn_solve(opts, Model::<BigRational>::solve_with)
+    }
+}
+
+impl Model<f64> {
+    /// Starts `solve_with` on a background thread, returning a handle to
+    /// wait for or cancel it.
+    pub fn solve_handle(&self, opts: &SolveOptions) -> SolveHandle<f64> {
+        self.spawn_solve(opts, Model::<f64>::solve_with)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{path::Path, time::Instant};
+
+    use num::BigRational;
+
+    use crate::{
+        c, test_support::write_stub, Model, SolveError, SolveOptions, SolveStatus, Solver,
+    };
+
+    /// Writes a SCIP stand-in that runs `body`, with the solution path in
+    /// `$solution`.
+    fn write_scip_stub(dir: &Path, body: &str) -> std::path::PathBuf {
+        write_stub(
+            dir,
+            &format!(
+                "for arg in \"$@\"; do\n\
+                 case \"$arg\" in\n\
+                 \"write solution \"*) solution=\"${{arg#write solution }}\" ;;\n\
+                 esac\n\
+                 done\n\
+                 {body}"
+            ),
+        )
+    }
+
+    /// Waits for the stub to write its pid.
+    fn wait_for_pid(path: &Path) -> libc::pid_t {
+        let start = Instant::now();
+        loop {
+            if let Some(pid) = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|pid| pid.trim().parse().ok())
+            {
+                return pid;
+            }
+            assert!(start.elapsed().as_secs() < 10, "the stub did not start");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    fn is_running(pid: libc::pid_t) -> bool {
+        // SAFETY: signal 0 only checks whether the process exists
+        unsafe { libc::kill(pid, 0) == 0 }
+    }
+
+    fn model(stub: &Path) -> (Model<BigRational>, crate::Variable<BigRational>) {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(5).0).build();
+        model.maximize();
+        model.set_objective((&x).into());
+        model.set_solver_path(Solver::Scip, stub);
+        (model, x)
+    }
+
+    #[test]
+    fn test_cancel_interrupts() {
+        // searches until interrupted, then writes its incumbent
+        let dir = tempfile::TempDir::new().unwrap();
+        let pid_file = dir.path().join("pid");
+        let stub = write_scip_stub(
+            dir.path(),
+            &format!(
+                "trap 'kill $sleeper; printf \"solution status: user interrupt\\nx 3\\n\" > \"$solution\"; exit 0' INT\n\
+                 sleep 30 &\n\
+                 sleeper=$!\n\
+                 echo $$ > {}\n\
+                 wait $sleeper\n",
+                pid_file.display()
+            ),
+        );
+        let (model, x) = model(&stub);
+
+        let handle = model.solve_handle(&SolveOptions {
+            quiet: true,
+            ..Default::default()
+        });
+        let pid = wait_for_pid(&pid_file);
+        assert!(!handle.is_finished());
+        handle.cancel();
+        let solution = handle.join().unwrap();
+        assert_eq!(solution.status(), SolveStatus::Interrupted);
+        assert_eq!(solution.get_value(x), c(3).0);
+        assert!(!is_running(pid));
+    }
+
+    #[test]
+    fn test_cancel_kills() {
+        // ignores interrupts
+        let dir = tempfile::TempDir::new().unwrap();
+        let pid_file = dir.path().join("pid");
+        let stub = write_scip_stub(
+            dir.path(),
+            &format!(
+                "trap '' INT\n\
+                 echo $$ > {}\n\
+                 exec sleep 30\n",
+                pid_file.display()
+            ),
+        );
+        let (model, _) = model(&stub);
+
+        let start = Instant::now();
+        let handle = model.solve_handle(&SolveOptions {
+            quiet: true,
+            ..Default::default()
+        });
+        let pid = wait_for_pid(&pid_file);
+        handle.cancel();
+        handle.cancel();
+        assert!(matches!(handle.join(), Err(SolveError::Cancelled)));
+        assert!(!is_running(pid));
+        assert!(start.elapsed().as_secs() < 20);
+
+        let handle = model.solve_handle(&SolveOptions::default());
+        handle.cancel();
+        assert!(matches!(handle.join(), Err(SolveError::Cancelled)));
+    }
+}
@@ -0,0 +1,228 @@
+use std::time::Instant;
+
+use num::Num;
+
+use crate::{
+    backend::{Backend, ScipBackend},
+    constraint::ConstraintId,
+    error::SolveError,
+    expression::Expression,
+    options::SolveOptions,
+    solution::SolveStatus,
+    variable::Variable,
+    Bound, Model, VariableType,
+};
+
+/**
+ * Constraint or variable bounds in an infeasible subsystem found by
+ * `Model::find_iis`
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IisMember<N>
+where
+    N: Num + Clone,
+{
+    Constraint(ConstraintId),
+    /// Both bounds of the variable, which are dropped together.
+    BoundOf(Variable<N>),
+}
+
+/**
+ * Infeasible subsystem of a model, from `Model::find_iis`
+ */
+#[derive(Clone, Debug)]
+pub struct Iis<N>
+where
+    N: Num + Clone,
+{
+    members: Vec<IisMember<N>>,
+    irreducible: bool,
+}
+
+impl<N> Iis<N>
+where
+    N: Num + Clone,
+{
+    /// Constraints and bounds that together cannot hold, empty if the model
+    /// is feasible.
+    pub fn members(&self) -> &[IisMember<N>] {
+        &self.members
+    }
+
+    /// Whether dropping any member makes the rest feasible. `false` when the
+    /// time budget ran out or a solve ended without deciding feasibility, in
+    /// which case the members not yet decided are all kept.
+    pub fn is_irreducible(&self) -> bool {
+        self.irreducible
+    }
+}
+
+/// Whether a solve of a model without objective found it infeasible,
+/// `None` if it did not decide.
+fn infeasible(status: SolveStatus) -> Option<bool> {
+    match status {
+        SolveStatus::Infeasible => Some(true),
+        SolveStatus::Optimal => Some(false),
+        _ => None,
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+    ScipBackend: Backend<N>,
+{
+    /// Irreducible infeasible subsystem of the model, found with the
+    /// deletion filter: every constraint and the bounds of every variable
+    /// that has one, except binary variables, are dropped in turn and
+    /// stay dropped if the rest is still infeasible, so that what remains
+    /// is infeasible while dropping any part of it is not. Each step
+    /// solves the model without its objective, so this is slow on big
+    /// models; `SolveOptions::iis_time_budget` limits the time spent,
+    /// after which the members found so far and the ones not yet tried are
+    /// returned.
+    pub fn find_iis(&self, opts: &SolveOptions) -> Result<Iis<N>, SolveError> {
+        let start = Instant::now();
+        let mut opts = SolveOptions {
+            duals: false,
+            ..opts.clone()
+        };
+        let budget = opts.iis_time_budget;
+        let time_limit = opts.time_limit;
+
+        let mut work = self.clone();
+        work.set_objective(Expression::default());
+        work.objectives.clear();
+        for v in &mut work.variables {
+            v.obj = None;
+        }
+
+        let mut members = self
+            .enabled_constraints()
+            .map(|(i, _)| IisMember::Constraint(ConstraintId(i)))
+            .collect::<Vec<_>>();
+        members.extend(
+            self.live_variables()
+                .filter(|(_, v)| !matches!(v.v_type, VariableType::Binary))
+                .filter(|(_, v)| v.lb.is_finite() || v.ub.is_finite())
+                .map(|(i, v)| IisMember::BoundOf(Variable::new(i, v.name.clone()))),
+        );
+
+        let mut solve = |work: &Model<N>| {
+            let remaining = match budget {
+                Some(budget) => match budget.checked_sub(start.elapsed()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return Ok(None),
+                },
+                None => None,
+            };
+            opts.time_limit = match (time_limit, remaining) {
+                (Some(limit), Some(remaining)) => Some(limit.min(remaining)),
+                (limit, remaining) => limit.or(remaining),
+            };
+            Ok::<_, SolveError>(infeasible(work.solve_using(&ScipBackend, &opts)?.status()))
+        };
+
+        match solve(&work)? {
+            Some(true) => {}
+            Some(false) => {
+                return Ok(Iis {
+                    members: vec![],
+                    irreducible: true,
+                })
+            }
+            None => {
+                return Ok(Iis {
+                    members,
+                    irreducible: false,
+                })
+            }
+        }
+
+        let mut irreducible = true;
+        let mut kept = vec![];
+        for member in members {
+            if !irreducible {
+                kept.push(member);
+                continue;
+            }
+            let still_infeasible = match &member {
+                IisMember::Constraint(id) => {
+                    let constraint = work.constraints[id.0].take();
+                    let result = solve(&work)?;
+                    if result != Some(true) {
+                        work.constraints[id.0] = constraint;
+                    }
+                    result
+                }
+                IisMember::BoundOf(v) => {
+                    let variable = &mut work.variables[v.id()];
+                    let lb = std::mem::replace(&mut variable.lb, Bound::NegInf);
+                    let ub = std::mem::replace(&mut variable.ub, Bound::PosInf);
+                    let result = solve(&work)?;
+                    if result != Some(true) {
+                        let variable = &mut work.variables[v.id()];
+                        variable.lb = lb;
+                        variable.ub = ub;
+                    }
+                    result
+                }
+            };
+            match still_infeasible {
+                Some(true) => {}
+                Some(false) => kept.push(member),
+                None => {
+                    irreducible = false;
+                    kept.push(member);
+                }
+            }
+        }
+        Ok(Iis {
+            members: kept,
+            irreducible,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use super::IisMember;
+    use crate::{c, Model, SolveOptions};
+
+    #[test]
+    fn test_find_iis() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(10).0).build();
+        let y = model.add_var().name("y").build();
+        model.set_objective(&x + &y);
+        let cap = model.add_const((&x + &y).le(c(1).0));
+        model.add_const((&x - &y).le(c(5).0));
+        let demand = model.add_const((&x + &y).ge(c(3).0));
+        model.add_const(x.clone().le(c(4).0));
+
+        let iis = model.find_iis(&SolveOptions::default()).unwrap();
+        assert_eq!(
+            iis.members(),
+            [IisMember::Constraint(cap), IisMember::Constraint(demand)]
+        );
+        assert!(iis.is_irreducible());
+
+        let mut relaxed = model.clone();
+        relaxed.remove_const(demand);
+        let iis = relaxed.find_iis(&SolveOptions::default()).unwrap();
+        assert!(iis.members().is_empty());
+        assert!(iis.is_irreducible());
+
+        // x - y >= 3 only conflicts with the upper bound of x and the lower
+        // bound of y
+        let mut bounded = Model::<BigRational>::new();
+        let x = bounded.add_var().name("x").ub(c(1).0).build();
+        let y = bounded.add_var().name("y").build();
+        bounded.add_const((&x - &y).ge(c(3).0));
+        let iis = bounded.find_iis(&SolveOptions::default()).unwrap();
+        assert_eq!(iis.members().len(), 3);
+        assert!(iis.members().contains(&IisMember::BoundOf(y)));
+    }
+}
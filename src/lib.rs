@@ -1,30 +1,507 @@
-#![feature(format_args_nl)]
-#![feature(iterator_try_collect)]
-
+mod backend;
+mod binary;
+mod bound;
 mod constraint;
+mod cuts;
+mod dedup;
+mod display;
+mod dual;
+mod error;
 mod expression;
+mod feasibility;
+mod groups;
+mod handle;
+mod iis;
+mod linearization;
+mod lp;
+mod lp_writer;
+mod merge;
+mod models;
+mod mps;
+mod objectives;
+mod options;
+mod params;
+mod parse;
+pub mod prelude;
+mod presolve;
+mod quadratic;
+mod rational;
+mod scaling;
+#[cfg(feature = "serde")]
+mod serialization;
+mod solution;
+mod standard_form;
+mod stats;
+#[cfg(all(test, unix))]
+mod test_support;
+mod validate;
 mod variable;
-pub use constraint::Constraint;
+pub use backend::{
+    Backend, CbcBackend, CplexBackend, GlpkBackend, GurobiBackend, HighsBackend, ScipBackend,
+    VariableInfo,
+};
+pub use bound::Bound;
+pub use constraint::{Constraint, ConstraintId, ConstraintSense};
+pub use error::{
+    DecodeError, DualError, EvaluationError, IntegralityError, LpParseError, MissingVariables,
+    ModelError, ParseError, SolveError,
+};
 pub use expression::Expression;
+pub use feasibility::Violation;
+pub use handle::SolveHandle;
+pub use iis::{Iis, IisMember};
+pub use lp_writer::LpWriter;
+pub use merge::VariableIdOffset;
+pub use options::{
+    CommandInput, DebugArtifacts, EqualityRows, ExportOptions, ImportPolicy, MissingSolutionValues,
+    MissingStartValues, SolveOptions, Solver,
+};
+pub use params::{Aggressiveness, Emphasis, Param};
+pub use parse::UnknownNames;
+pub use presolve::PresolveReport;
+pub use quadratic::QuadExpression;
+pub use solution::{ConstraintRef, Solution, SolutionIter, SolveStatus};
+pub use standard_form::StandardFormMap;
+pub use stats::{ModelStats, SolveStats};
+pub use validate::ValidationReport;
 pub use variable::Variable;
 
-use num::{BigInt, BigRational, Num, Signed};
-use std::{collections::BTreeMap, fmt::Display, io::BufRead, str::FromStr};
+#[cfg(feature = "serde")]
+pub use serialization::SerdeCoefficient;
+
+use num::{BigInt, BigRational, FromPrimitive, Num, Signed};
+use scaling::Scaling;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+use validate::check_coefficient;
 
-enum VariableType {
+/**
+ * Kind of values a variable can take
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariableType {
     Binary,
     Integer,
     Continuous,
+    /// Zero or within the bounds.
+    SemiContinuous,
+    /// Zero or an integer within the bounds.
+    SemiInteger,
+}
+
+impl VariableType {
+    fn is_integral(&self) -> bool {
+        matches!(
+            self,
+            VariableType::Binary | VariableType::Integer | VariableType::SemiInteger
+        )
+    }
+
+    fn is_semi(&self) -> bool {
+        matches!(
+            self,
+            VariableType::SemiContinuous | VariableType::SemiInteger
+        )
+    }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
 struct InternalVariable<N>
 where
     N: Num,
 {
     v_type: VariableType,
     name: Option<String>,
-    lb: Option<N>,
-    ub: Option<N>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::lower_bound"))]
+    lb: Bound<N>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::upper_bound"))]
+    ub: Bound<N>,
+    /// Replaced by `Model::substitute_variable` and left out of exports.
+    #[cfg_attr(feature = "serde", serde(default))]
+    eliminated: bool,
+    /// Objective coefficient given with the variable, added to the
+    /// objective set with `Model::set_objective`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serialization::optional_coefficient")
+    )]
+    obj: Option<N>,
+}
+
+impl<N> InternalVariable<N>
+where
+    N: Num + Clone + Display + PartialOrd,
+{
+    /// Fails if a bound of the variable, called `name` in errors, is
+    /// infinite or NaN, the lower bound exceeds the upper one, or the
+    /// variable is semi-continuous or semi-integer without an upper bound,
+    /// or binary with a bound other than 0 or 1.
+    fn check_bounds(&self, name: String) -> Result<(), ModelError> {
+        // `b - b` is NaN for infinite and NaN bounds and zero for others
+        let non_finite = [self.lb.finite(), self.ub.finite()]
+            .into_iter()
+            .flatten()
+            .filter(|b| {
+                let difference = (*b).clone() - (*b).clone();
+                difference.partial_cmp(&N::zero()).is_none()
+            })
+            .map(N::to_string)
+            .collect::<Vec<_>>();
+        if !non_finite.is_empty() {
+            return Err(ModelError::NonFiniteCoefficients {
+                row: format!("the bounds of `{}`", name),
+                terms: non_finite,
+            });
+        }
+        if let (Some(lb), Some(ub)) = (self.lb.finite(), self.ub.finite()) {
+            if lb > ub {
+                return Err(ModelError::InvertedBounds(name));
+            }
+        }
+        if self.v_type.is_semi() && !self.ub.is_finite() {
+            return Err(ModelError::MissingUpperBound(name));
+        }
+        if let VariableType::Binary = self.v_type {
+            if [self.lb.finite(), self.ub.finite()]
+                .into_iter()
+                .flatten()
+                .any(|bound| !bound.is_zero() && !bound.is_one())
+            {
+                return Err(ModelError::InvalidBinaryValue(name));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+struct InternalConstraint<N>
+where
+    N: Num + Clone,
+{
+    name: Option<String>,
+    constraint: Constraint<N>,
+    /// Binary variable and the value for which the constraint is enforced.
+    indicator: Option<(Variable<N>, bool)>,
+    /// Label of the group the constraint was added in, see
+    /// `Model::begin_group`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    group: Option<String>,
+}
+
+/// Constraint index, indicator and normalized row of an exported constraint.
+type ExportRow<'a, N> = (usize, Option<&'a (Variable<N>, bool)>, Constraint<N>);
+
+/// Environment variable that turns off exact mode for models that do not
+/// call `Model::set_exact`.
+const EXACT_VARIABLE: &str = "SCIP_EXACT";
+
+/// Answers of SCIP to `set exact enabled TRUE` when it was built without
+/// exact solving, or has the menu but not the parameter, which it prints as
+/// an error with its source location in front.
+const EXACT_REJECTIONS: [&str; 2] = [
+    "command <exact> not found",
+    "parameter <exact/enabled> unknown",
+];
+
+/// Characters other than ASCII letters and digits allowed in LP names.
+const LP_NAME_SYMBOLS: &str = "!\"#$%&()/,.;?@_`'{}|~";
+
+/// Keywords of the LP format, which cannot be used as names.
+const LP_KEYWORDS: &[&str] = &[
+    "max", "maximize", "maximum", "min", "minimize", "minimum", "st", "s.t.", "subject", "such",
+    "bound", "bounds", "free", "inf", "infinity", "gen", "general", "generals", "bin", "binary",
+    "binaries", "semi", "semis", "sos", "end",
+];
+
+/// Whether model files use `name` for the objective or for an unnamed
+/// constraint, e.g. `obj` or `c12`.
+fn is_reserved_constraint_name(name: &str) -> bool {
+    name == "obj"
+        || name
+            .strip_prefix('c')
+            .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Whether `name` can be used as an identifier in the LP format.
+fn is_valid_lp_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if first.is_ascii_digit() || first == '.' {
+        return false;
+    }
+    // names like `e1` would be read as exponents
+    if matches!(first, 'e' | 'E') && chars.next().is_some_and(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if LP_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(name)) {
+        return false;
+    }
+    name.len() <= 255
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || LP_NAME_SYMBOLS.contains(c))
+}
+
+/// Closest legal LP name to `name`: illegal characters become `_`, and an
+/// `_` is prepended when the name would still be rejected.
+fn sanitize_lp_name(name: &str) -> String {
+    let mut result = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || LP_NAME_SYMBOLS.contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(240)
+        .collect::<String>();
+    if !is_valid_lp_name(&result) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+/**
+ * Builder adding `n` variables of the same type and bounds, named
+ * `{prefix}_0` to `{prefix}_{n-1}`
+ */
+pub struct VariableVecBuilder<'a, N>
+where
+    N: Num + Clone,
+{
+    builder: VariableBuilder<'a, N>,
+    n: usize,
+    prefix: Option<String>,
+}
+
+impl<'a, N> VariableVecBuilder<'a, N>
+where
+    N: Num + Clone + Display + PartialOrd,
+{
+    pub fn binary(mut self) -> Self {
+        self.builder = self.builder.binary();
+        self
+    }
+
+    pub fn integer(mut self) -> Self {
+        self.builder = self.builder.integer();
+        self
+    }
+
+    /// Without a prefix the variables are left unnamed.
+    pub fn name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn lb(mut self, lb: N) -> Self {
+        self.builder = self.builder.lb(lb);
+        self
+    }
+
+    pub fn ub(mut self, ub: N) -> Self {
+        self.builder = self.builder.ub(ub);
+        self
+    }
+
+    /// Removes both bounds, including the default lower bound of zero.
+    pub fn free(mut self) -> Self {
+        self.builder = self.builder.free();
+        self
+    }
+
+    /// Adds the variables to the model, naming them like `VariableBuilder::build`.
+    pub fn build(mut self) -> Vec<Variable<N>> {
+        (0..self.n).map(|i| self.add(format!("_{i}"))).collect()
+    }
+
+    /// Adds one variable named `{prefix}{suffix}`, or unnamed without a
+    /// prefix.
+    fn add(&mut self, suffix: String) -> Variable<N> {
+        let builder = VariableBuilder {
+            model: &mut *self.builder.model,
+            variable: self.builder.variable.clone(),
+            columns: vec![],
+        };
+        match &self.prefix {
+            Some(prefix) => builder.name(format!("{prefix}{suffix}")).build(),
+            None => builder.build(),
+        }
+    }
+}
+
+/**
+ * Builder adding a `rows` × `cols` matrix of variables of the same type and
+ * bounds, named `{prefix}_{row}_{col}`
+ */
+pub struct VariableMatrixBuilder<'a, N>
+where
+    N: Num + Clone,
+{
+    vec: VariableVecBuilder<'a, N>,
+    cols: usize,
+}
+
+impl<'a, N> VariableMatrixBuilder<'a, N>
+where
+    N: Num + Clone + Display + PartialOrd,
+{
+    pub fn binary(self) -> Self {
+        Self {
+            vec: self.vec.binary(),
+            ..self
+        }
+    }
+
+    pub fn integer(self) -> Self {
+        Self {
+            vec: self.vec.integer(),
+            ..self
+        }
+    }
+
+    /// Without a prefix the variables are left unnamed.
+    pub fn name_prefix(self, prefix: impl Into<String>) -> Self {
+        Self {
+            vec: self.vec.name_prefix(prefix),
+            ..self
+        }
+    }
+
+    pub fn lb(self, lb: N) -> Self {
+        Self {
+            vec: self.vec.lb(lb),
+            ..self
+        }
+    }
+
+    pub fn ub(self, ub: N) -> Self {
+        Self {
+            vec: self.vec.ub(ub),
+            ..self
+        }
+    }
+
+    pub fn free(self) -> Self {
+        Self {
+            vec: self.vec.free(),
+            ..self
+        }
+    }
+
+    /// Adds the variables to the model row by row.
+    pub fn build(self) -> Vec<Vec<Variable<N>>> {
+        let Self { mut vec, cols } = self;
+        (0..vec.n)
+            .map(|i| (0..cols).map(|j| vec.add(format!("_{i}_{j}"))).collect())
+            .collect()
+    }
+}
+
+/// Directory holding the files of a single solve, removed afterwards unless
+/// `DebugArtifacts` asks to keep it.
+struct WorkDir {
+    path: PathBuf,
+    /// Set when the directory is removed on drop.
+    temp: Option<tempfile::TempDir>,
+}
+
+impl WorkDir {
+    fn new(artifacts: &DebugArtifacts) -> std::io::Result<Self> {
+        Ok(match artifacts {
+            DebugArtifacts::None => {
+                let temp = tempfile::TempDir::new()?;
+                Self {
+                    path: temp.path().to_path_buf(),
+                    temp: Some(temp),
+                }
+            }
+            DebugArtifacts::Keep => Self {
+                path: tempfile::TempDir::new()?.keep(),
+                temp: None,
+            },
+            DebugArtifacts::KeepIn(path) => {
+                std::fs::create_dir_all(path)?;
+                Self {
+                    path: path.clone(),
+                    temp: None,
+                }
+            }
+        })
+    }
+
+    /// Writes the solver log next to the other files if they are kept, and
+    /// records where they are in `solution`.
+    fn finish<N: Num + Clone>(self, solution: Solution<N>) -> std::io::Result<Solution<N>> {
+        if self.temp.is_some() {
+            return Ok(solution);
+        }
+        std::fs::write(self.path.join("solver.log"), solution.solver_log())?;
+        Ok(solution.with_artifact_dir(self.path))
+    }
+}
+
+struct SolverOutput {
+    status: std::process::ExitStatus,
+    log: String,
+    stderr: String,
+}
+
+impl SolverOutput {
+    /// Fails if SCIP did not know `set exact enabled TRUE`, answering with
+    /// one of `EXACT_REJECTIONS`, and went on in floating point.
+    fn check_exact(self) -> Result<Self, SolveError> {
+        let rejected = self.log.lines().any(|line| {
+            let line = line.trim_end();
+            EXACT_REJECTIONS
+                .iter()
+                .any(|rejection| line.ends_with(rejection))
+        });
+        if rejected {
+            return Err(SolveError::ExactModeUnavailable);
+        }
+        Ok(self)
+    }
+
+    /// Fails unless the solver exited successfully and wrote `solution`.
+    fn check(self, solution: &Path) -> Result<Self, SolveError> {
+        if !self.status.success() {
+            return Err(SolveError::SolverFailed {
+                status: self.status,
+                stderr: self.stderr,
+            });
+        }
+        if !std::fs::exists(solution)? {
+            // e.g. `SCIP Status : problem is solved [infeasible]`
+            let status_line = self
+                .log
+                .lines()
+                .rev()
+                .find(|line| line.to_ascii_lowercase().contains("status"))
+                .map(|line| line.trim().to_string());
+            return Err(SolveError::NoSolutionFile { status_line });
+        }
+        Ok(self)
+    }
 }
 
 pub struct VariableBuilder<'a, N>
@@ -33,6 +510,8 @@ where
 {
     model: &'a mut Model<N>,
     variable: InternalVariable<N>,
+    /// Constraints to add the variable to, with its coefficient there.
+    columns: Vec<(ConstraintId, N)>,
 }
 
 impl<'a, N> VariableBuilder<'a, N>
@@ -45,97 +524,266 @@ where
             variable: InternalVariable {
                 v_type: VariableType::Continuous,
                 name: None,
-                lb: None,
-                ub: None,
+                lb: Bound::default_lower(),
+                ub: Bound::default_upper(),
+                eliminated: false,
+                obj: None,
             },
+            columns: vec![],
         }
     }
 
+    /// Variable that is 0 or 1. Bounds are optional and can only be 0 or 1,
+    /// e.g. to fix the variable.
     pub fn binary(mut self) -> Self {
         self.variable.v_type = VariableType::Binary;
         self
     }
 
+    /// Integer variable, at least zero unless bounds are given, as in the
+    /// LP format.
     pub fn integer(mut self) -> Self {
         self.variable.v_type = VariableType::Integer;
         self
     }
 
+    /// Variable that is either zero or within its bounds. An upper bound is
+    /// required.
+    pub fn semi_continuous(mut self) -> Self {
+        self.variable.v_type = VariableType::SemiContinuous;
+        self
+    }
+
+    /// Variable that is either zero or an integer within its bounds. An
+    /// upper bound is required.
+    pub fn semi_integer(mut self) -> Self {
+        self.variable.v_type = VariableType::SemiInteger;
+        self
+    }
+
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.variable.name = Some(name.into());
         self
     }
 
     pub fn lb(mut self, lb: N) -> Self {
-        self.variable.lb = Some(lb);
+        self.variable.lb = Bound::Finite(lb);
         self
     }
 
     pub fn ub(mut self, ub: N) -> Self {
-        self.variable.ub = Some(ub);
+        self.variable.ub = Bound::Finite(ub);
         self
     }
 
-    pub fn build(self) -> Variable<N> {
-        let Self { model, variable } = self;
-        let result = Variable::new(model.variables.len(), variable.name.clone());
-        model.variables.push(variable);
-        result
+    /// Removes the lower bound, which is zero unless given.
+    pub fn lb_neg_inf(mut self) -> Self {
+        self.variable.lb = Bound::NegInf;
+        self
     }
-}
 
-pub struct Solution<N>
-where
-    N: Num + Clone,
-{
-    values: BTreeMap<String, N>,
+    /// Removes the upper bound, e.g. after `ub`.
+    pub fn ub_pos_inf(mut self) -> Self {
+        self.variable.ub = Bound::PosInf;
+        self
+    }
+
+    /// Objective coefficient of the variable, added to the objective set
+    /// with `Model::set_objective`, see `Model::set_objective_coefficient`.
+    pub fn obj(mut self, coefficient: N) -> Self {
+        self.variable.obj = Some(coefficient);
+        self
+    }
+
+    /// Adds the variable with `coefficient` to the left-hand side of the
+    /// constraint `c`, to build a model column by column, e.g. in column
+    /// generation. See `Model::add_term_to_constraint`.
+    pub fn in_constraint(mut self, c: ConstraintId, coefficient: N) -> Self {
+        self.columns.push((c, coefficient));
+        self
+    }
+
+    /// Sets both bounds to `value`.
+    pub fn fix(mut self, value: N) -> Self {
+        self.variable.lb = Bound::Finite(value.clone());
+        self.variable.ub = Bound::Finite(value);
+        self
+    }
+
+    /// Removes both bounds, including the default lower bound of zero.
+    /// Infinite values are rejected by `lb` and `ub`, so this and
+    /// `lb_neg_inf` and `ub_pos_inf` are how to say there is no bound.
+    pub fn free(mut self) -> Self {
+        self.variable.lb = Bound::NegInf;
+        self.variable.ub = Bound::PosInf;
+        self
+    }
+
+    /// Bounds the variable will be built with, the defaults unless changed.
+    pub fn bounds(&self) -> (&Bound<N>, &Bound<N>) {
+        (&self.variable.lb, &self.variable.ub)
+    }
 }
 
-impl<N> Solution<N>
+impl<N> VariableBuilder<'_, N>
 where
-    N: Num + Clone,
+    N: Num + Clone + Display + PartialOrd,
 {
-    pub fn get_value(&self, e: impl Into<Expression<N>>) -> N {
-        e.into()
-            .0
-            .iter()
-            .map(|(w, v)| {
-                v.as_ref()
-                    .map(|i| {
-                        self.values
-                            .get(&i.name())
-                            .map(Clone::clone)
-                            .unwrap_or(N::zero())
-                    })
-                    .unwrap_or_else(N::one)
-                    .clone()
-                    * w.clone()
-            })
-            .reduce(|a, b| a + b)
-            .unwrap_or_else(N::zero)
+    /// Adds the variable to the model. A name that is not legal in the LP
+    /// format, or already taken, is replaced by a close legal one, which is
+    /// what `Variable::name` reports. Otherwise this is `try_build`.
+    ///
+    /// # Panics
+    ///
+    /// Where `try_build` fails, with the error naming the variable: if a
+    /// bound is infinite or NaN, the lower bound exceeds the upper one, a
+    /// semi-continuous or semi-integer variable has no upper bound, a binary
+    /// variable has a bound other than 0 or 1, or a constraint given to
+    /// `in_constraint` was removed.
+    pub fn build(mut self) -> Variable<N> {
+        if let Some(name) = self.variable.name.take() {
+            let candidate = if is_valid_lp_name(&name) {
+                name
+            } else {
+                sanitize_lp_name(&name)
+            };
+            self.variable.name = Some(self.model.unused_variable_name(candidate));
+        }
+        match self.try_build() {
+            Ok(variable) => variable,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Adds the variable to the model, rejecting names that are not legal in
+    /// the LP format or already taken, and the bounds and constraints that
+    /// `build` panics on.
+    pub fn try_build(self) -> Result<Variable<N>, ModelError> {
+        if let Some(name) = &self.variable.name {
+            if !is_valid_lp_name(name) {
+                return Err(ModelError::InvalidName(name.clone()));
+            }
+            if self.model.variable_names.contains(name) {
+                return Err(ModelError::DuplicateName(name.clone()));
+            }
+        }
+        self.variable.check_bounds(self.error_name())?;
+        if let Some(obj) = &self.variable.obj {
+            check_coefficient(|| "the objective".to_string(), || self.error_name(), obj)?;
+        }
+        self.check_columns()?;
+        Ok(self.push())
+    }
+
+    /// Name of the variable in errors, `v{index}` if it has none.
+    fn error_name(&self) -> String {
+        match &self.variable.name {
+            Some(name) => name.clone(),
+            None => format!("v{}", self.model.variables.len()),
+        }
+    }
+
+    fn check_columns(&self) -> Result<(), ModelError> {
+        for (c, coefficient) in &self.columns {
+            if self.model.get_const(*c).is_none() {
+                return Err(ModelError::RemovedConstraint(c.0));
+            }
+            let row = || format!("constraint `{}`", self.model.constraint_name(c.0));
+            check_coefficient(row, || self.error_name(), coefficient)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the variable, naming an unnamed one `v{index}`, or with a
+    /// suffix if a user took that name, so no two variables share a name.
+    fn push(self) -> Variable<N> {
+        let Self {
+            model,
+            mut variable,
+            columns,
+        } = self;
+        let index = model.variables.len();
+        let name = match variable.name.take() {
+            Some(name) => name,
+            None => model.unused_variable_name(format!("v{index}")),
+        };
+        model.variable_names.insert(name.clone());
+        variable.name = Some(name.clone());
+        model.variables.push(variable);
+        let variable = Variable::new(index, Some(name));
+        for (c, coefficient) in columns {
+            model
+                .add_term_to_constraint(c, variable.clone(), coefficient)
+                .expect("checked before adding the variable");
+        }
+        variable
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptimizationDirection {
     Maximize,
+    #[default]
     Minimize,
 }
 
-impl Default for OptimizationDirection {
-    fn default() -> Self {
-        OptimizationDirection::Minimize
-    }
-}
-
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
 pub struct Model<N>
 where
     N: Num + Clone,
 {
+    /// Set with `set_solver_path`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    solver_paths: BTreeMap<Solver, PathBuf>,
+    solver_args: Vec<String>,
     commands: Vec<String>,
+    /// Set with `set_param`, sorted by `Param::rank`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    params: Vec<Param>,
     objective: Expression<N>,
     direction: OptimizationDirection,
+    /// Products of two variables in the objective, set with
+    /// `set_quadratic_objective`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    quadratic_objective: Vec<quadratic::QuadTerm<N>>,
+    /// Objectives added with `add_objective`, numbered from 1.
+    #[cfg_attr(feature = "serde", serde(default))]
+    objectives: Vec<objectives::Objective<N>>,
     variables: Vec<InternalVariable<N>>,
-    constraints: Vec<Constraint<N>>,
+    variable_names: BTreeSet<String>,
+    /// Removed constraints leave a `None` so ids stay valid.
+    constraints: Vec<Option<InternalConstraint<N>>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::optional_coefficient_map")
+    )]
+    start: Option<BTreeMap<String, N>>,
+    /// Number of auxiliary variables added by the modelling helpers.
+    auxiliaries: usize,
+    /// Set with `set_exact`, otherwise taken from `SCIP_EXACT`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    exact: Option<bool>,
+    /// Group constraints are added to, between `begin_group` and
+    /// `end_group`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    current_group: Option<String>,
+    /// Groups turned off with `set_group_enabled`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    disabled_groups: BTreeSet<String>,
+    /// Variables replaced with `substitute_variable` or left out by
+    /// `prune_unused`, with their replacement, to give them values in
+    /// solutions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    substitutions: Vec<(Variable<N>, Expression<N>)>,
+    /// Set on the copy solved through a `SolveHandle`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cancellation: Option<handle::Cancellation>,
 }
 
 impl<N> Model<N>
@@ -145,373 +793,3906 @@ where
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Empty model with room for `num_vars` variables and `num_constraints`
+    /// constraints, e.g. for generated models of known size.
+    pub fn with_capacity(num_vars: usize, num_constraints: usize) -> Self {
+        Self {
+            variables: Vec::with_capacity(num_vars),
+            constraints: Vec::with_capacity(num_constraints),
+            ..Self::default()
+        }
+    }
+
+    /// Makes room for at least `additional` more variables.
+    pub fn reserve_vars(&mut self, additional: usize) {
+        self.variables.reserve(additional);
+    }
+
+    /// Makes room for at least `additional` more constraints.
+    pub fn reserve_constraints(&mut self, additional: usize) {
+        self.constraints.reserve(additional);
+    }
+
+    /// Whether SCIP solves a model with rational coefficients in exact
+    /// mode. Without it the solution holds rational approximations of
+    /// SCIP's floating-point values, see `Solution::is_approximate`, e.g.
+    /// for SCIP builds without exact solving. If this is not called, exact
+    /// mode is only off when the environment variable `SCIP_EXACT` is `0`
+    /// or `false`.
+    /// `f64` models are always solved in floating point.
+    pub fn set_exact(&mut self, exact: bool) {
+        self.exact = Some(exact);
+    }
+
+    /// Whether a model with rational coefficients is solved in exact mode,
+    /// see `set_exact`.
+    fn solves_exactly(&self) -> bool {
+        self.exact.unwrap_or_else(|| {
+            !matches!(
+                std::env::var(EXACT_VARIABLE).as_deref(),
+                Ok("0" | "false" | "FALSE")
+            )
+        })
+    }
+
     pub fn maximize(&mut self) {
         self.direction = OptimizationDirection::Maximize;
     }
     pub fn minimize(&mut self) {
         self.direction = OptimizationDirection::Minimize;
     }
+    /// Adds a raw SCIP shell command, run after the parameters set with
+    /// `set_param`. Only used when solving with SCIP.
     pub fn add_command(&mut self, command: &str) {
         self.commands.push(command.to_string())
     }
-    pub fn add_var(&mut self) -> VariableBuilder<'_, N> {
-        VariableBuilder::new(self)
+
+    /// Sets a solver parameter, replacing an earlier value of the same
+    /// parameter. Parameters are applied in a fixed order before the
+    /// commands added with `add_command`; a solver without an equivalent
+    /// fails with `SolveError::UnsupportedParameter`.
+    pub fn set_param(&mut self, param: Param) {
+        match self.params.binary_search_by_key(&param.rank(), Param::rank) {
+            Ok(i) => self.params[i] = param,
+            Err(i) => self.params.insert(i, param),
+        }
     }
 
-    pub fn add_const(&mut self, c: Constraint<N>) {
-        self.constraints.push(c)
+    /// SCIP shell commands applying the parameters.
+    fn scip_param_commands(&self) -> Vec<String> {
+        self.params.iter().map(Param::scip_command).collect()
     }
 
-    pub fn set_objective(&mut self, obj: Expression<N>) {
-        self.objective = obj;
+    /// HiGHS options file lines applying the parameters.
+    fn highs_param_options(&self) -> Result<Vec<String>, SolveError> {
+        self.params.iter().map(Param::highs_option).collect()
     }
-}
 
-impl<N> Default for Model<N>
-where
-    N: Num + Clone,
-{
-    fn default() -> Self {
-        Self {
-            commands: Default::default(),
-            objective: Default::default(),
-            direction: Default::default(),
-            variables: Default::default(),
-            constraints: Default::default(),
-        }
+    /// CBC command-line arguments applying the parameters.
+    fn cbc_param_arguments(&self) -> Result<Vec<String>, SolveError> {
+        let arguments = self
+            .params
+            .iter()
+            .map(Param::cbc_arguments)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(arguments.into_iter().flatten().collect())
     }
-}
 
-impl<N> Model<N>
-where
-    N: Num + Clone + Display + Signed,
-{
-    fn export(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
-        match self.direction {
-            OptimizationDirection::Maximize => w.write(b"Maximize\n")?,
-            OptimizationDirection::Minimize => w.write(b"Minimize\n")?,
-        };
-        let obj = Expression(self.objective.0.iter().filter(|(_, b)| b.is_some()).map(Clone::clone).collect());
-        w.write_fmt(format_args_nl!(" obj: {}", obj))?;
-        w.write(b"Subject To\n")?;
-        for (i, c) in self.constraints.iter().enumerate() {
-            w.write_fmt(format_args_nl!(" c{}: {}", i, c.clone().to_normalized()))?;
-        }
-        w.write(b"Bounds\n")?;
-        for (i, v) in self.variables.iter().enumerate() {
-            match (&v.lb, &v.ub) {
-                (Some(lb), Some(ub)) => {
-                    w.write_fmt(format_args_nl!(
-                        " {} <= {} <= {}",
-                        lb,
-                        v.name.clone().unwrap_or_else(|| format!("v{i}")),
-                        ub
-                    ))?;
-                }
-                (Some(lb), None) => {
-                    w.write_fmt(format_args_nl!(
-                        " {} <= {} <= +inf",
-                        lb,
-                        v.name.clone().unwrap_or_else(|| format!("v{i}"))
-                    ))?;
-                }
-                (None, Some(ub)) => {
-                    w.write_fmt(format_args_nl!(
-                        " -inf <= {} <= {}",
-                        v.name.clone().unwrap_or_else(|| format!("v{i}")),
-                        ub
-                    ))?;
+    /// glpsol command-line arguments applying the parameters.
+    fn glpk_param_arguments(&self) -> Result<Vec<String>, SolveError> {
+        let arguments = self
+            .params
+            .iter()
+            .map(Param::glpk_arguments)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(arguments.into_iter().flatten().collect())
+    }
+
+    /// CPLEX interactive optimizer commands applying the parameters.
+    fn cplex_param_commands(&self) -> Result<Vec<String>, SolveError> {
+        self.params.iter().map(Param::cplex_command).collect()
+    }
+
+    /// `gurobi_cl` arguments applying the parameters.
+    fn gurobi_param_arguments(&self) -> Result<Vec<String>, SolveError> {
+        self.params.iter().map(Param::gurobi_argument).collect()
+    }
+    pub fn add_var(&mut self) -> VariableBuilder<'_, N> {
+        VariableBuilder::new(self)
+    }
+
+    /// Copy of the model with integer and binary variables made continuous,
+    /// binaries keeping their 0/1 bounds. Semi-continuous and semi-integer
+    /// variables become continuous with their lower bound, if any, moved to
+    /// zero so the relaxation still allows them to be off. Variables stay
+    /// binary when they are the indicator of an indicator constraint, which
+    /// requires them to be. Variables of `self` can be used with the
+    /// relaxation's solution.
+    pub fn relax(&self) -> Model<N> {
+        let indicators = self
+            .live_constraints()
+            .filter_map(|(_, c)| Some(c.indicator.as_ref()?.0.id()))
+            .collect::<BTreeSet<_>>();
+        let mut relaxed = self.clone();
+        for (i, v) in relaxed.variables.iter_mut().enumerate() {
+            if indicators.contains(&i) {
+                continue;
+            }
+            if let VariableType::Binary = v.v_type {
+                if !v.lb.is_finite() {
+                    v.lb = Bound::Finite(N::zero());
                 }
-                (None, None) => {
-                    w.write_fmt(format_args_nl!(
-                        " {} free",
-                        v.name.clone().unwrap_or_else(|| format!("v{i}"))
-                    ))?;
+                if !v.ub.is_finite() {
+                    v.ub = Bound::Finite(N::one());
                 }
             }
-        }
-        w.write(b"General\n")?;
-        for (i, v) in self.variables.iter().enumerate() {
-            if let VariableType::Integer = v.v_type {
-                if let Some(name) = &v.name {
-                    w.write_fmt(format_args_nl!("{name}"))?;
-                } else {
-                    w.write_fmt(format_args_nl!(" v{i}"))?;
-                }
+            if v.v_type.is_semi() && v.lb.is_finite() {
+                v.lb = Bound::Finite(N::zero());
             }
+            v.v_type = VariableType::Continuous;
         }
-        w.write(b"Binary\n")?;
-        for (i, v) in self.variables.iter().enumerate() {
-            if let VariableType::Binary = v.v_type {
-                if let Some(name) = &v.name {
-                    w.write_fmt(format_args_nl!("{name}"))?;
-                } else {
-                    w.write_fmt(format_args_nl!(" v{i}"))?;
-                }
-            }
+        relaxed
+    }
+
+    /// Adds `n` variables sharing their type and bounds.
+    pub fn add_var_vec(&mut self, n: usize) -> VariableVecBuilder<'_, N> {
+        VariableVecBuilder {
+            builder: VariableBuilder::new(self),
+            n,
+            prefix: None,
         }
-        w.write(b"End\n")?;
+    }
 
-        Ok(())
+    /// Adds a `rows` × `cols` matrix of variables sharing their type and
+    /// bounds.
+    pub fn add_var_matrix(&mut self, rows: usize, cols: usize) -> VariableMatrixBuilder<'_, N> {
+        VariableMatrixBuilder {
+            vec: self.add_var_vec(rows),
+            cols,
+        }
     }
-}
 
-impl Model<BigRational> {
-    fn import(&self, v: &mut impl std::io::Read) -> std::io::Result<Solution<BigRational>> {
-        let re = std::cell::LazyCell::new(|| {
-            use regex::Regex;
-            Regex::new(r"^(?<id>\w+)\s+(?<fraction>\d+(?:\/\d+)?)").unwrap()
-        });
+    /// Makes SCIP start from the values of `start`, e.g. a heuristic
+    /// solution. Variables are matched by name.
+    pub fn set_start(&mut self, start: &Solution<N>) {
+        self.start = Some(start.values().clone());
+    }
 
-        let mut result = Solution {
-            values: Default::default(),
-        };
+    pub fn clear_start(&mut self) {
+        self.start = None;
+    }
 
-        let lines = std::io::BufReader::new(v).lines().try_collect::<Vec<_>>()?;
+    /// Adds a constraint, see `try_add_const`.
+    ///
+    /// # Panics
+    ///
+    /// Where `try_add_const` fails, if a coefficient is infinite or NaN, or
+    /// a bound of a range is NaN.
+    pub fn add_const(&mut self, c: Constraint<N>) -> ConstraintId {
+        match self.try_add_const(c) {
+            Ok(id) => id,
+            Err(e) => panic!("{e}"),
+        }
+    }
 
-        let re = &*re;
-        for capture in lines.iter().map(|l| re.captures(l)) {
-            let Some(caps) = capture else {
-                continue;
-            };
-            let id = caps["id"].to_string();
-            let fraction = BigRational::from_str(&caps["fraction"]).unwrap();
-            result.values.insert(id, fraction);
+    /// Adds a constraint, failing with `ModelError::NonFiniteCoefficients`
+    /// if a coefficient is infinite or NaN, or a bound of a range is NaN.
+    /// An infinite bound of a range, as in `between(0.0, f64::INFINITY)`,
+    /// is no bound.
+    pub fn try_add_const(&mut self, c: Constraint<N>) -> Result<ConstraintId, ModelError> {
+        let index = self.constraints.len();
+        c.check_finite(|| format!("constraint `c{index}`"))?;
+        Ok(self.push_const(InternalConstraint {
+            name: None,
+            constraint: c,
+            indicator: None,
+            group: self.current_group.clone(),
+        }))
+    }
+
+    /// Adds a constraint that model files and duals call `name`. Names
+    /// must be legal in the LP format and unused, and cannot be `obj` or
+    /// `c` followed by digits, the names of the objective and of unnamed
+    /// constraints. Coefficients are checked as in `try_add_const`.
+    pub fn add_const_named(
+        &mut self,
+        name: impl Into<String>,
+        c: Constraint<N>,
+    ) -> Result<ConstraintId, ModelError> {
+        let name = name.into();
+        if !is_valid_lp_name(&name) {
+            return Err(ModelError::InvalidName(name));
+        }
+        if is_reserved_constraint_name(&name) {
+            return Err(ModelError::ReservedName(name));
+        }
+        if self
+            .live_constraints()
+            .any(|(_, c)| c.name.as_deref() == Some(name.as_str()))
+        {
+            return Err(ModelError::DuplicateName(name));
+        }
+        c.check_finite(|| format!("constraint `{name}`"))?;
+        Ok(self.push_const(InternalConstraint {
+            name: Some(name),
+            constraint: c,
+            indicator: None,
+            group: self.current_group.clone(),
+        }))
+    }
+
+    /// Adds a constraint that only has to hold when the binary variable
+    /// `indicator` equals `active_when`, checking its coefficients as
+    /// `try_add_const` does.
+    pub fn add_indicator(
+        &mut self,
+        indicator: Variable<N>,
+        active_when: bool,
+        c: Constraint<N>,
+    ) -> Result<ConstraintId, ModelError> {
+        if !matches!(self.var_type(&indicator)?, VariableType::Binary) {
+            return Err(ModelError::NotBinary(indicator.name()));
         }
+        if let ConstraintSense::Range { .. } = c.sense {
+            return Err(ModelError::RangeNotSupported);
+        }
+        let index = self.constraints.len();
+        c.check_finite(|| format!("constraint `c{index}`"))?;
+        Ok(self.push_const(InternalConstraint {
+            name: None,
+            constraint: c,
+            indicator: Some((indicator, active_when)),
+            group: self.current_group.clone(),
+        }))
+    }
 
-        Ok(result)
+    fn push_const(&mut self, c: InternalConstraint<N>) -> ConstraintId {
+        self.constraints.push(Some(c));
+        ConstraintId(self.constraints.len() - 1)
     }
 
-    pub fn solve(&self, leave_debug_info: bool) -> std::io::Result<Solution<BigRational>> {
-        use std::fs;
-        use std::process::{Command, Stdio};
-        use tempfile::TempDir;
+    /// The constraint `id` refers to, `None` once it has been removed.
+    pub fn get_const(&self, id: ConstraintId) -> Option<&Constraint<N>> {
+        self.constraints.get(id.0)?.as_ref().map(|c| &c.constraint)
+    }
 
-        let dir = TempDir::new().unwrap();
+    /// Removes the constraint `id` refers to and returns it. The ids of the
+    /// other constraints and the names of unnamed ones do not change.
+    pub fn remove_const(&mut self, id: ConstraintId) -> Option<Constraint<N>> {
+        self.constraints.get_mut(id.0)?.take().map(|c| c.constraint)
+    }
 
-        let formulation_path = dir.path().join("formulation.lp");
-        let solution_path = dir.path().join("solution.sol");
-        if leave_debug_info {
-            std::mem::forget(dir);
+    /// Replaces the constraint `id` refers to, keeping its name and
+    /// indicator, and returns the previous one. Coefficients are checked as
+    /// in `try_add_const`.
+    pub fn replace_const(
+        &mut self,
+        id: ConstraintId,
+        c: Constraint<N>,
+    ) -> Result<Constraint<N>, ModelError> {
+        if self.get_const(id).is_none() {
+            return Err(ModelError::RemovedConstraint(id.0));
         }
-        let mut f = fs::File::create(&formulation_path).unwrap();
-        self.export(&mut f).unwrap();
-        drop(f);
+        c.check_finite(|| format!("constraint `{}`", self.constraint_name(id.0)))?;
+        let current = self.constraints[id.0].as_mut().expect("constraint is live");
+        if current.indicator.is_some() {
+            if let ConstraintSense::Range { .. } = c.sense {
+                return Err(ModelError::RangeNotSupported);
+            }
+        }
+        Ok(std::mem::replace(&mut current.constraint, c))
+    }
+
+    /// Adds `coefficient` times `v` to the left-hand side of the constraint
+    /// `id` refers to, to the existing term of `v` if there is one. Fails if
+    /// `v` is not a variable of this model or has been substituted, or if
+    /// `coefficient` is not finite.
+    pub fn add_term_to_constraint(
+        &mut self,
+        id: ConstraintId,
+        v: Variable<N>,
+        coefficient: N,
+    ) -> Result<(), ModelError> {
+        if self.get_const(id).is_none() {
+            return Err(ModelError::RemovedConstraint(id.0));
+        }
+        if self.variable_mut(&v)?.eliminated {
+            return Err(ModelError::UnknownVariable(v.id()));
+        }
+        check_coefficient(
+            || format!("constraint `{}`", self.constraint_name(id.0)),
+            || v.name(),
+            &coefficient,
+        )?;
+        let current = self.constraints[id.0].as_mut().expect("constraint is live");
+        let lhs = &mut current.constraint.lhs;
+        match lhs
+            .0
+            .iter_mut()
+            .find(|(_, u)| u.as_ref().is_some_and(|u| u.id() == v.id()))
+        {
+            Some((w, _)) => *w = w.clone() + coefficient,
+            None => lhs.0.push((coefficient, Some(v))),
+        }
+        Ok(())
+    }
+
+    /// Constraints that have not been removed, with their index.
+    fn live_constraints(&self) -> impl Iterator<Item = (usize, &InternalConstraint<N>)> {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some((i, c.as_ref()?)))
+    }
 
-        let mut command = Command::new("scip");
-        command.arg("-c").arg("set exact enabled TRUE");
+    /// Constraints that have not been removed and are not in a disabled
+    /// group, with their index. These are the ones exported and solved.
+    fn enabled_constraints(&self) -> impl Iterator<Item = (usize, &InternalConstraint<N>)> {
+        self.live_constraints().filter(|(_, c)| {
+            c.group
+                .as_ref()
+                .is_none_or(|group| !self.disabled_groups.contains(group))
+        })
+    }
+
+    /// Variables that have not been eliminated, with their index.
+    fn live_variables(&self) -> impl Iterator<Item = (usize, &InternalVariable<N>)> {
+        self.variables
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.eliminated)
+    }
 
-        for c in self.commands.iter() {
-            command.arg("-c").arg(c);
+    /// Sets both bounds of `v` to `value`. Binary variables can only be
+    /// fixed to 0 or 1.
+    pub fn fix_variable(&mut self, v: &Variable<N>, value: N) -> Result<(), ModelError> {
+        let variable = self.variable_mut(v)?;
+        if matches!(variable.v_type, VariableType::Binary) && !value.is_zero() && !value.is_one() {
+            return Err(ModelError::InvalidBinaryValue(v.name()));
         }
-        command
-            .arg("-c")
-            .arg(format!("read {}", formulation_path.to_string_lossy()))
-            .arg("-c")
-            .arg(&format!("optimize"))
-            .arg("-c")
-            .arg(&format!(
-                "write solution {}",
-                solution_path.to_string_lossy()
-            ))
-            .stdout(Stdio::inherit())
-            .output()
-            .unwrap();
+        variable.lb = Bound::Finite(value.clone());
+        variable.ub = Bound::Finite(value);
+        Ok(())
+    }
 
-        let mut f = fs::File::open(&solution_path).unwrap();
+    /// The internal variable behind `v`, failing if `v` is not a variable
+    /// of this model.
+    fn variable_mut(&mut self, v: &Variable<N>) -> Result<&mut InternalVariable<N>, ModelError> {
+        self.variables
+            .get_mut(v.id())
+            .ok_or(ModelError::UnknownVariable(v.id()))
+    }
 
-        let solution = self.import(&mut f).unwrap();
-        Ok(solution)
+    /// Lower and upper bound of `v`, `None` being no bound. Binary variables
+    /// without explicit bounds report no upper bound, though they are at
+    /// most 1.
+    pub fn var_bounds(&self, v: &Variable<N>) -> Result<(Option<&N>, Option<&N>), ModelError> {
+        let (lb, ub) = self.bounds(v)?;
+        Ok((lb.finite(), ub.finite()))
     }
-}
 
-impl Model<f64> {
-    fn import(&self, v: &mut impl std::io::Read) -> std::io::Result<Solution<f64>> {
-        let re = std::cell::LazyCell::new(|| {
-            use regex::Regex;
-            Regex::new(r"^(?<id>\w+)\s+(?<number>.+)\(obj:").unwrap()
-        });
+    /// Lower and upper bound of `v` as `Bound`s, which tell the default
+    /// lower bound of zero from an explicit one only by value, but an
+    /// intentionally free variable from one given no bounds.
+    pub fn bounds(&self, v: &Variable<N>) -> Result<(&Bound<N>, &Bound<N>), ModelError> {
+        let variable = self
+            .variables
+            .get(v.id())
+            .ok_or(ModelError::UnknownVariable(v.id()))?;
+        Ok((&variable.lb, &variable.ub))
+    }
 
-        let mut result = Solution {
-            values: Default::default(),
-        };
+    /// Type of `v`.
+    pub fn var_type(&self, v: &Variable<N>) -> Result<VariableType, ModelError> {
+        self.variables
+            .get(v.id())
+            .map(|variable| variable.v_type)
+            .ok_or(ModelError::UnknownVariable(v.id()))
+    }
 
-        let lines = std::io::BufReader::new(v).lines().try_collect::<Vec<_>>()?;
+    /// The value `v` is fixed to, if its bounds are equal.
+    pub fn is_fixed(&self, v: &Variable<N>) -> Result<Option<N>, ModelError> {
+        Ok(match self.bounds(v)? {
+            (Bound::Finite(lb), Bound::Finite(ub)) if lb == ub => Some(lb.clone()),
+            _ => None,
+        })
+    }
 
-        let re = &*re;
-        for capture in lines.iter().map(|l| re.captures(l)) {
-            let Some(caps) = capture else {
+    /// Replaces `var` by `replacement` in the objectives and every
+    /// constraint, and leaves `var` out of the exported model. Finite
+    /// bounds of `var` become a constraint on `replacement`, and solutions
+    /// give `var` the value of `replacement`. Constraints added afterwards
+    /// must not use `var`. Fails if `replacement` contains `var`, or `var`
+    /// is integral, semi-continuous or the indicator of an indicator
+    /// constraint, as a constraint cannot keep it integral or zero.
+    pub fn substitute_variable(
+        &mut self,
+        var: &Variable<N>,
+        replacement: &Expression<N>,
+    ) -> Result<(), ModelError> {
+        let v_type = self.var_type(var)?;
+        let is_indicator = self
+            .live_constraints()
+            .any(|(_, c)| matches!(&c.indicator, Some((y, _)) if y.id() == var.id()));
+        if is_indicator
+            || v_type.is_integral()
+            || v_type.is_semi()
+            || replacement.terms().any(|(_, v)| v.id() == var.id())
+        {
+            return Err(ModelError::CannotSubstitute(var.name()));
+        }
+        self.objective = self.objective.substitute(var, replacement);
+        if let Some(coefficient) = self.variables[var.id()].obj.take() {
+            self.objective += replacement.clone() * coefficient;
+        }
+        for o in &mut self.objectives {
+            o.expression = o.expression.substitute(var, replacement);
+        }
+        let mut quadratic = Vec::with_capacity(self.quadratic_objective.len());
+        for t in std::mem::take(&mut self.quadratic_objective) {
+            let (w, a, b) = (t.coefficient(), t.a(), t.b());
+            if a.id() != var.id() && b.id() != var.id() {
+                quadratic.push(t);
                 continue;
+            }
+            let factor = |v: &Variable<N>| match v.id() == var.id() {
+                true => replacement.clone(),
+                false => Expression::from(v),
             };
-            let id = caps["id"].to_string();
-            let fraction = f64::from_str(&caps["number"].trim()).unwrap();
-            result.values.insert(id, fraction);
+            let product = (factor(a) * w.clone()) * factor(b);
+            self.objective += product.linear;
+            quadratic.extend(product.quadratic);
+        }
+        self.quadratic_objective = quadratic;
+        for c in self.constraints.iter_mut().flatten() {
+            c.constraint = c.constraint.substitute(var, replacement);
         }
 
-        Ok(result)
+        let variable = &mut self.variables[var.id()];
+        variable.eliminated = true;
+        let bounds = match (variable.lb.finite(), variable.ub.finite()) {
+            (Some(lb), Some(ub)) => Some(replacement.clone().between(lb.clone(), ub.clone())),
+            (Some(lb), None) => Some(replacement.clone().ge(lb.clone())),
+            (None, Some(ub)) => Some(replacement.clone().le(ub.clone())),
+            (None, None) => None,
+        };
+        if let Some(bounds) = bounds {
+            // bounds hold whichever groups are enabled
+            self.push_const(InternalConstraint {
+                name: None,
+                constraint: bounds,
+                indicator: None,
+                group: None,
+            });
+        }
+        self.substitutions.push((var.clone(), replacement.clone()));
+        Ok(())
+    }
+
+    /// Adds the values of the variables replaced by `substitute_variable`
+    /// or `prune_unused` to `solution`, unless it has no values at all. Replacements are
+    /// evaluated latest first, as they can contain variables substituted
+    /// after them.
+    pub(crate) fn add_substituted_values(&self, solution: &mut Solution<N>) {
+        if solution.is_empty() && solution.status() != SolveStatus::Optimal {
+            return;
+        }
+        for (v, replacement) in self.substitutions.iter().rev() {
+            let value = replacement.evaluate_or_zero(solution.values());
+            solution.insert_value(self.variable_name(v.id()), value);
+        }
+    }
+
+    /// Sets the objective, see `try_set_objective`.
+    ///
+    /// # Panics
+    ///
+    /// Where `try_set_objective` fails, if a coefficient is infinite or NaN.
+    pub fn set_objective(&mut self, obj: Expression<N>) {
+        if let Err(e) = self.try_set_objective(obj) {
+            panic!("{e}");
+        }
+    }
+
+    /// Sets the objective, dropping a quadratic one, or fails with
+    /// `ModelError::NonFiniteCoefficients` if a coefficient is infinite or
+    /// NaN. Coefficients given per variable are kept and added to `obj`.
+    pub fn try_set_objective(&mut self, obj: Expression<N>) -> Result<(), ModelError> {
+        obj.check_finite_objective()?;
+        self.objective = obj;
+        self.quadratic_objective.clear();
+        Ok(())
     }
 
-    pub fn solve(&self, leave_debug_info: bool) -> std::io::Result<Solution<f64>> {
-        use std::fs;
-        use std::process::{Command, Stdio};
-        use tempfile::TempDir;
+    /// Sets the objective coefficient of `v` that is kept with the variable,
+    /// as given by `VariableBuilder::obj`, e.g. to change it for another
+    /// solve. The objective is the expression of `set_objective` plus these
+    /// coefficients, so a variable in both has the sum of its coefficients.
+    pub fn set_objective_coefficient(
+        &mut self,
+        v: &Variable<N>,
+        coefficient: N,
+    ) -> Result<(), ModelError> {
+        check_coefficient(|| "the objective".to_string(), || v.name(), &coefficient)?;
+        self.variable_mut(v)?.obj = Some(coefficient);
+        Ok(())
+    }
 
-        let dir = TempDir::new().unwrap();
+    /// The expression of `set_objective` followed by the coefficients given
+    /// per variable, which together are the objective.
+    fn objective_expression(&self) -> Expression<N> {
+        let mut objective = self.objective.clone();
+        objective.0.extend(
+            self.live_variables().filter_map(|(i, v)| {
+                Some((v.obj.clone()?, Some(Variable::new(i, v.name.clone()))))
+            }),
+        );
+        objective
+    }
 
-        let formulation_path = dir.path().join("formulation.lp");
-        let solution_path = dir.path().join("solution.sol");
-        if leave_debug_info {
-            std::mem::forget(dir);
+    /// Moves the coefficients given per variable into the objective
+    /// expression, for copies that replace it.
+    fn clear_objective_coefficients(&mut self) {
+        self.objective = self.objective_expression();
+        for v in &mut self.variables {
+            v.obj = None;
         }
-        let mut f = fs::File::create(&formulation_path).unwrap();
-        self.export(&mut f).unwrap();
-        drop(f);
+    }
+
+    /// Runs `solver` from the executable at `path` instead of looking it up
+    /// through its environment variable, such as `SCIP_EXECUTABLE`, or
+    /// `PATH`. Other solvers are not affected.
+    pub fn set_solver_path(&mut self, solver: Solver, path: impl Into<PathBuf>) {
+        self.solver_paths.insert(solver, path.into());
+    }
 
-        let mut command = Command::new("scip");
+    /// Arguments passed to the solver executable before any command.
+    pub fn set_solver_prefix_args(&mut self, args: Vec<String>) {
+        self.solver_args = args;
+    }
+
+    fn solver_path(&self, solver: Solver) -> PathBuf {
+        self.solver_paths
+            .get(&solver)
+            .cloned()
+            .or_else(|| std::env::var_os(solver.executable_variable()).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(solver.default_executable()))
+    }
+
+    fn solver_command(&self, solver: Solver) -> std::process::Command {
+        let mut command = std::process::Command::new(self.solver_path(solver));
+        command.args(&self.solver_args);
+        command
+    }
 
-        for c in self.commands.iter() {
-            command.arg("-c").arg(c);
+    /// `base`, or `base` with the first numeric suffix that makes it a name
+    /// no variable has.
+    fn unused_variable_name(&self, base: String) -> String {
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+        while self.variable_names.contains(&candidate) {
+            candidate = format!("{base}_{suffix}");
+            suffix += 1;
         }
-        let out = command
-            .arg("-c")
-            .arg(format!("read {}", formulation_path.to_string_lossy()))
-            .arg("-c")
-            .arg(&format!("optimize"))
-            .arg("-c")
-            .arg(&format!(
-                "write solution {}",
-                solution_path.to_string_lossy()
-            ))
-            .arg("-c")
-            .arg("quit")
-            .stdout(Stdio::inherit())
-            .spawn()
-            .unwrap()
-            .wait()
-            .unwrap();
-        
-        let mut attempt = 0;
-        while !fs::exists(&solution_path).unwrap() && attempt < 10 {
-            std::thread::sleep(std::time::Duration::from_millis(100));
-            attempt += 1;
+        candidate
+    }
+
+    /// Name of the `i`-th variable as written to model files.
+    fn variable_name(&self, i: usize) -> String {
+        self.variable_name_ref(i).into_owned()
+    }
+
+    /// `variable_name` without copying a stored name.
+    fn variable_name_ref(&self, i: usize) -> Cow<'_, str> {
+        match &self.variables[i].name {
+            Some(name) => Cow::Borrowed(name),
+            None => Cow::Owned(format!("v{i}")),
         }
-        if !fs::exists(&solution_path).unwrap() {
-            panic!("No file {:?}. Exit status {:?}. Command: {:?}", solution_path, out, command);
+    }
+
+    /// Name of the `i`-th constraint as written to model files.
+    fn constraint_name(&self, i: usize) -> String {
+        self.constraints[i]
+            .as_ref()
+            .and_then(|c| c.name.clone())
+            .unwrap_or_else(|| format!("c{i}"))
+    }
+
+    /// The objective without its constant term, with duplicate terms merged.
+    fn objective_terms(&self) -> Expression<N> {
+        let mut obj = self.objective_expression();
+        obj.0.retain(|(_, v)| v.is_some());
+        obj.simplify();
+        obj
+    }
+
+    /// The constant term of the objective, which the LP format cannot hold,
+    /// so it is added to the objective value the solver reports.
+    fn objective_constant(&self) -> N {
+        self.objective
+            .0
+            .iter()
+            .filter(|(_, v)| v.is_none())
+            .fold(N::zero(), |acc, (w, _)| acc + w.clone())
+    }
+
+    /// Runs `command` to completion, feeding it `input` on stdin if given,
+    /// collecting its log and echoing it to stdout unless `quiet` is set.
+    fn run_solver(
+        &self,
+        command: &mut std::process::Command,
+        input: Option<String>,
+        quiet: bool,
+    ) -> Result<SolverOutput, SolveError> {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::process::Stdio;
+
+        let stdin = if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        };
+        let mut child = command
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    SolveError::SolverNotFound(PathBuf::from(command.get_program()))
+                } else {
+                    SolveError::Io(e)
+                }
+            })?;
+
+        // written from a thread so a solver filling its stdout pipe before
+        // reading all of its input cannot deadlock
+        let stdin_writer = input.map(|input| {
+            let mut stdin = child.stdin.take().expect("stdin is piped");
+            std::thread::spawn(move || stdin.write_all(input.as_bytes()))
+        });
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buf = vec![];
+            stderr.read_to_end(&mut buf).map(|_| buf)
+        });
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let cancellation = self.cancellation.clone().unwrap_or_default();
+        cancellation.register(child);
+
+        let mut stdout = BufReader::new(stdout);
+        let mut log = String::new();
+        let mut line = vec![];
+        while stdout.read_until(b'\n', &mut line)? > 0 {
+            let text = String::from_utf8_lossy(&line);
+            if !quiet {
+                print!("{text}");
+            }
+            log.push_str(&text);
+            line.clear();
         }
-        let mut f = fs::File::open(&solution_path).unwrap();
 
-        let solution = self.import(&mut f).unwrap();
-        Ok(solution)
+        let status = cancellation.wait()?;
+        if cancellation.is_cancelled() && !status.success() {
+            return Err(SolveError::Cancelled);
+        }
+        if let Some(stdin_writer) = stdin_writer {
+            // a solver exiting before reading all commands is reported
+            // through its status
+            let _ = stdin_writer.join().expect("stdin writer does not panic");
+        }
+        let stderr = stderr_reader
+            .join()
+            .expect("stderr reader does not panic")?;
+        Ok(SolverOutput {
+            status,
+            log,
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        })
     }
 }
 
-pub struct Constant<N>(pub N)
+impl<N> Model<N>
 where
-    N: Num + Clone;
+    N: Num + Clone + Display + PartialOrd,
+{
+    /// Replaces the bounds of `v`, `None` being no bound. Fails, leaving
+    /// the bounds unchanged, where `VariableBuilder::try_build` would.
+    pub fn set_var_bounds(
+        &mut self,
+        v: &Variable<N>,
+        lb: Option<N>,
+        ub: Option<N>,
+    ) -> Result<(), ModelError> {
+        let name = v.name();
+        let variable = self.variable_mut(v)?;
+        let previous = (
+            std::mem::replace(&mut variable.lb, Bound::lower(lb)),
+            std::mem::replace(&mut variable.ub, Bound::upper(ub)),
+        );
+        variable.check_bounds(name).inspect_err(|_| {
+            (variable.lb, variable.ub) = previous;
+        })
+    }
 
-impl<IntoExpression, N> std::ops::Mul<IntoExpression> for Constant<N>
+    /// Replaces the bounds of `v`, e.g. to undo `fix_variable`. `None` is
+    /// no bound, not the default lower bound of zero. Fails like
+    /// `set_var_bounds`.
+    pub fn unfix_variable(
+        &mut self,
+        v: &Variable<N>,
+        lb: Option<N>,
+        ub: Option<N>,
+    ) -> Result<(), ModelError> {
+        self.set_var_bounds(v, lb, ub)
+    }
+
+    /// Changes the type of `v`, keeping its bounds. Fails, leaving the type
+    /// unchanged, where `VariableBuilder::try_build` would.
+    pub fn set_var_type(
+        &mut self,
+        v: &Variable<N>,
+        v_type: VariableType,
+    ) -> Result<(), ModelError> {
+        let name = v.name();
+        let variable = self.variable_mut(v)?;
+        let previous = std::mem::replace(&mut variable.v_type, v_type);
+        variable.check_bounds(name).inspect_err(|_| {
+            variable.v_type = previous;
+        })
+    }
+}
+
+impl<N> Default for Model<N>
 where
     N: Num + Clone,
-    IntoExpression: Into<Expression<N>>,
 {
-    type Output = Expression<N>;
-    fn mul(self, rhs: IntoExpression) -> Self::Output {
-        rhs.into() * self.0
+    fn default() -> Self {
+        Self {
+            solver_paths: BTreeMap::new(),
+            solver_args: Default::default(),
+            commands: Default::default(),
+            params: Default::default(),
+            objective: Default::default(),
+            direction: Default::default(),
+            quadratic_objective: Default::default(),
+            objectives: Default::default(),
+            variables: Default::default(),
+            variable_names: Default::default(),
+            constraints: Default::default(),
+            start: None,
+            auxiliaries: 0,
+            exact: None,
+            current_group: None,
+            disabled_groups: Default::default(),
+            substitutions: Default::default(),
+            cancellation: None,
+        }
     }
 }
 
-pub fn c<N>(e: N) -> Constant<BigRational>
+impl<N> Model<N>
 where
-    N: Into<BigInt>,
+    N: Num + Clone + Display + Signed,
 {
-    Constant(e.into().into())
-}
+    /// Writes the model in the LP format read by SCIP. Fractional
+    /// coefficients of a `Model<BigRational>` are written as `p/q`, which
+    /// `read_lp` accepts but solvers do not; `solve` instead hands SCIP an
+    /// equivalent model with rows and objective scaled to integers. Rows
+    /// longer than 250 characters are wrapped, see `LpWriter` for other
+    /// widths.
+    pub fn write_lp(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        LpWriter::new(w).write_model(self)
+    }
 
-#[cfg(test)]
-mod tests {
-    use num::{BigInt, BigRational};
+    /// `write_lp` with `options`, e.g. a canonical export for snapshot tests.
+    pub fn write_lp_with(
+        &self,
+        w: &mut impl std::io::Write,
+        options: &ExportOptions,
+    ) -> std::io::Result<()> {
+        LpWriter::new(w).options(*options).write_model(self)
+    }
 
-    use crate::{c, Expression, Model};
+    /// Normalized constraints as exported, leaving out disabled groups and
+    /// the constraints that hold trivially. An indicator constraint that can
+    /// never hold becomes a row fixing its indicator to the other value; any
+    /// other such constraint fails the export with
+    /// `ModelError::InfeasibleConstraint`.
+    fn export_rows(&self) -> Result<Vec<ExportRow<'_, N>>, ModelError> {
+        let mut rows = vec![];
+        for (i, c) in self.enabled_constraints() {
+            let constraint = c.constraint.normalized();
+            match (constraint.normalized_trivially_holds(), &c.indicator) {
+                (None, indicator) => rows.push((i, indicator.as_ref(), constraint)),
+                (Some(true), _) => {}
+                (Some(false), Some((indicator, active_when))) => {
+                    let value = if *active_when { N::zero() } else { N::one() };
+                    rows.push((i, None, indicator.clone().eq(value)))
+                }
+                (Some(false), None) => {
+                    return Err(ModelError::InfeasibleConstraint(self.constraint_name(i)))
+                }
+            }
+        }
+        Ok(rows)
+    }
 
-    #[test]
-    fn test_expression() {
-        let mut model = Model::<BigRational>::default();
-        let x = c(2) * model.add_var().name("a").build();
-        let y = c(3) * model.add_var().name("b").build();
-        let z = c(2) * y.clone();
-        model.add_var();
-        let k = c(6) * model.add_var().name("e").build();
-        let expr = (x + y) + (z + k);
-        println!("{expr}")
+    /// Writes `header` followed by what `entry` writes for every variable,
+    /// given its name.
+    fn write_variable_section<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        header: &str,
+        by_name: bool,
+        entry: impl Fn(&mut W, &str, &InternalVariable<N>) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        writeln!(w, "{header}")?;
+        let mut variables = self
+            .live_variables()
+            .map(|(i, v)| (self.variable_name_ref(i), v))
+            .collect::<Vec<_>>();
+        if by_name {
+            variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+        for (name, v) in variables {
+            entry(w, &name, v)?;
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_expression2() {
-        /*let mut model = Model::<BigRational>::default();
-        let x = model.add_var().build() * 2.into();
-        let b = model.add_var().build() * 3.into();
-        let c = model.add_var().build() * 4.into();
-        let e = model.add_var().build() * 6.into();
-        let expr = (a + b) + (c + e);
-        println!("{expr}")*/
+    /// Writes `sol` as a SCIP solution file, as read by `read` in SCIP and by
+    /// `Solution::parse`: an `objective value:` line if `sol` has one, then
+    /// a `name value` line per variable of the model. Exact values are
+    /// written as fractions like `22/7`, and `f64` values with as many
+    /// digits as needed to read back the same value.
+    pub fn write_sol(
+        &self,
+        sol: &Solution<N>,
+        w: &mut impl std::io::Write,
+        missing: MissingSolutionValues,
+    ) -> std::io::Result<()> {
+        if let Some(objective) = sol.objective_value() {
+            writeln!(w, "objective value: {}", objective)?;
+        }
+        for (i, _) in self.live_variables() {
+            let name = self.variable_name_ref(i);
+            match (sol.get(&name), missing) {
+                (Some(value), _) => writeln!(w, "{} {}", name, value)?,
+                (None, MissingSolutionValues::Zero) => writeln!(w, "{} 0", name)?,
+                (None, MissingSolutionValues::Omit) => {}
+            }
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_expression3() {
-        /*let mut model = Model::<Rational32>::default();
-        let a = model.add_var().build() * 2.into();
-        let b = model.add_var().build() * 3.into();
-        let c = model.add_var().build() * (4.into()) / 3.into();
-        let e = model.add_var().build() * 6.into();
-        let expr: Expression<Rational32> = (a + b) - (c + e) + Rational32::from(9);
-        println!("{expr}")*/
+    /// Writes the start set with `set_start` as a SCIP solution file, one
+    /// `name value` line per variable.
+    fn write_start(
+        &self,
+        w: &mut impl std::io::Write,
+        missing: MissingStartValues,
+    ) -> std::io::Result<()> {
+        let Some(start) = &self.start else {
+            return Ok(());
+        };
+        for (i, v) in self.live_variables() {
+            let name = self.variable_name_ref(i);
+            let value = match (start.get(name.as_ref()), missing) {
+                (Some(value), _) => value,
+                (None, MissingStartValues::LowerBound) => match v.lb.finite() {
+                    Some(lb) => lb,
+                    None => continue,
+                },
+                (None, MissingStartValues::Omit) => continue,
+            };
+            writeln!(w, "{} {}", name, value)?;
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_constraint() {
-        /*let mut model = Model::<Rational32>::default();
-        let a = model.add_var().build() * 2.into();
-        let b = model.add_var().build() * 3.into();
-        let c = model.add_var().build() * (4.into()) / 3.into();
-        let e = model.add_var().build() * 6.into();
-        let expr: Expression<Rational32> = (a + b) - (c + e) + Rational32::from(9);
-        println!("{}", expr.le(Rational32::from(-10)))*/
+    /// Writes the start file into `dir` if a start is set, returning the
+    /// SCIP command reading it.
+    fn start_command(&self, dir: &Path, opts: &SolveOptions) -> std::io::Result<Option<String>> {
+        use std::io::{BufWriter, Write};
+
+        if self.start.is_none() {
+            return Ok(None);
+        }
+        let start_path = dir.join("start.sol");
+        let mut f = BufWriter::new(std::fs::File::create(&start_path)?);
+        self.write_start(&mut f, opts.missing_start_values)?;
+        f.flush()?;
+        Ok(Some(format!("read {}", start_path.to_string_lossy())))
     }
 
-    #[test]
-    fn test_export_and_import() {
-        let mut model = Model::<BigRational>::new();
+    /// `scip_script` with the files a solve with `opts` writes, relative to
+    /// its temporary directory unless they are kept in a given one.
+    fn scip_script_preview(&self, exact: bool, opts: &SolveOptions) -> Vec<String> {
+        let dir = match &opts.debug_artifacts {
+            DebugArtifacts::KeepIn(path) => path.clone(),
+            DebugArtifacts::None | DebugArtifacts::Keep => PathBuf::new(),
+        };
+        let start_command = self
+            .start
+            .as_ref()
+            .map(|_| format!("read {}", dir.join("start.sol").to_string_lossy()));
+        self.scip_script(
+            exact,
+            &dir.join("formulation.lp"),
+            start_command,
+            &dir.join("solution.sol"),
+            &dir.join("dual.sol"),
+            opts,
+        )
+    }
 
-        let x = model
-            .add_var()
-            .name("x")
-            .lb(BigRational::new(0.into(), 1.into()))
-            .build();
-        let y = model
-            .add_var()
-            .name("y")
-            .lb(BigRational::new(0.into(), 1.into()))
-            .build();
+    /// SCIP shell commands solving the model in `formulation` and writing
+    /// the solution, and duals if requested, to the given paths.
+    fn scip_script(
+        &self,
+        exact: bool,
+        formulation: &Path,
+        start_command: Option<String>,
+        solution: &Path,
+        duals: &Path,
+        opts: &SolveOptions,
+    ) -> Vec<String> {
+        let mut script = vec![];
+        if exact {
+            script.push("set exact enabled TRUE".to_string());
+        }
+        script.extend(self.scip_param_commands());
+        script.extend(self.commands.iter().cloned());
+        script.extend(opts.scip_commands());
+        script.push(format!("read {}", formulation.to_string_lossy()));
+        script.extend(start_command);
+        script.push("optimize".to_string());
+        // zeros are left out otherwise, which hides unreadable entries
+        script.push("set write printzeros TRUE".to_string());
+        script.push(format!("write solution {}", solution.to_string_lossy()));
+        if opts.duals {
+            script.push(format!("write dualsolution {}", duals.to_string_lossy()));
+        }
+        script.push("quit".to_string());
+        script
+    }
 
-        model.maximize();
-        model.set_objective(
-            x.clone() * BigRational::new(2.into(), 1.into())
-                + y.clone() * BigRational::new(5.into(), 1.into()),
-        );
+    /// Runs SCIP on `script`, passing it as `-c` arguments or on stdin as
+    /// `opts.command_input` asks.
+    fn run_scip(
+        &self,
+        script: Vec<String>,
+        opts: &SolveOptions,
+    ) -> Result<SolverOutput, SolveError> {
+        let mut command = self.solver_command(Solver::Scip);
+        match opts.command_input {
+            CommandInput::Arguments => {
+                for c in script {
+                    command.arg("-c").arg(c);
+                }
+                self.run_solver(&mut command, None, opts.quiet)
+            }
+            CommandInput::Stdin => {
+                let input = script.into_iter().map(|c| c + "\n").collect();
+                self.run_solver(&mut command, Some(input), opts.quiet)
+            }
+        }
+    }
 
-        model.add_const((x.clone() + c(4) * y.clone()).le(BigRational::new(24.into(), 1.into())));
-        model.add_const((c(3) * x.clone() + y.clone()).le(BigRational::new(21.into(), 1.into())));
-        model.add_const((x.clone() + y.clone()).le(BigRational::new(9.into(), 1.into())));
+    /// Fails unless every variable is continuous, as duals are only
+    /// meaningful for LPs.
+    fn check_duals_available(&self) -> Result<(), SolveError> {
+        match self
+            .variables
+            .iter()
+            .position(|v| !matches!(v.v_type, VariableType::Continuous))
+        {
+            Some(i) => Err(SolveError::DualsRequireLp(self.variable_name(i))),
+            None => Ok(()),
+        }
+    }
 
-        let solution = model.solve(false).unwrap();
+    /// Attaches `duals`, keyed by constraint name, to `solution` together
+    /// with the reduced costs they imply.
+    fn attach_duals(&self, solution: Solution<N>, duals: BTreeMap<String, N>) -> Solution<N> {
+        let constraint_names = (0..self.constraints.len())
+            .map(|i| self.constraint_name(i))
+            .collect::<Vec<_>>();
+        let mut reduced_costs = self
+            .live_variables()
+            .map(|(i, _)| (self.variable_name(i), N::zero()))
+            .collect::<BTreeMap<_, _>>();
+        for (coefficient, v) in self.objective_terms().0 {
+            if let Some(v) = v {
+                let rc = reduced_costs
+                    .get_mut(&v.name())
+                    .expect("variable of the model");
+                *rc = rc.clone() + coefficient;
+            }
+        }
+        for (i, c) in self.live_constraints() {
+            let Some(dual) = duals.get(&constraint_names[i]) else {
+                continue;
+            };
+            for (coefficient, v) in c.constraint.normalized().lhs.0 {
+                if let Some(v) = v {
+                    let rc = reduced_costs
+                        .get_mut(&v.name())
+                        .expect("variable of the model");
+                    *rc = rc.clone() - coefficient * dual.clone();
+                }
+            }
+        }
+        solution.with_duals(duals, constraint_names, reduced_costs)
+    }
 
-        assert_eq!(
-            solution.get_value(x.clone()),
-            BigRational::new(4.into(), 1.into())
+    /// Result of a model that the built-in backends solve without starting
+    /// a solver: infeasible if a constraint without an indicator can never
+    /// hold, which model files cannot express, and for a model without
+    /// variables otherwise optimal with the objective constant as value.
+    fn trivial_solution(&self, opts: &SolveOptions) -> Option<Solution<N>> {
+        let infeasible = self.enabled_constraints().any(|(_, c)| {
+            c.indicator.is_none()
+                && c.constraint.normalized().normalized_trivially_holds() == Some(false)
+        });
+        if infeasible {
+            return Some(Solution::new(
+                SolveStatus::Infeasible,
+                None,
+                BTreeMap::new(),
+            ));
+        }
+        if self.live_variables().next().is_some() {
+            return None;
+        }
+        let value = self.objective_constant();
+        let solution = Solution::new(SolveStatus::Optimal, Some(value.clone()), BTreeMap::new())
+            .with_bounds(Some(value.clone()), Some(value));
+        if !opts.duals {
+            return Some(solution);
+        }
+        let duals = self
+            .enabled_constraints()
+            .map(|(i, _)| (self.constraint_name(i), N::zero()))
+            .collect();
+        Some(self.attach_duals(solution, duals))
+    }
+
+    /// Solves `formulation`, which is equivalent to the model up to
+    /// `scaling`, with SCIP, in exact mode if `exact` is set, reading the
+    /// solution and dual files with `parse` and the bounds in the log with
+    /// `parse_value`.
+    fn solve_scip(
+        &self,
+        formulation: &Model<N>,
+        scaling: &Scaling<N>,
+        exact: bool,
+        opts: &SolveOptions,
+        parse: impl Fn(std::fs::File) -> Result<Solution<N>, SolveError>,
+        parse_value: impl Fn(&str) -> Option<N>,
+    ) -> Result<Solution<N>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.lp");
+        let solution_path = dir.path.join("solution.sol");
+        let dual_path = dir.path.join("dual.sol");
+        if opts.duals {
+            self.check_duals_available()?;
+        }
+        let start_command = self.start_command(&dir.path, opts)?;
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        formulation.write_lp(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        let script = self.scip_script(
+            exact,
+            &formulation_path,
+            start_command,
+            &solution_path,
+            &dual_path,
+            opts,
+        );
+        let start = std::time::Instant::now();
+        let out = self.run_scip(script, opts)?;
+        let stats = SolveStats::from_scip_log(&out.log, start.elapsed());
+        let out = if exact { out.check_exact()? } else { out };
+        let out = out.check(&solution_path)?;
+        let unscale = |v: Option<N>| v.map(|v| v / scaling.objective.clone());
+        let (primal_bound, dual_bound) = stats::scip_bounds(&out.log, parse_value);
+
+        let solution = parse(fs::File::open(&solution_path)?)?;
+        let objective_value = unscale(solution.objective_value().cloned());
+        let solution = solution
+            .with_objective_value(objective_value)
+            .with_bounds(unscale(primal_bound), unscale(dual_bound))
+            .with_stats(stats)
+            .with_log(out.log)
+            .with_objective_offset(self.objective_constant());
+        if !opts.duals {
+            return Ok(dir.finish(solution)?);
+        }
+        let scaled_duals = parse(fs::File::open(&dual_path)?)?;
+        let duals = self
+            .live_constraints()
+            .filter_map(|(i, _)| {
+                let name = self.constraint_name(i);
+                let dual = scaled_duals.values().get(&name)?.clone() * scaling.row(i)
+                    / scaling.objective.clone();
+                Some((name, dual))
+            })
+            .collect();
+        Ok(dir.finish(self.attach_duals(solution, duals))?)
+    }
+
+    /// The model as written by `write_lp`, or the error that fails the
+    /// export, such as `ModelError::InfeasibleConstraint`.
+    pub fn to_lp_string(&self) -> Result<String, ModelError> {
+        self.to_lp_string_with(&ExportOptions::default())
+    }
+
+    /// The model as written by `write_lp_with`.
+    pub fn to_lp_string_with(&self, options: &ExportOptions) -> Result<String, ModelError> {
+        let mut out = Vec::new();
+        if let Err(e) = self.write_lp_with(&mut out, options) {
+            // writing to a Vec does not fail, so the model did
+            let e = e.into_inner().and_then(|e| e.downcast::<ModelError>().ok());
+            return Err(*e.expect("only the model fails an export to a Vec"));
+        }
+        Ok(String::from_utf8(out).expect("the LP export is valid UTF-8"))
+    }
+}
+
+impl Model<BigRational> {
+    pub fn solve(
+        &self,
+        debug_artifacts: DebugArtifacts,
+    ) -> Result<Solution<BigRational>, SolveError> {
+        self.solve_with(&SolveOptions {
+            debug_artifacts,
+            ..Default::default()
+        })
+    }
+
+    pub fn solve_with(&self, opts: &SolveOptions) -> Result<Solution<BigRational>, SolveError> {
+        self.solve_using(&ScipBackend, opts)
+    }
+
+    /// The SCIP shell commands `solve_with(opts)` runs, in order. Files are
+    /// named after what they hold, e.g. `formulation.lp`, and are given
+    /// relative to the temporary directory of the solve unless
+    /// `DebugArtifacts::KeepIn` names one. Nothing depends on hash order or
+    /// the time, so the same model and options always give the same
+    /// commands.
+    pub fn solver_command_preview(&self, opts: &SolveOptions) -> Vec<String> {
+        self.scip_script_preview(self.solves_exactly(), opts)
+    }
+}
+
+impl Model<f64> {
+    pub fn solve(&self, debug_artifacts: DebugArtifacts) -> Result<Solution<f64>, SolveError> {
+        self.solve_with(&SolveOptions {
+            debug_artifacts,
+            ..Default::default()
+        })
+    }
+
+    pub fn solve_with(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        self.solve_using(&ScipBackend, opts)
+    }
+
+    /// The SCIP shell commands `solve_with(opts)` runs, see
+    /// `Model::<BigRational>::solver_command_preview`.
+    pub fn solver_command_preview(&self, opts: &SolveOptions) -> Vec<String> {
+        self.scip_script_preview(false, opts)
+    }
+
+    /// Solves with `solver` instead of SCIP. Commands added with
+    /// `add_command` are SCIP specific and only used by `Solver::Scip`,
+    /// parameters set with `set_param` are translated for each solver.
+    pub fn solve_with_solver(
+        &self,
+        solver: Solver,
+        opts: &SolveOptions,
+    ) -> Result<Solution<f64>, SolveError> {
+        match solver {
+            Solver::Scip => self.solve_using(&ScipBackend, opts),
+            Solver::Highs => self.solve_using(&HighsBackend, opts),
+            Solver::Cbc => self.solve_using(&CbcBackend, opts),
+            Solver::Glpk => self.solve_using(&GlpkBackend, opts),
+            Solver::Cplex => self.solve_using(&CplexBackend, opts),
+            Solver::Gurobi => self.solve_using(&GurobiBackend, opts),
+        }
+    }
+
+    fn solve_cbc(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let arguments = self.cbc_param_arguments()?;
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.mps");
+        let solution_path = dir.path.join("solution.sol");
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        self.export_mps(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        let mut command = self.solver_command(Solver::Cbc);
+        command
+            .arg(&formulation_path)
+            .args(arguments)
+            .args(opts.cbc_arguments())
+            .arg("solve")
+            .arg("solution")
+            .arg(&solution_path);
+        let out = self
+            .run_solver(&mut command, None, opts.quiet)?
+            .check(&solution_path)?;
+        let mut solution = Solution::<f64>::parse_cbc(fs::File::open(&solution_path)?)?;
+        if solution.objective_value().is_some() {
+            // CBC only writes nonzero values
+            solution =
+                solution.with_zeros_for(self.live_variables().map(|(i, _)| self.variable_name(i)));
+        }
+        // recomputed so the objective constant in the MPS file does not
+        // depend on how CBC reports it
+        let objective = solution.objective_value().map(|_| {
+            self.objective_expression()
+                .evaluate_or_zero(solution.values())
+        });
+        Ok(dir.finish(solution.with_log(out.log).with_objective_value(objective))?)
+    }
+
+    fn solve_highs(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let mut options = self.highs_param_options()?;
+        options.extend(opts.highs_options());
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.lp");
+        let options_path = dir.path.join("highs.opt");
+        let solution_path = dir.path.join("solution.sol");
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        self.write_lp(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        let mut command = self.solver_command(Solver::Highs);
+        command
+            .arg(&formulation_path)
+            .arg("--solution_file")
+            .arg(&solution_path);
+        if !options.is_empty() {
+            fs::write(&options_path, options.join("\n") + "\n")?;
+            command.arg("--options_file").arg(&options_path);
+        }
+        let out = self
+            .run_solver(&mut command, None, opts.quiet)?
+            .check(&solution_path)?;
+        let solution = Solution::<f64>::parse_highs(fs::File::open(&solution_path)?)?;
+        Ok(dir.finish(
+            solution
+                .with_log(out.log)
+                .with_objective_offset(self.objective_constant()),
+        )?)
+    }
+
+    fn solve_glpk(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let arguments = self.glpk_param_arguments()?;
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.lp");
+        let report_path = dir.path.join("report.txt");
+        let solution_path = dir.path.join("solution.sol");
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        self.write_lp(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        let mut command = self.solver_command(Solver::Glpk);
+        command
+            .arg("--lp")
+            .arg(&formulation_path)
+            .arg("-o")
+            .arg(&report_path)
+            .arg("-w")
+            .arg(&solution_path)
+            .args(arguments)
+            .args(opts.glpk_arguments());
+        let out = self
+            .run_solver(&mut command, None, opts.quiet)?
+            .check(&report_path)?;
+        let (mut solution, columns) =
+            Solution::<f64>::parse_glpk_report(fs::File::open(&report_path)?)?;
+        // the report rounds values to six digits and the objective to ten,
+        // the solution file has them exactly
+        let values = if solution.values().is_empty() || !fs::exists(&solution_path)? {
+            None
+        } else {
+            Solution::<f64>::parse_glpk_values(fs::File::open(&solution_path)?, &columns)?
+        };
+        solution = match values {
+            Some(values) => {
+                let objective = self.objective_expression().evaluate_or_zero(&values);
+                solution
+                    .with_values(values)
+                    .with_objective_value(Some(objective))
+            }
+            None => solution.with_objective_offset(self.objective_constant()),
+        };
+        if solution.status() != SolveStatus::Optimal && out.log.contains("TIME LIMIT EXCEEDED") {
+            solution = solution.with_status(SolveStatus::TimeLimit);
+        }
+        Ok(dir.finish(solution.with_log(out.log))?)
+    }
+
+    fn solve_cplex(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let mut commands = self.cplex_param_commands()?;
+        commands.extend(opts.cplex_commands());
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.lp");
+        let solution_path = dir.path.join("solution.sol");
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        self.write_lp(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        // the extension of the written file selects CPLEX's XML format
+        let mut command = self.solver_command(Solver::Cplex);
+        command
+            .arg("-c")
+            .arg(format!("read {}", formulation_path.display()))
+            .args(commands)
+            .arg("optimize")
+            .arg(format!("write {}", solution_path.display()));
+        let out = self.run_solver(&mut command, None, opts.quiet)?;
+        let out = match SolveStatus::from_cplex_log(&out.log) {
+            // there is no solution to write
+            status @ (SolveStatus::Infeasible | SolveStatus::Unbounded)
+                if out.status.success() && !fs::exists(&solution_path)? =>
+            {
+                let solution = Solution::new(status, None, BTreeMap::new());
+                return Ok(dir.finish(solution.with_log(out.log))?);
+            }
+            _ => out.check(&solution_path)?,
+        };
+        let solution = Solution::<f64>::parse_cplex(fs::File::open(&solution_path)?)?;
+        Ok(dir.finish(
+            solution
+                .with_log(out.log)
+                .with_objective_offset(self.objective_constant()),
+        )?)
+    }
+
+    fn solve_gurobi(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        use std::{
+            fs,
+            io::{BufWriter, Write},
+        };
+
+        let mut arguments = self.gurobi_param_arguments()?;
+        arguments.extend(opts.gurobi_arguments());
+        let dir = WorkDir::new(&opts.debug_artifacts)?;
+
+        let formulation_path = dir.path.join("formulation.lp");
+        let solution_path = dir.path.join("solution.sol");
+        let mut f = BufWriter::new(fs::File::create(&formulation_path)?);
+        self.write_lp(&mut f)?;
+        f.flush()?;
+        drop(f);
+
+        let mut command = self.solver_command(Solver::Gurobi);
+        let mut result_file = std::ffi::OsString::from("ResultFile=");
+        result_file.push(&solution_path);
+        command
+            .arg(result_file)
+            .args(arguments)
+            .arg(&formulation_path);
+        let out = self.run_solver(&mut command, None, opts.quiet)?;
+        let status = SolveStatus::from_gurobi_log(&out.log);
+        let out = match status {
+            // there is no solution to write
+            SolveStatus::Infeasible | SolveStatus::Unbounded
+                if out.status.success() && !fs::exists(&solution_path)? =>
+            {
+                let solution = Solution::new(status, None, BTreeMap::new());
+                return Ok(dir.finish(solution.with_log(out.log))?);
+            }
+            _ => out.check(&solution_path)?,
+        };
+        let solution = Solution::<f64>::parse_gurobi(fs::File::open(&solution_path)?)?;
+        Ok(dir.finish(
+            solution
+                .with_status(status)
+                .with_log(out.log)
+                .with_objective_offset(self.objective_constant()),
+        )?)
+    }
+}
+
+pub struct Constant<N>(pub N)
+where
+    N: Num + Clone;
+
+impl<IntoExpression, N> std::ops::Mul<IntoExpression> for Constant<N>
+where
+    N: Num + Clone,
+    IntoExpression: Into<Expression<N>>,
+{
+    type Output = Expression<N>;
+    fn mul(self, rhs: IntoExpression) -> Self::Output {
+        rhs.into() * self.0
+    }
+}
+
+/// Constraints written right-hand side first, e.g. `c(10).ge(&x + &y)`
+/// for `10 >= x + y`. They are built as the mirrored constraint, here
+/// `(&x + &y).le(10)`, so both ways of writing give the same constraint.
+impl<N> Constant<N>
+where
+    N: Num + Clone,
+{
+    pub fn le(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
+        expr.into().ge(self.0)
+    }
+
+    pub fn eq(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
+        expr.into().eq(self.0)
+    }
+
+    pub fn ge(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
+        expr.into().le(self.0)
+    }
+}
+
+impl<N> Constant<N>
+where
+    N: Num + Clone + FromPrimitive,
+{
+    /// The integer `n` as a coefficient of any type, e.g.
+    /// `Constant::int(3) * &x` for a variable of a `Model<Rational64>`,
+    /// where `c` only makes `BigRational`s.
+    ///
+    /// Panics if `n` does not fit the type.
+    pub fn int(n: i64) -> Self {
+        Constant(N::from_i64(n).expect("integer fits the coefficient type"))
+    }
+}
+
+pub fn c<N>(e: N) -> Constant<BigRational>
+where
+    N: Into<BigInt>,
+{
+    Constant(e.into().into())
+}
+
+/// The integer `n` as a `BigRational`, e.g. `rat(24)`.
+pub fn rat(n: impl Into<BigInt>) -> BigRational {
+    BigRational::from_integer(n.into())
+}
+
+/// The fraction `numer / denom` in lowest terms, e.g. `rat_frac(1, 3)`.
+///
+/// Panics if `denom` is zero.
+pub fn rat_frac(numer: impl Into<BigInt>, denom: impl Into<BigInt>) -> BigRational {
+    BigRational::new(numer.into(), denom.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use num::{
+        rational::{Rational32, Rational64},
+        BigRational,
+    };
+
+    use std::collections::BTreeMap;
+
+    #[cfg(unix)]
+    use crate::test_support::write_stub;
+    use crate::{
+        c, rat_frac, Aggressiveness, Bound, CommandInput, Constant, Constraint, DebugArtifacts,
+        Emphasis, EvaluationError, Expression, MissingStartValues, Model, ModelError, Param,
+        Solution, SolveError, SolveOptions, SolveStatus, Solver, VariableType,
+    };
+
+    #[test]
+    fn test_expression() {
+        let mut model = Model::<BigRational>::default();
+        let x = c(2) * model.add_var().name("a").build();
+        let y = c(3) * model.add_var().name("b").build();
+        let z = c(2) * y.clone();
+        model.add_var();
+        let k = c(6) * model.add_var().name("e").build();
+        let expr = (x + y) + (z + k);
+        println!("{expr}")
+    }
+
+    #[test]
+    fn test_expression2() {
+        /*let mut model = Model::<BigRational>::default();
+        let x = model.add_var().build() * 2.into();
+        let b = model.add_var().build() * 3.into();
+        let c = model.add_var().build() * 4.into();
+        let e = model.add_var().build() * 6.into();
+        let expr = (a + b) + (c + e);
+        println!("{expr}")*/
+    }
+
+    #[test]
+    fn test_expression3() {
+        let mut model = Model::<Rational32>::default();
+        let a = Constant::int(2) * model.add_var().name("a").build();
+        let b = Constant::int(3) * model.add_var().name("b").build();
+        let c = model.add_var().name("c").build() * Rational32::new(4, 3);
+        let e = Constant::int(6) * model.add_var().name("e").build();
+        let expr: Expression<Rational32> = (a + b) - (c + e) + Rational32::from(9);
+        assert_eq!(expr.to_string(), "2 a + 3 b - 4/3 c - 6 e + 9");
+    }
+
+    #[test]
+    fn test_constraint() {
+        let mut model = Model::<Rational32>::default();
+        let a = Constant::int(2) * model.add_var().name("a").build();
+        let b = model.add_var().name("b").build() * Rational32::new(4, 3);
+        let expr: Expression<Rational32> = a - b + Rational32::from(9);
+        assert_eq!(
+            expr.le(Rational32::from(-10)).to_string(),
+            "2 a - 4/3 b + 9 <= -10"
+        );
+    }
+
+    #[test]
+    fn test_export_and_import() {
+        let mut model = Model::<BigRational>::new();
+
+        let x = model
+            .add_var()
+            .name("x")
+            .lb(BigRational::new(0.into(), 1.into()))
+            .build();
+        let y = model
+            .add_var()
+            .name("y")
+            .lb(BigRational::new(0.into(), 1.into()))
+            .build();
+
+        model.maximize();
+        model.set_objective(
+            x.clone() * BigRational::new(2.into(), 1.into())
+                + y.clone() * BigRational::new(5.into(), 1.into()),
+        );
+
+        model.add_const((x.clone() + c(4) * y.clone()).le(BigRational::new(24.into(), 1.into())));
+        model.add_const((c(3) * x.clone() + y.clone()).le(BigRational::new(21.into(), 1.into())));
+        model.add_const((x.clone() + y.clone()).le(BigRational::new(9.into(), 1.into())));
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+
+        assert_eq!(
+            solution.get_value(x.clone()),
+            BigRational::new(4.into(), 1.into())
         );
         assert_eq!(
             solution.get_value(y.clone()),
             BigRational::new(5.into(), 1.into())
         );
+        assert_eq!(
+            solution.objective_value(),
+            Some(&BigRational::new(33.into(), 1.into()))
+        );
+        assert_eq!(model.binding_constraints(&solution, c(0).0), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_rhs_first_constraints() {
+        // the example model, x + 4 y <= 24, 3 x + y <= 21 and x + y <= 9
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        let normalized =
+            |rows: [Constraint<BigRational>; 3]| rows.map(|row| row.to_normalized().to_string());
+        let rows = normalized([
+            (&x + c(4) * &y).le(c(24).0),
+            (c(3) * &x + &y).le(c(21).0),
+            (&x + &y).le(c(9).0),
+        ]);
+        let rhs_first = normalized([
+            c(24).ge(&x + c(4) * &y),
+            c(21).ge(c(3) * &x + &y),
+            c(9).ge(&x + &y),
+        ]);
+        let literals = normalized([(&x + 4 * &y).le(24), (3 * &x + &y).le(21), (&x + &y).le(9)]);
+        assert_eq!(rhs_first, rows);
+        assert_eq!(literals, rows);
+        assert_eq!(rows[0], "x + 4 y <= 24");
+        for row in [c(24).ge(&x + c(4) * &y), (&x + 4 * &y).le(24)] {
+            model.add_const(row);
+        }
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(" c0: x + 4 y <= 24\n c1: x + 4 y <= 24\n"),
+            "{lp}"
+        );
+
+        assert_eq!(c(3).le(&x).to_string(), "x >= 3");
+        assert_eq!(c(3).eq(&x + 1).to_string(), "x + 1 = 3");
+        // integers are converted exactly
+        assert_eq!(
+            (&x + i64::MAX).ge(i64::MIN).to_normalized().to_string(),
+            "x >= -18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_rationals() {
+        let mut model = Model::<Rational64>::new();
+        let x = model.add_var().name("x").lb(Rational64::from(0)).build();
+        let y = model.add_var().name("y").lb(Rational64::from(0)).build();
+
+        model.maximize();
+        model.set_objective(Constant::int(2) * &x + Constant::int(5) * &y);
+        model.add_const((&x + Constant::int(4) * &y).le(Rational64::from(24)));
+        model.add_const((Constant::int(3) * &x + &y).le(Rational64::from(21)));
+        model.add_const((&x + &y).le(Rational64::new(19, 2)));
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), Rational64::new(14, 3));
+        assert_eq!(solution.get_value(&y), Rational64::new(29, 6));
+        assert_eq!(solution.objective_value(), Some(&Rational64::new(67, 2)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exact_mode_unavailable() {
+        // a SCIP build that answers `set exact enabled TRUE` with `reply`
+        // and goes on in floating point
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = |name: &str, reply: &str| {
+            let dir = dir.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            write_stub(
+                &dir,
+                &format!(
+                    "echo 'presolving (exact): 0 rounds, symmetry not found'\n\
+                     for arg in \"$@\"; do\n\
+                     case \"$arg\" in\n\
+                     \"set exact enabled TRUE\") echo '{reply}' ;;\n\
+                     \"write solution \"*) printf 'objective value: 4/3\\nx 4/3\\n' > \"${{arg#write solution }}\" ;;\n\
+                     esac\n\
+                     done\n"
+                ),
+            )
+        };
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        model.set_objective(Expression::from(&x));
+        for (name, reply) in [
+            ("no-menu", "command <exact> not found"),
+            (
+                "no-parameter",
+                "[paramset.c:2300] ERROR: parameter <exact/enabled> unknown",
+            ),
+        ] {
+            model.set_solver_path(Solver::Scip, stub(name, reply));
+            let error = model.solve(DebugArtifacts::None).err();
+            assert!(
+                matches!(error, Some(SolveError::ExactModeUnavailable)),
+                "{error:?}"
+            );
+        }
+
+        // other log lines about exact presolving are not a rejection
+        model.set_solver_path(Solver::Scip, stub("exact", ""));
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert!(!solution.is_approximate());
+        assert_eq!(solution.get_value(&x), rat_frac(4, 3));
+
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"set exact enabled TRUE\") echo 'command <exact> not found' ;;\n\
+             \"write solution \"*) printf 'objective value: 1.33333333333333\\nx 1.33333333333333\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+        model.set_solver_path(Solver::Scip, &stub);
+
+        model.set_exact(false);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert!(solution.is_approximate());
+        assert!(!solution.solver_log().contains("not found"));
+        assert_eq!(
+            solution.get_value(&x),
+            rat_frac(133333333333333_i64, 100000000000000_i64)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fixed_width_overflow() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'objective value: 1/3\\nx 1/3\\ny 4294967296\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = Model::<Rational32>::new();
+        let x = model.add_var().name("x").build();
+        model.add_var().name("y").build();
+        model.set_objective(Expression::from(&x));
+        model.set_solver_path(Solver::Scip, &stub);
+        let error = model.solve(DebugArtifacts::None).err();
+        assert_eq!(
+            error.map(|e| e.to_string()),
+            Some("solution value `4294967296` does not fit the coefficient type".to_string())
+        );
+
+        let mut model = Model::<Rational64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.set_objective(Expression::from(&x));
+        model.set_solver_path(Solver::Scip, &stub);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), Rational64::new(1, 3));
+        assert_eq!(solution.get_value(&y), Rational64::from(1 << 32));
+        assert_eq!(solution.objective_value(), Some(&Rational64::new(1, 3)));
+    }
+
+    #[test]
+    fn test_solver_not_found() {
+        let mut model = Model::<BigRational>::new();
+        model.set_solver_path(Solver::Scip, "exact_lp-nonexistent-solver");
+        let x = model.add_var().name("x").build();
+        model.set_objective(Expression::from(x));
+
+        let Err(SolveError::SolverNotFound(solver)) = model.solve(DebugArtifacts::None) else {
+            panic!("expected SolverNotFound");
+        };
+        assert_eq!(solver, PathBuf::from("exact_lp-nonexistent-solver"));
+
+        let mut model = Model::<f64>::new();
+        model.set_solver_path(Solver::Scip, "exact_lp-nonexistent-solver");
+        model.add_var().name("x").build();
+        assert!(matches!(
+            model.solve(DebugArtifacts::None),
+            Err(SolveError::SolverNotFound(_))
+        ));
+
+        // each solver has its own path
+        model.set_solver_path(Solver::Highs, "exact_lp-nonexistent-highs");
+        let Err(SolveError::SolverNotFound(solver)) =
+            model.solve_with_solver(Solver::Highs, &SolveOptions::default())
+        else {
+            panic!("expected SolverNotFound");
+        };
+        assert_eq!(solver, PathBuf::from("exact_lp-nonexistent-highs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_solver_stub() {
+        // answers `write solution <path>` with a canned solution file, and
+        // fails unless called with the prefix argument
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ \"$1\" = --stub ] || exit 1\n\
+                 echo stub banner\n\
+                 echo 'Solving Nodes      : 7'\n\
+                 echo 'Primal Bound       : +9.80000000000000e+01 (12 solutions)'\n\
+                 echo 'Dual Bound         : 112'\n\
+                 for arg in \"$@\"; do\n\
+                 case \"$arg\" in\n\
+                 \"write solution \"*) cp {} \"${{arg#write solution }}\" ;;\n\
+                 esac\n\
+                 done\n",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/optimal.sol")
+            ),
+        );
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        model.set_solver_path(Solver::Scip, &stub);
+        assert!(matches!(
+            model.solve(DebugArtifacts::None),
+            Err(SolveError::SolverFailed { .. })
+        ));
+
+        model.set_solver_prefix_args(vec!["--stub".to_string()]);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x), c(4).0);
+        let stats = solution.stats().unwrap();
+        assert_eq!((stats.nodes, stats.solving_time), (Some(7), None));
+        assert_eq!(solution.primal_bound(), Some(&c(98).0));
+        assert_eq!(solution.dual_bound(), Some(&c(112).0));
+        assert_eq!(solution.gap(), Some(rat_frac(1, 7)));
+        assert!(!solution.is_approximate());
+
+        let mut model = Model::<f64>::new();
+        let y = model.add_var().name("y").build();
+        model.set_solver_path(Solver::Scip, &stub);
+        model.set_solver_prefix_args(vec!["--stub".to_string()]);
+        let solution = model
+            .solve_with(&SolveOptions {
+                quiet: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(solution.get_value(y), 5.0);
+        assert!(solution
+            .solver_log()
+            .starts_with("stub banner\nSolving Nodes      : 7\n"));
+        assert_eq!(
+            (solution.primal_bound(), solution.dual_bound()),
+            (Some(&98.0), Some(&112.0))
+        );
+    }
+
+    #[test]
+    fn test_named_constraints() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        model
+            .add_const_named("capacity", (x.clone() + y.clone()).le(c(4).0))
+            .unwrap();
+        model.add_const(x.clone().ge(c(1).0));
+
+        assert_eq!(
+            model.add_const_named("capacity", y.clone().le(c(2).0)),
+            Err(ModelError::DuplicateName("capacity".to_string()))
+        );
+        assert_eq!(
+            model.add_const_named("has space", y.clone().le(c(2).0)),
+            Err(ModelError::InvalidName("has space".to_string()))
+        );
+        assert_eq!(
+            model.add_const_named("2nd", y.clone().le(c(2).0)),
+            Err(ModelError::InvalidName("2nd".to_string()))
+        );
+        // would clash with the unnamed constraint 1 and the MPS objective
+        for name in ["c1", "c7", "obj"] {
+            assert_eq!(
+                model.add_const_named(name, y.clone().le(c(2).0)),
+                Err(ModelError::ReservedName(name.to_string()))
+            );
+        }
+        model.add_const_named("cap1", y.clone().le(c(2).0)).unwrap();
+        model.add_const_named("c", y.clone().le(c(2).0)).unwrap();
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains("\n capacity: x + y <= 4\n"), "{lp}");
+        assert!(lp.contains("\n c1: x >= 1\n"), "{lp}");
+    }
+
+    #[test]
+    fn test_simplify() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let mut sum = Expression::default();
+        for _ in 0..1000 {
+            sum += x.clone();
+        }
+        model.add_const(sum.le(c(1000).0));
+
+        let mut e = x.clone() + y.clone() + c(2).0 - x.clone() + c(3).0;
+        e.simplify();
+        assert_eq!(e.0.len(), 2);
+        assert_eq!(e.0[0].0, c(1).0);
+        assert_eq!(e.0[0].1.as_ref().unwrap().id(), y.id());
+        assert_eq!(e.0[1].0, c(5).0);
+        assert!(e.0[1].1.is_none());
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains("\n c0: 1000 x <= 1000\n"), "{lp}");
+    }
+
+    #[test]
+    fn test_neg_variable() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(3).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+
+        let neg = -x.clone();
+        assert_eq!(neg.0.len(), 1);
+        assert_eq!(neg.0[0].0, c(-1).0);
+        assert_eq!((-&x).0[0].0, c(-1).0);
+
+        // y <= 5 - x, so maximizing y - x picks x = 0 and y = 5
+        model.maximize();
+        model.set_objective(-&x + y.clone());
+        model.add_const((-x.clone() + c(5).0).ge(y.clone()));
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x), c(0).0);
+        assert_eq!(solution.get_value(y), c(5).0);
+    }
+
+    #[test]
+    fn test_between() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+
+        model.maximize();
+        model.set_objective(x.clone() + y.clone());
+        model.add_const((x.clone() + y.clone() + c(1).0).between(c(3).0, c(8).0));
+        model.add_const(x.clone().between(c(1).0, c(2).0));
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains("\n c0: 2 <= x + y <= 7\n"), "{lp}");
+        assert!(lp.contains("\n c1: 1 <= x <= 2\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x + y), c(7).0);
+    }
+
+    #[test]
+    fn test_sum() {
+        let mut model = Model::<BigRational>::new();
+        let vars = (0..100)
+            .map(|i| {
+                model
+                    .add_var()
+                    .name(format!("x{i}"))
+                    .lb(c(0).0)
+                    .ub(c(i).0)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+
+        let sum = vars.iter().cloned().sum::<Expression<_>>();
+        assert_eq!(sum.0.len(), 100);
+        let doubled = vars
+            .iter()
+            .map(|v| c(2) * v.clone())
+            .chain(vars.iter().map(|v| Expression::from(v.clone())))
+            .sum::<Expression<_>>();
+        assert_eq!(doubled.0.len(), 100);
+        assert!(doubled.0.iter().all(|(w, _)| *w == c(3).0));
+
+        let mut objective = Expression::default();
+        for v in vars.iter() {
+            objective += v.clone();
+        }
+        objective -= vars[0].clone();
+        objective -= c(1).0;
+        assert_eq!(objective.0.len(), 102);
+
+        model.maximize();
+        model.set_objective(objective);
+        model.add_const(sum.le(c(100).0));
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(
+            solution.get_value(vars.into_iter().sum::<Expression<_>>()),
+            c(100).0
+        );
+    }
+
+    #[test]
+    fn test_solve_options() {
+        let opts = SolveOptions {
+            time_limit: Some(std::time::Duration::from_millis(1500)),
+            gap: Some(0.01),
+            node_limit: Some(1000),
+            threads: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            opts.scip_commands(),
+            vec![
+                "set limits time 1.5",
+                "set limits gap 0.01",
+                "set limits nodes 1000",
+                "set parallel maxnthreads 4",
+            ]
+        );
+        assert!(SolveOptions::default().scip_commands().is_empty());
+
+        let opts = opts.with_random_seed(42).with_deterministic(true);
+        assert_eq!(
+            opts.scip_commands()[3..],
+            [
+                "set parallel maxnthreads 1",
+                "set randomization permutevars FALSE",
+                "set randomization permuteconss FALSE",
+                "set randomization randomseedshift 42",
+                "set randomization permutationseed 42",
+                "set randomization lpseed 42",
+            ]
+        );
+        assert_eq!(
+            opts.highs_options()[3..],
+            ["threads = 1", "random_seed = 42"]
+        );
+        assert_eq!(opts.gurobi_arguments()[3..], ["Threads=1", "Seed=42"]);
+    }
+
+    #[test]
+    fn test_solver_command_preview() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(3).0).build();
+        model.set_objective((&x).into());
+        model.set_exact(true);
+        model.set_param(Param::Presolve(false));
+        model.set_start(&Solution::new(
+            SolveStatus::Unknown,
+            None,
+            [("x".to_string(), c(1).0)].into(),
+        ));
+        let opts = SolveOptions {
+            debug_artifacts: DebugArtifacts::KeepIn(PathBuf::from("run")),
+            threads: Some(4),
+            ..Default::default()
+        }
+        .with_random_seed(7)
+        .with_deterministic(true);
+
+        let preview = model.solver_command_preview(&opts);
+        assert_eq!(
+            preview,
+            vec![
+                "set exact enabled TRUE",
+                "set presolving emphasis off",
+                "set parallel maxnthreads 1",
+                "set randomization permutevars FALSE",
+                "set randomization permuteconss FALSE",
+                "set randomization randomseedshift 7",
+                "set randomization permutationseed 7",
+                "set randomization lpseed 7",
+                &format!("read {}", Path::new("run").join("formulation.lp").display()),
+                &format!("read {}", Path::new("run").join("start.sol").display()),
+                "optimize",
+                "set write printzeros TRUE",
+                &format!(
+                    "write solution {}",
+                    Path::new("run").join("solution.sol").display()
+                ),
+                "quit",
+            ]
+        );
+        assert_eq!(model.clone().solver_command_preview(&opts), preview);
+
+        let mut model = Model::<f64>::new();
+        model.add_var().name("y").build();
+        assert_eq!(
+            model.solver_command_preview(&SolveOptions::default()),
+            vec![
+                "read formulation.lp",
+                "optimize",
+                "set write printzeros TRUE",
+                "write solution solution.sol",
+                "quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_limit_bounds() {
+        let weights = [23, 31, 29, 44, 53, 38, 63, 85, 89, 82];
+        let values = [92, 57, 49, 68, 60, 43, 67, 84, 87, 72];
+        let mut model = Model::<BigRational>::new();
+        let items = (0..weights.len())
+            .map(|i| model.add_var().name(format!("item{i}")).binary().build())
+            .collect::<Vec<_>>();
+        let weight = items
+            .iter()
+            .zip(weights)
+            .map(|(x, w)| c(w) * x)
+            .sum::<Expression<_>>();
+        model.add_const(weight.le(c(165).0));
+        model.maximize();
+        model.set_objective(items.iter().zip(values).map(|(x, v)| c(v) * x).sum());
+        model.set_param(Param::NodeLimit(1));
+        model.set_param(Param::Presolve(false));
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        let primal = solution.primal_bound().unwrap();
+        assert_eq!(solution.objective_value(), Some(primal));
+        assert!(solution.dual_bound().unwrap() >= primal);
+        assert!(solution.gap().unwrap() >= c(0).0);
+        if solution.status() == SolveStatus::Optimal {
+            assert_eq!(solution.gap(), Some(c(0).0));
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let mut model = Model::<f64>::new();
+        model.add_var().name("x").build();
+        model.add_command("set display verblevel 0");
+        model.set_param(Param::Heuristics(Aggressiveness::Off));
+        model.set_param(Param::TimeLimit(std::time::Duration::from_secs(60)));
+        model.set_param(Param::RandomSeed(7));
+        model.set_param(Param::TimeLimit(std::time::Duration::from_secs(30)));
+        model.set_param(Param::Presolve(false));
+        let opts = SolveOptions {
+            gap: Some(0.01),
+            ..Default::default()
+        };
+        let script = model.scip_script(
+            false,
+            Path::new("f.lp"),
+            None,
+            Path::new("s.sol"),
+            Path::new("d.sol"),
+            &opts,
+        );
+        assert_eq!(
+            script,
+            vec![
+                "set limits time 30",
+                "set randomization randomseedshift 7",
+                "set presolving emphasis off",
+                "set heuristics emphasis off",
+                "set display verblevel 0",
+                "set limits gap 0.01",
+                "read f.lp",
+                "optimize",
+                "set write printzeros TRUE",
+                "write solution s.sol",
+                "quit",
+            ]
+        );
+        assert_eq!(
+            model.cbc_param_arguments().unwrap(),
+            vec![
+                "seconds",
+                "30",
+                "randomCbcSeed",
+                "7",
+                "presolve",
+                "off",
+                "heuristicsOnOff",
+                "off",
+            ]
+        );
+        assert!(matches!(
+            model.solve_with_solver(Solver::Highs, &opts),
+            Err(SolveError::UnsupportedParameter {
+                solver: Solver::Highs,
+                ..
+            })
+        ));
+
+        model.set_param(Param::Heuristics(Aggressiveness::Default));
+        model.set_param(Param::Emphasis(Emphasis::Feasibility));
+        let script = model.scip_script(
+            false,
+            Path::new("f.lp"),
+            None,
+            Path::new("s.sol"),
+            Path::new("d.sol"),
+            &opts,
+        );
+        assert_eq!(
+            script[..6],
+            [
+                "set emphasis feasibility",
+                "set limits time 30",
+                "set randomization randomseedshift 7",
+                "set presolving emphasis off",
+                "set heuristics emphasis default",
+                "set display verblevel 0",
+            ]
+        );
+        assert_eq!(
+            model
+                .solve_with_solver(Solver::Cbc, &opts)
+                .err()
+                .map(|e| e.to_string()),
+            Some("parameter `Emphasis(Feasibility)` is not supported by Cbc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lp_snapshot() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model
+            .add_var()
+            .name("y")
+            .integer()
+            .lb(c(0).0)
+            .ub(c(10).0)
+            .build();
+        let b = model.add_var().name("b").binary().build();
+        let z = model.add_var().integer().lb_neg_inf().ub(c(4).0).build();
+        let f = model.add_var().name("f").free().build();
+
+        model.maximize();
+        model.set_objective(c(2) * x.clone() + c(5) * y.clone() - b.clone());
+        model.add_const((x.clone() + c(4) * y.clone()).le(c(24).0));
+        model.add_const((z.clone() + f.clone() - b.clone()).ge(c(-2).0));
+
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Maximize\n\
+             \x20obj: 2 x + 5 y - b\n\
+             Subject To\n\
+             \x20c0: x + 4 y <= 24\n\
+             \x20c1: v3 + f - b >= -2\n\
+             Bounds\n\
+             \x200 <= x <= +inf\n\
+             \x200 <= y <= 10\n\
+             \x20-inf <= v3 <= 4\n\
+             \x20f free\n\
+             General\n\
+             \x20y\n\
+             \x20v3\n\
+             Binary\n\
+             \x20b\n\
+             End\n"
+        );
+    }
+
+    #[test]
+    fn test_lp_integer_sections() {
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("b").binary().build();
+        model.add_var().binary().build();
+        model.add_var().integer().build();
+        model.add_var().name("n").integer().build();
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.ends_with("General\n v2\n n\nBinary\n b\n v1\nEnd\n"),
+            "{lp}"
+        );
+
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("b").binary().build();
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.ends_with("General\nBinary\n b\nEnd\n"), "{lp}");
+
+        let mut model = Model::<BigRational>::new();
+        model.add_var().integer().build();
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.ends_with("General\n v0\nBinary\nEnd\n"), "{lp}");
+    }
+
+    #[test]
+    fn test_large_export() {
+        let mut model = Model::<BigRational>::new();
+        let x = (0..1000)
+            .map(|i| model.add_var().name(format!("x{i}")).lb(c(0).0).build())
+            .collect::<Vec<_>>();
+        model.set_objective(x.iter().cloned().sum());
+        for i in 0..100_000 {
+            let (a, b) = (&x[i % 1000], &x[(i * 7 + 1) % 1000]);
+            model.add_const((c(3) * a + b - c(2) * a + c(1).0).ge(c(i as i64).0));
+        }
+
+        let start = std::time::Instant::now();
+        let mut out = vec![];
+        model.write_lp(&mut out).unwrap();
+        assert!(start.elapsed().as_secs() < 30, "{:?}", start.elapsed());
+        let lp = String::from_utf8(out).unwrap();
+        // the objective of 1000 terms is wrapped
+        let rows = lp.lines().filter(|line| !line.starts_with("   "));
+        assert_eq!(rows.count(), 100_000 + 1000 + 7);
+        assert!(lp.contains(" c99999: x999 + x994 >= 99998\n"), "{lp}");
+    }
+
+    #[test]
+    fn test_trivial_constraints() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let b = model.add_var().name("b").binary().build();
+
+        assert_eq!((&x + &x).le(c(4).0).trivially_holds(), None);
+        assert_eq!((&x - &x).le(c(4).0).trivially_holds(), Some(true));
+        assert_eq!((&x - &x).ge(c(4).0).trivially_holds(), Some(false));
+        assert_eq!((&x - &x + c(1).0).eq(c(1).0).trivially_holds(), Some(true));
+        assert_eq!(
+            (&y * c(0).0).between(c(-1).0, c(0).0).trivially_holds(),
+            Some(true)
+        );
+        assert_eq!(
+            (&y * c(0).0).between(c(1).0, c(2).0).trivially_holds(),
+            Some(false)
+        );
+
+        model.add_const((&x + &x + &y - &y).le(c(4).0));
+        model.add_const((&x - &x).le(c(4).0));
+        model
+            .add_indicator(b.clone(), true, (&y - &y).ge(c(1).0))
+            .unwrap();
+        model
+            .add_indicator(b.clone(), false, (&y - &y).le(c(1).0))
+            .unwrap();
+        assert!(
+            model.to_lp_string().unwrap().contains(
+                "Subject To\n \
+                 c0: 2 x <= 4\n \
+                 c2: b = 0\n\
+                 Bounds\n"
+            ),
+            "{}",
+            model.to_lp_string().unwrap()
+        );
+        let mut mps = Vec::new();
+        model.export_mps(&mut mps).unwrap();
+        let mps = String::from_utf8(mps).unwrap();
+        assert!(
+            mps.contains("ROWS\n N obj\n L c0\n E c2\nCOLUMNS\n"),
+            "{mps}"
+        );
+
+        model.add_const((&x - &x + c(1).0).le(c(0).0));
+        let infeasible = || ModelError::InfeasibleConstraint("c4".to_string());
+        assert_eq!(model.to_lp_string(), Err(infeasible()));
+        let mut out = Vec::new();
+        let e = model.export_mps(&mut out).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            e.get_ref().unwrap().downcast_ref::<ModelError>(),
+            Some(&infeasible())
+        );
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_lp_variable_bounds() {
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("b").binary().build();
+        model
+            .add_var()
+            .name("c")
+            .binary()
+            .lb(c(0).0)
+            .ub(c(1).0)
+            .build();
+        model.add_var().name("d").binary().lb(c(1).0).build();
+        model.add_var().name("e").binary().fix(c(0).0).build();
+        model
+            .add_var()
+            .name("n")
+            .integer()
+            .lb(c(-3).0)
+            .ub(c(3).0)
+            .build();
+        model.add_var().name("m").integer().lb(c(1).0).build();
+        model.add_var().name("k").integer().free().build();
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.ends_with(
+                "Bounds\n \
+                 1 <= d <= 1\n \
+                 0 <= e <= 0\n \
+                 -3 <= n <= 3\n \
+                 1 <= m <= +inf\n \
+                 k free\n\
+                 General\n n\n m\n k\n\
+                 Binary\n b\n c\n d\n e\n\
+                 End\n"
+            ),
+            "{lp}"
+        );
+
+        assert_eq!(
+            model
+                .add_var()
+                .name("f")
+                .binary()
+                .ub(c(5).0)
+                .try_build()
+                .err(),
+            Some(ModelError::InvalidBinaryValue("f".to_string()))
+        );
+        assert_eq!(
+            model
+                .add_var()
+                .binary()
+                .lb(BigRational::new(1.into(), 2.into()))
+                .try_build()
+                .err(),
+            Some(ModelError::InvalidBinaryValue("v7".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_default_bounds() {
+        let mut model = Model::<BigRational>::new();
+        let builder = model.add_var().name("a");
+        assert_eq!(
+            builder.bounds(),
+            (&Bound::default_lower(), &Bound::default_upper())
+        );
+        assert_eq!(builder.bounds(), (&Bound::Finite(c(0).0), &Bound::PosInf));
+        let a = builder.build();
+        model.add_var().name("b").ub(c(4).0).build();
+        model.add_var().name("c").lb_neg_inf().ub(c(4).0).build();
+        model.add_var().name("d").lb(c(1).0).build();
+        model.add_var().name("e").ub(c(4).0).ub_pos_inf().build();
+        let f = model.add_var().name("f").fix(c(2).0).free().build();
+        let g = model.add_var().name("g").lb_neg_inf().build();
+        assert_eq!(
+            model.bounds(&a),
+            Ok((&Bound::Finite(c(0).0), &Bound::PosInf))
+        );
+        assert_eq!(model.bounds(&f), Ok((&Bound::NegInf, &Bound::PosInf)));
+        assert_eq!(model.var_bounds(&g), Ok((None, None)));
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(
+                "Bounds\n \
+                 0 <= a <= +inf\n \
+                 0 <= b <= 4\n \
+                 -inf <= c <= 4\n \
+                 1 <= d <= +inf\n \
+                 0 <= e <= +inf\n \
+                 f free\n \
+                 g free\n"
+            ),
+            "{lp}"
+        );
+        let read = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(read.to_lp_string().unwrap(), lp);
+    }
+
+    #[test]
+    fn test_variable_names() {
+        let mut model = Model::<BigRational>::new();
+        assert_eq!(
+            model.add_var().name("x y").try_build().err(),
+            Some(ModelError::InvalidName("x y".to_string()))
+        );
+        assert_eq!(
+            model.add_var().name("2cost").try_build().err(),
+            Some(ModelError::InvalidName("2cost".to_string()))
+        );
+        assert_eq!(
+            model.add_var().name("e12").try_build().err(),
+            Some(ModelError::InvalidName("e12".to_string()))
+        );
+        assert_eq!(
+            model.add_var().name("Free").try_build().err(),
+            Some(ModelError::InvalidName("Free".to_string()))
+        );
+        let x = model.add_var().name("x").try_build().unwrap();
+        assert_eq!(
+            model.add_var().name("x").try_build().err(),
+            Some(ModelError::DuplicateName("x".to_string()))
+        );
+
+        let spaced = model.add_var().name("x y").build();
+        let cost = model.add_var().name("2cost").build();
+        let exponent = model.add_var().name("e12").build();
+        let free = model.add_var().name("free").build();
+        let duplicate = model.add_var().name("x").build();
+        assert_eq!(spaced.name(), "x_y");
+        assert_eq!(cost.name(), "_2cost");
+        assert_eq!(exponent.name(), "_e12");
+        assert_eq!(free.name(), "_free");
+        assert_eq!(duplicate.name(), "x_1");
+
+        model.add_const((x.clone() + spaced.clone() + cost.clone()).le(c(3).0));
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains(" c0: x + x_y + _2cost <= 3\n"), "{lp}");
+
+        let solution = Solution::<BigRational>::parse(
+            "solution status: optimal solution found\n\
+             x_y 1 (obj:0)\n\
+             _2cost 2 (obj:0)\n\
+             x_1 3 (obj:0)\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(solution.get_value(spaced), c(1).0);
+        assert_eq!(solution.get_value(cost), c(2).0);
+        assert_eq!(solution.get_value(duplicate), c(3).0);
+        assert_eq!(solution.get_value(x), c(0).0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_default_name_collision() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"read \"*) grep -q '^ c0: v1 + 2 v1_1 + v2_1 <= 10$' \"${arg#read }\" || exit 1 ;;\n\
+             \"write solution \"*) printf 'v1 4\\nv1_1 3\\nv2 0\\nv2_1 0\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = Model::<BigRational>::new();
+        let named = model.add_var().name("v1").build();
+        let unnamed = model.add_var().build();
+        assert_eq!(unnamed.name(), "v1_1");
+        let second = model.add_var().build();
+        assert_eq!(second.name(), "v2");
+        assert_eq!(
+            model.add_var().name("v2").try_build().err(),
+            Some(ModelError::DuplicateName("v2".to_string()))
+        );
+        let renamed = model.add_var().name("v2").build();
+        assert_eq!(renamed.name(), "v2_1");
+
+        model.add_const((&named + c(2) * &unnamed + &renamed).le(c(10).0));
+        model.set_solver_path(Solver::Scip, &stub);
+        let solution = model
+            .solve_with(&SolveOptions {
+                quiet: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(solution.get_value(named), c(4).0);
+        assert_eq!(solution.get_value(unnamed), c(3).0);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let z = model.add_var().name("z").build();
+
+        let values = BTreeMap::from([("x".to_string(), c(2).0), ("y".to_string(), c(-3).0)]);
+        let e = c(4) * x.clone() + y.clone() + c(7).0 - x.clone();
+        assert_eq!(e.try_evaluate(&values), Ok(c(10).0));
+        assert_eq!(e.evaluate_or_zero(&values), c(10).0);
+        assert_eq!(Expression::default().try_evaluate(&values), Ok(c(0).0));
+
+        let e = e + c(5) * z;
+        assert_eq!(
+            e.try_evaluate(&values),
+            Err(EvaluationError::MissingValue("z".to_string()))
+        );
+        assert_eq!(e.evaluate_or_zero(&values), c(10).0);
+    }
+
+    #[test]
+    fn test_indicator() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(10).0).build();
+        let y = model.add_var().name("y").binary().build();
+        let z = model.add_var().name("z").integer().build();
+
+        assert_eq!(
+            model.add_indicator(z.clone(), true, x.clone().le(c(5).0)),
+            Err(ModelError::NotBinary("z".to_string()))
+        );
+        assert_eq!(
+            model.add_indicator(y.clone(), true, x.clone().between(c(1).0, c(5).0)),
+            Err(ModelError::RangeNotSupported)
+        );
+        model
+            .add_indicator(y.clone(), true, x.clone().le(c(5).0))
+            .unwrap();
+        model
+            .add_indicator(y.clone(), false, x.clone().ge(c(2).0))
+            .unwrap();
+        let mut mps = Vec::new();
+        model.export_mps(&mut mps).unwrap();
+        let mps = String::from_utf8(mps).unwrap();
+        assert!(
+            mps.contains("INDICATORS\n IF c0 y 1\n IF c1 y 0\n"),
+            "{mps}"
+        );
+        let off = Solution::<BigRational>::parse("x 1 (obj:0)\ny 1 (obj:0)\n".as_bytes()).unwrap();
+        assert!(model.check_feasibility(&off).is_empty());
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains("\n c0: y = 1 -> x <= 5\n"), "{lp}");
+        assert!(lp.contains("\n c1: y = 0 -> x >= 2\n"), "{lp}");
+
+        // forcing the indicator on caps x at 5
+        model.maximize();
+        model.set_objective(x.clone() + z.clone() * c(0).0);
+        model.add_const(z.clone().eq(c(0).0));
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x.clone()), c(10).0);
+        model.add_const(y.clone().eq(c(1).0));
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x), c(5).0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_highs_stub() {
+        // expects `<model> --solution_file <path> [--options_file <path>]`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ -f \"$1\" ] && [ \"$2\" = --solution_file ] || exit 1\n\
+                 [ \"$4\" = --options_file ] && grep -q 'time_limit = 2' \"$5\" || exit 1\n\
+                 cp {} \"$3\"\n",
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/highs_optimal.sol"
+                )
+            ),
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.set_solver_path(Solver::Highs, &stub);
+        let solution = model
+            .solve_with_solver(
+                Solver::Highs,
+                &SolveOptions {
+                    time_limit: Some(std::time::Duration::from_secs(2)),
+                    quiet: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(solution.get_value(x), 4.0);
+        assert_eq!(solution.get_value(y), 5.0);
+        assert_eq!(solution.objective_value(), Some(&33.0));
+    }
+
+    #[test]
+    fn test_write_start() {
+        let mut model = Model::<BigRational>::new();
+        let _x = model.add_var().name("x").build();
+        let _y = model.add_var().name("y").lb(c(2).0).build();
+        let _z = model.add_var().name("z").free().build();
+        let start = Solution::<BigRational>::parse("x 1/3\n".as_bytes()).unwrap();
+        model.set_start(&start);
+
+        let mut out = Vec::new();
+        model
+            .write_start(&mut out, MissingStartValues::Omit)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "x 1/3\n");
+
+        let mut out = Vec::new();
+        model
+            .write_start(&mut out, MissingStartValues::LowerBound)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "x 1/3\ny 2\n");
+    }
+
+    #[test]
+    fn test_warm_start() {
+        // knapsack whose optimum is hard to reach within a single node
+        let weights = [23, 31, 29, 44, 53, 38, 63, 85, 89, 82];
+        let profits = [92, 57, 49, 68, 60, 43, 67, 84, 87, 72];
+        let mut model = Model::<BigRational>::new();
+        let items = (0..weights.len())
+            .map(|i| model.add_var().name(format!("b{i}")).binary().build())
+            .collect::<Vec<_>>();
+        model.maximize();
+        model.set_objective(
+            items
+                .iter()
+                .zip(profits)
+                .map(|(b, p)| c(p) * b.clone())
+                .sum::<Expression<_>>(),
+        );
+        model.add_const(
+            items
+                .iter()
+                .zip(weights)
+                .map(|(b, w)| c(w) * b.clone())
+                .sum::<Expression<_>>()
+                .le(c(165).0),
+        );
+
+        // items 0, 1, 2, 3 and 5 weigh 165 with profit 309
+        let start =
+            Solution::<BigRational>::parse("b0 1\nb1 1\nb2 1\nb3 1\nb5 1\n".as_bytes()).unwrap();
+        model.set_start(&start);
+        let solution = model
+            .solve_with(&SolveOptions {
+                node_limit: Some(1),
+                missing_start_values: MissingStartValues::LowerBound,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(*solution.objective_value().unwrap() >= c(309).0);
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let two = BigRational::from_integer(2.into());
+        let expected = "2 x";
+
+        assert_eq!((x.clone() * two.clone()).to_string(), expected);
+        assert_eq!((two.clone() * x.clone()).to_string(), expected);
+        assert_eq!(
+            (two.clone() * Expression::from(x.clone())).to_string(),
+            expected
+        );
+        assert_eq!(
+            (Expression::from(x.clone()) * two.clone()).to_string(),
+            expected
+        );
+        assert_eq!((c(2) * x.clone()).to_string(), expected);
+        assert_eq!((x.clone() * 2).to_string(), expected);
+        assert_eq!((2 * x.clone()).to_string(), expected);
+        assert_eq!((2i64 * (x.clone() + c(1).0)).to_string(), "2 x + 2");
+        assert_eq!(((x.clone() + c(1).0) * 2i64).to_string(), "2 x + 2");
+
+        let mut model = Model::<f64>::new();
+        let y = model.add_var().name("y").build();
+        assert_eq!((y.clone() * 2.5).0[0].0, 2.5);
+        assert_eq!((2.5 * y.clone()).0[0].0, 2.5);
+        assert_eq!((2.5 * (y.clone() + 1.0)).0[1].0, 2.5);
+
+        let mut model = Model::<num::rational::Rational64>::new();
+        let z = model.add_var().name("z").build();
+        let half = num::rational::Rational64::new(1, 2);
+        assert_eq!((half * z.clone()).to_string(), "1/2 z");
+        assert_eq!((z.clone() * half).to_string(), "1/2 z");
+    }
+
+    #[test]
+    fn test_var_vec() {
+        let mut model = Model::<BigRational>::new();
+        let xs = model
+            .add_var_vec(3)
+            .integer()
+            .lb(c(0).0)
+            .ub(c(4).0)
+            .name_prefix("x")
+            .build();
+        assert_eq!(
+            xs.iter().map(|x| x.name()).collect::<Vec<_>>(),
+            ["x_0", "x_1", "x_2"]
+        );
+        let unnamed = model.add_var_vec(2).build();
+        assert_eq!(unnamed[1].name(), "v4");
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .contains("Bounds\n 0 <= x_0 <= 4\n 0 <= x_1 <= 4\n 0 <= x_2 <= 4\n"));
+    }
+
+    #[test]
+    fn test_assignment() {
+        // costs[i][j] of assigning worker i to task j
+        let costs = [
+            [9, 2, 7, 8, 6],
+            [6, 4, 3, 7, 5],
+            [5, 8, 1, 8, 7],
+            [7, 6, 9, 4, 8],
+            [3, 7, 6, 9, 2],
+        ];
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var_matrix(5, 5).binary().name_prefix("x").build();
+        assert_eq!(x[2][3].name(), "x_2_3");
+
+        model.set_objective(
+            (0..5)
+                .flat_map(|i| (0..5).map(move |j| (i, j)))
+                .map(|(i, j)| c(costs[i][j]) * x[i][j].clone())
+                .sum::<Expression<_>>(),
+        );
+        for i in 0..5 {
+            model.add_const(x[i].iter().cloned().sum::<Expression<_>>().eq(c(1).0));
+            model.add_const(
+                x.iter()
+                    .map(|row| row[i].clone())
+                    .sum::<Expression<_>>()
+                    .eq(c(1).0),
+            );
+        }
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .contains("Binary\n x_0_0\n x_0_1\n"));
+
+        // 2 + 6 + 1 + 4 + 2
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(15).0));
+    }
+
+    #[test]
+    fn test_reference_operators() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let e = x.clone() + y.clone();
+
+        let by_value = x.clone() + y.clone() * 4 - c(2).0;
+        let by_reference = &x + &y * 4 - c(2).0;
+        assert_eq!(by_reference.to_string(), by_value.to_string());
+        assert_eq!(
+            (&x + c(4) * &y).to_string(),
+            by_value.to_string().replace(" - 2", "")
+        );
+        assert_eq!((&x - &y).to_string(), (x.clone() - y.clone()).to_string());
+        assert_eq!((-&x).to_string(), (-x.clone()).to_string());
+        assert_eq!((&x / c(2).0).to_string(), (x.clone() / c(2).0).to_string());
+        assert_eq!((2 * &x).to_string(), (2 * x.clone()).to_string());
+
+        assert_eq!((&e + &x).to_string(), (e.clone() + x.clone()).to_string());
+        assert_eq!((&e - &e).to_string(), (e.clone() - e.clone()).to_string());
+        assert_eq!((-&e).to_string(), (-e.clone()).to_string());
+        assert_eq!((&e * 3).to_string(), (e.clone() * 3).to_string());
+        assert_eq!((&e / c(3).0).to_string(), (e.clone() / c(3).0).to_string());
+        assert_eq!((c(3).0 * &e).to_string(), (e.clone() * c(3).0).to_string());
+        assert_eq!(
+            (e.clone() + &x).to_string(),
+            (e.clone() + x.clone()).to_string()
+        );
+    }
+
+    #[test]
+    fn test_relax() {
+        let mut model = Model::<BigRational>::new();
+        let items = (0..3)
+            .map(|i| model.add_var().name(format!("b{i}")).binary().build())
+            .collect::<Vec<_>>();
+        let n = model.add_var().name("n").integer().lb(c(0).0).build();
+        model.maximize();
+        model.set_objective(c(5) * &items[0] + c(4) * &items[1] + c(3) * &items[2] + c(1) * &n);
+        model.add_const((c(2) * &items[0] + c(3) * &items[1] + c(1) * &items[2]).le(c(4).0));
+        model.add_const((c(2) * &n).le(c(3).0));
+
+        let relaxed = model.relax();
+        let lp = relaxed.to_lp_string().unwrap();
+        assert!(lp.contains(" 0 <= b0 <= 1\n"));
+        assert!(lp.ends_with("General\nBinary\nEnd\n"));
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .contains("General\n n\nBinary\n b0\n b1\n b2\n"));
+
+        // integer optimum b0 + b2 + n = 9, relaxation b0 + 1/3 b1 + b2 + 3/2 n
+        let integral = model.solve(DebugArtifacts::None).unwrap();
+        let relaxation = relaxed.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(integral.objective_value(), Some(&c(9).0));
+        assert!(relaxation.objective_value().unwrap() > integral.objective_value().unwrap());
+        assert_eq!(
+            relaxation.get_value(&n),
+            BigRational::new(3.into(), 2.into())
+        );
+    }
+    /// `min 2x + 3y` subject to `x + y >= 4` and `x + 3y >= 6`, optimal at
+    /// `x = 3, y = 1` with duals 3/2 and 1/2.
+    fn dual_model() -> Model<f64> {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(0.0).build();
+        let y = model.add_var().name("y").lb(0.0).build();
+        model.set_objective(&x * 2.0 + &y * 3.0);
+        model.add_const_named("cover", (&x + &y).ge(4.0)).unwrap();
+        model.add_const((&x + &y * 3.0).ge(6.0));
+        model
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_duals_stub() {
+        // answers `write solution` and `write dualsolution` with the optimum
+        // of `dual_model`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'x 3\\ny 1\\n' > \"${arg#write solution }\" ;;\n\
+             \"write dualsolution \"*) printf 'cover 1.5\\nc1 0.5\\n' > \"${arg#write dualsolution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = dual_model();
+        let z = model.add_var().name("z").lb(0.0).build();
+        model.set_objective(model.objective.clone() + &z);
+        model.set_solver_path(Solver::Scip, &stub);
+        let opts = SolveOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let solution = model.solve_with(&opts).unwrap();
+        assert_eq!(solution.dual_value(0), None);
+
+        let solution = model.solve_with(&opts.clone().with_duals(true)).unwrap();
+        assert_eq!(solution.get_value(&z), 0.0);
+        assert_eq!(solution.dual_value(0), Some(1.5));
+        assert_eq!(solution.dual_value("cover"), Some(1.5));
+        assert_eq!(solution.dual_value(1), Some(0.5));
+        assert_eq!(solution.dual_value(2), None);
+        assert_eq!(solution.reduced_cost(&z), Some(1.0));
+        let unused = model.add_var().name("unused").build();
+        assert_eq!(solution.reduced_cost(&unused), None);
+
+        let b = model.add_var().name("b").binary().build();
+        assert!(matches!(
+            model.solve_with(&opts.with_duals(true)),
+            Err(SolveError::DualsRequireLp(name)) if name == b.name()
+        ));
+    }
+
+    #[test]
+    fn test_duals() {
+        let model = dual_model();
+        let solution = model
+            .solve_with(&SolveOptions::default().with_duals(true))
+            .unwrap();
+        assert!((solution.dual_value("cover").unwrap() - 1.5).abs() < 1e-9);
+        assert!((solution.dual_value(1).unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expression_display() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        assert_eq!((&x + &y).to_string(), "x + y");
+        assert_eq!((-&x - &y * 2 - c(3).0).to_string(), "-x - 2 y - 3");
+        assert_eq!((c(-2) * &x + &y).to_string(), "-2 x + y");
+        assert_eq!((&x * 0 + &y - &y * 3).to_string(), "y - 3 y");
+        assert_eq!((&x * 0 + c(3).0).to_string(), "3");
+        assert_eq!(Expression::from(c(-1).0).to_string(), "-1");
+        assert_eq!((&x - &x).to_string(), "x - x");
+        assert_eq!((&x * 0).to_string(), "0");
+        assert_eq!(Expression::<BigRational>::default().to_string(), "0");
+        assert_eq!(((&x - &y).le(c(0).0)).to_string(), "x - y <= 0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_debug_artifacts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "echo solving\n\
+             for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'x 1\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("x").build();
+        model.set_solver_path(Solver::Scip, &stub);
+        let solve = |debug_artifacts| {
+            model
+                .solve_with(&SolveOptions {
+                    debug_artifacts,
+                    quiet: true,
+                    ..Default::default()
+                })
+                .unwrap()
+        };
+
+        assert_eq!(solve(DebugArtifacts::None).artifact_dir(), None);
+
+        let requested = dir.path().join("artifacts");
+        let solution = solve(DebugArtifacts::KeepIn(requested.clone()));
+        assert_eq!(solution.artifact_dir(), Some(requested.as_path()));
+        assert!(requested.join("formulation.lp").is_file());
+        assert!(requested.join("solution.sol").is_file());
+        assert_eq!(
+            std::fs::read_to_string(requested.join("solver.log")).unwrap(),
+            "solving\n"
+        );
+
+        let solution = solve(DebugArtifacts::Keep);
+        let kept = solution.artifact_dir().unwrap();
+        assert!(kept.join("formulation.lp").is_file());
+        std::fs::remove_dir_all(kept).unwrap();
+    }
+
+    #[test]
+    fn test_semi_continuous() {
+        let mut model = Model::<BigRational>::new();
+        let x = model
+            .add_var()
+            .name("x")
+            .semi_continuous()
+            .lb(c(2).0)
+            .ub(c(5).0)
+            .build();
+        let n = model.add_var().name("n").semi_integer().ub(c(9).0).build();
+        assert_eq!(
+            model
+                .add_var()
+                .name("s")
+                .semi_continuous()
+                .try_build()
+                .err(),
+            Some(ModelError::MissingUpperBound("s".to_string()))
+        );
+        assert_eq!(model.num_vars(), 2);
+
+        model.set_objective(-&x - &n);
+        model.add_const((&x + &n).le(c(1).0));
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.ends_with(
+                "Bounds\n 2 <= x <= 5\n 0 <= n <= 9\n\
+                 General\n n\n\
+                 Binary\n\
+                 Semi-continuous\n x\n n\n\
+                 End\n"
+            ),
+            "{lp}"
+        );
+        assert!(!model
+            .relax()
+            .to_lp_string()
+            .unwrap()
+            .contains("Semi-continuous"));
+
+        // x cannot take a value in (0, 2), so n takes the whole budget
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), c(0).0);
+        assert_eq!(solution.get_value(&n), c(1).0);
+    }
+
+    #[test]
+    #[should_panic(expected = "upper bound")]
+    fn test_semi_continuous_without_upper_bound() {
+        let mut model = Model::<f64>::new();
+        model.add_var().semi_continuous().lb(1.0).build();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stdin_commands() {
+        // reads its commands from stdin, writing the solution only after a
+        // delay to catch callers that do not wait for it to exit
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "[ $# -eq 0 ] || exit 1\n\
+             while read -r line; do\n\
+             case \"$line\" in\n\
+             \"write solution \"*) sleep 0.01; printf 'x 2\\n' > \"${line#write solution }\" ;;\n\
+             quit) exit 0 ;;\n\
+             esac\n\
+             done\n\
+             exit 1\n",
+        );
+
+        let opts = SolveOptions {
+            quiet: true,
+            command_input: CommandInput::Stdin,
+            ..Default::default()
+        };
+        for i in 0..50 {
+            let mut model = Model::<f64>::new();
+            let x = model.add_var().name("x").ub(f64::from(i)).build();
+            model.set_objective(&x * 1.0);
+            model.set_solver_path(Solver::Scip, &stub);
+            let solution = model.solve_with(&opts).unwrap();
+            assert_eq!(solution.get_value(&x), 2.0);
+        }
+    }
+
+    #[test]
+    fn test_remove_const() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let first = model.add_const(x.clone().ge(c(1).0));
+        let middle = model.add_const(x.clone().le(c(5).0));
+        let last = model.add_const_named("cap", x.clone().le(c(4).0)).unwrap();
+        assert_eq!(middle.index(), 1);
+
+        let removed = model.remove_const(middle).unwrap();
+        assert_eq!(removed.to_string(), "x <= 5");
+        assert!(model.remove_const(middle).is_none());
+        assert!(model.get_const(middle).is_none());
+        assert_eq!(model.get_const(first).unwrap().to_string(), "x >= 1");
+        assert_eq!(
+            model.replace_const(middle, x.clone().le(c(3).0)).err(),
+            Some(ModelError::RemovedConstraint(1))
+        );
+        let replaced = model.replace_const(last, x.clone().le(c(3).0)).unwrap();
+        assert_eq!(replaced.to_string(), "x <= 4");
+        assert_eq!(model.get_const(last).unwrap().to_string(), "x <= 3");
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains("Subject To\n c0: x >= 1\n cap: x <= 3\nBounds\n"),
+            "{lp}"
+        );
+        // ids handed out later do not reuse the removed slot
+        assert_eq!(model.add_const(x.clone().ge(c(0).0)).index(), 3);
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let cap = model.add_const_named("cap", (&x * 1.0).le(4.0)).unwrap();
+        assert_eq!(
+            model.replace_const(cap, (&x * f64::INFINITY).le(3.0)).err(),
+            Some(ModelError::NonFiniteCoefficients {
+                row: "constraint `cap`".to_string(),
+                terms: vec!["x".to_string()],
+            })
+        );
+        assert_eq!(model.get_const(cap).unwrap().lhs.0[0].0, 1.0);
+    }
+
+    #[test]
+    fn test_add_term_to_constraint() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").free().build();
+        let cap = model.add_const_named("cap", (&x * 1.0).le(4.0)).unwrap();
+        assert_eq!(
+            model.add_term_to_constraint(cap, x.clone(), f64::NAN),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "constraint `cap`".to_string(),
+                terms: vec!["x".to_string()],
+            })
+        );
+
+        let mut other = Model::<f64>::new();
+        other.add_var().build();
+        other.add_var().build();
+        let foreign = other.add_var().name("z").build();
+        assert_eq!(
+            model.add_term_to_constraint(cap, foreign, 1.0),
+            Err(ModelError::UnknownVariable(2))
+        );
+
+        model.substitute_variable(&y, &(&x * 2.0 + 0.0)).unwrap();
+        assert_eq!(
+            model.add_term_to_constraint(cap, y.clone(), 1.0),
+            Err(ModelError::UnknownVariable(1))
+        );
+        assert_eq!(model.get_const(cap).unwrap().lhs.0, vec![(1.0, Some(x))]);
+    }
+
+    #[test]
+    fn test_add_column() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").obj(c(3).0).build();
+        let demand = model
+            .add_const_named("demand", x.clone().ge(c(4).0))
+            .unwrap();
+        let cap = model.add_const_named("cap", x.clone().le(c(10).0)).unwrap();
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(12).0));
+
+        // a cheaper column covering the demand twice, sharing the capacity
+        let y = model
+            .add_var()
+            .name("y")
+            .obj(c(5).0)
+            .in_constraint(demand, c(2).0)
+            .in_constraint(cap, c(1).0)
+            .build();
+        model
+            .add_term_to_constraint(cap, x.clone(), c(1).0)
+            .unwrap();
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains(" obj: 3 x + 5 y\n"), "{lp}");
+        assert!(lp.contains(" demand: x + 2 y >= 4\n"), "{lp}");
+        assert!(lp.contains(" cap: 2 x + y <= 10\n"), "{lp}");
+
+        model.remove_const(cap);
+        assert_eq!(
+            model.add_term_to_constraint(cap, y.clone(), c(1).0),
+            Err(ModelError::RemovedConstraint(1))
+        );
+        assert_eq!(
+            model.add_var().in_constraint(cap, c(1).0).try_build().err(),
+            Some(ModelError::RemovedConstraint(1))
+        );
+        assert_eq!(model.num_vars(), 2);
+
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&y), c(2).0);
+        assert_eq!(solution.objective_value(), Some(&c(10).0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cut_round_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'objective value: 4\\nx 4\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(4).0).build();
+        model.maximize();
+        model.set_objective(Expression::from(&x));
+        model.set_solver_path(Solver::Scip, &stub);
+        let opts = SolveOptions {
+            cut_rounds: Some(2),
+            quiet: true,
+            ..Default::default()
+        };
+        let mut calls = 0;
+        let solution = model
+            .solve_with_cuts(&opts, |solution| {
+                calls += 1;
+                vec![x.clone().le(solution.get_value(&x) - c(1).0)]
+            })
+            .unwrap();
+        assert_eq!(calls, 3);
+        assert_eq!(solution.cut_rounds(), 2);
+        assert!(solution.cut_limit_reached());
+        assert_eq!(solution.get_value(&x), c(4).0);
+        assert_eq!(model.num_constraints(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_objective_constant() {
+        // answers for the formulation scaled to `max x`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'objective value: 4\\nx 4\\n' > \"${arg#write solution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(4).0).build();
+        model.maximize();
+        model.set_objective(c(2) * &x + c(5).0);
+        assert!(model.to_lp_string().unwrap().contains(" obj: 2 x\n"));
+        model.set_solver_path(Solver::Scip, &stub);
+
+        let solution = model
+            .solve_with(&SolveOptions {
+                quiet: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(13).0));
+        assert_eq!(
+            solution.get_value(model.objective.clone()),
+            *solution.objective_value().unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scaled_objective_and_duals() {
+        // answers for the formulation scaled to `max 7 x + 66 y` subject to
+        // `cap: 2 x + 3 y <= 6`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            "for arg in \"$@\"; do\n\
+             case \"$arg\" in\n\
+             \"write solution \"*) printf 'objective value: 132\\nx 0\\ny 2\\n' > \"${arg#write solution }\" ;;\n\
+             \"write dualsolution \"*) printf 'cap 22\\nx_ub 0\\n' > \"${arg#write dualsolution }\" ;;\n\
+             esac\n\
+             done\n",
+        );
+
+        let third = BigRational::new(1.into(), 3.into());
+        let mut model = Model::<BigRational>::new();
+        let x = model
+            .add_var()
+            .name("x")
+            .lb(c(0).0)
+            .ub(third.clone())
+            .build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        model.maximize();
+        model.set_objective(
+            third.clone() * &x + BigRational::new(22.into(), 7.into()) * &y + c(1).0,
+        );
+        model
+            .add_const_named(
+                "cap",
+                (third * &x + BigRational::new(1.into(), 2.into()) * &y).le(c(1).0),
+            )
+            .unwrap();
+        model.set_solver_path(Solver::Scip, &stub);
+
+        let solution = model
+            .solve_with(
+                &SolveOptions {
+                    quiet: true,
+                    ..Default::default()
+                }
+                .with_duals(true),
+            )
+            .unwrap();
+        assert_eq!(
+            solution.objective_value(),
+            Some(&BigRational::new(51.into(), 7.into()))
+        );
+        assert_eq!(solution.get_value(&y), c(2).0);
+        assert_eq!(
+            solution.dual_value("cap"),
+            Some(BigRational::new(44.into(), 7.into()))
+        );
+        assert_eq!(
+            solution.reduced_cost(&x),
+            Some(BigRational::new((-37).into(), 21.into()))
+        );
+        assert_eq!(solution.dual_value("x_ub"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cbc_stub() {
+        // expects `<model> seconds 2 solve solution <path>`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ -f \"$1\" ] && [ \"$2 $3 $4 $5\" = 'seconds 2 solve solution' ] || exit 1\n\
+                 grep -q '^ROWS' \"$1\" || exit 1\n\
+                 cp {} \"$6\"\n",
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/cbc_optimal.sol"
+                )
+            ),
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.maximize();
+        model.set_objective(&x * 3.0 + &y * 4.0 + 1.0);
+        model.set_solver_path(Solver::Cbc, &stub);
+        let solution = model
+            .solve_with_solver(
+                Solver::Cbc,
+                &SolveOptions {
+                    time_limit: Some(std::time::Duration::from_secs(2)),
+                    quiet: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.get_value(x), 4.0);
+        assert_eq!(solution.get_value(y), 5.0);
+        assert_eq!(solution.objective_value(), Some(&33.0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_glpk_stub() {
+        // expects `--lp <model> -o <report> -w <solution> --tmlim 2`
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ \"$1\" = --lp ] && [ -f \"$2\" ] || exit 1\n\
+                 [ \"$3 $5 $7 $8\" = '-o -w --tmlim 2' ] || exit 1\n\
+                 cp {fixtures}/glpk_mip.txt \"$4\"\n\
+                 cp {fixtures}/glpk_mip.sol \"$6\"\n"
+            ),
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("production_volume").integer().build();
+        let y = model.add_var().name("y").integer().build();
+        model.maximize();
+        model.set_objective(&x + &y + 1.0);
+        model.set_solver_path(Solver::Glpk, &stub);
+        let opts = SolveOptions {
+            time_limit: Some(std::time::Duration::from_millis(1500)),
+            quiet: true,
+            ..Default::default()
+        };
+        let solution = model.solve_with_solver(Solver::Glpk, &opts).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.get_value(&x), 1234567.0);
+        assert_eq!(solution.get_value(&y), 3.0);
+        assert_eq!(solution.objective_value(), Some(&1234571.0));
+
+        model.set_param(Param::Threads(2));
+        assert!(matches!(
+            model.solve_with_solver(Solver::Glpk, &opts),
+            Err(SolveError::UnsupportedParameter {
+                solver: Solver::Glpk,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cplex_stub() {
+        // expects `-c 'read <model>' <commands> optimize 'write <solution>'`
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ \"$1\" = -c ] && [ -f \"${{2#read }}\" ] || exit 1\n\
+                 [ \"$3|$4|$5\" = 'set emphasis mip 1|set timelimit 2|optimize' ] || exit 1\n\
+                 cp {} \"${{6#write }}\"\n",
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/cplex_optimal.sol"
+                )
+            ),
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").integer().build();
+        model.maximize();
+        model.set_objective(&x * 2.0 + &y * 5.0 + 1.0);
+        model.set_param(Param::Emphasis(Emphasis::Feasibility));
+        model.set_solver_path(Solver::Cplex, &stub);
+        let opts = SolveOptions {
+            time_limit: Some(std::time::Duration::from_secs(2)),
+            quiet: true,
+            ..Default::default()
+        };
+        let solution = model.solve_with_solver(Solver::Cplex, &opts).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.get_value(&x), 4.0);
+        assert_eq!(solution.get_value(&y), 5.0);
+        assert_eq!(solution.objective_value(), Some(&34.0));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(dir.path(), "echo 'MIP - Integer infeasible.'\n");
+        model.set_solver_path(Solver::Cplex, &stub);
+        let solution = model.solve_with_solver(Solver::Cplex, &opts).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Infeasible);
+        assert!(solution.is_empty());
+
+        model.set_param(Param::Emphasis(Emphasis::HardLp));
+        assert!(matches!(
+            model.solve_with_solver(Solver::Cplex, &opts),
+            Err(SolveError::UnsupportedParameter {
+                solver: Solver::Cplex,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_gurobi_stub() {
+        // expects `ResultFile=<solution> <parameters> <model>`
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!(
+                "[ \"$2 $3\" = 'Threads=2 TimeLimit=2' ] && [ -f \"$4\" ] || exit 1\n\
+                 cp {fixtures}/gurobi_optimal.sol \"${{1#ResultFile=}}\"\n\
+                 echo 'Optimal solution found (tolerance 1.00e-04)'\n"
+            ),
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").integer().build();
+        model.maximize();
+        model.set_objective(&x * 2.0 + &y * 5.0 + 1.0);
+        model.set_param(Param::Threads(2));
+        model.set_solver_path(Solver::Gurobi, &stub);
+        let opts = SolveOptions {
+            time_limit: Some(std::time::Duration::from_secs(2)),
+            quiet: true,
+            ..Default::default()
+        };
+        let solution = model.solve_with_solver(Solver::Gurobi, &opts).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.get_value(&x), 4.0);
+        assert_eq!(solution.get_value(&y), 5.0);
+        assert_eq!(solution.objective_value(), Some(&34.0));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub(
+            dir.path(),
+            &format!("cat {fixtures}/gurobi_infeasible_log.txt\n"),
+        );
+        model.set_solver_path(Solver::Gurobi, &stub);
+        let solution = model.solve_with_solver(Solver::Gurobi, &opts).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Infeasible);
+        assert!(solution.is_empty());
+    }
+
+    #[test]
+    fn test_degenerate_models() {
+        // no variables, solved without starting SCIP
+        let mut model = Model::<BigRational>::new();
+        model.set_solver_path(Solver::Scip, "/nonexistent/scip");
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Minimize\n obj: 0\nSubject To\nBounds\nGeneral\nBinary\nEnd\n"
+        );
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.objective_value(), Some(&c(0).0));
+        assert!(solution.is_empty());
+
+        model.maximize();
+        model.set_objective(Expression::from(c(3).0));
+        let holds = model.add_const(Expression::from(c(1).0).le(c(2).0));
+        let opts = SolveOptions {
+            duals: true,
+            ..Default::default()
+        };
+        let solution = model.solve_with(&opts).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(3).0));
+        assert_eq!(solution.dual_value(holds), Some(c(0).0));
+
+        model.add_const(Expression::from(c(1).0).ge(c(2).0));
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Infeasible);
+        assert_eq!(solution.objective_value(), None);
+
+        // objective only
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(4).0).build();
+        model.maximize();
+        model.set_objective((&x).into());
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .contains(" obj: x\nSubject To\nBounds\n 0 <= x <= 4\n"));
+        let objective_only = model;
+
+        // constraints only, with a zero term standing in for the objective
+        let mut model = Model::<BigRational>::new();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        let z = model.add_var().name("z").lb(c(0).0).build();
+        model.add_const((&y + &z).ge(c(2).0));
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .starts_with("Minimize\n obj: 0 y\nSubject To\n c0: y + z >= 2\n"));
+
+        let solution = objective_only.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&x), c(4).0);
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert!(solution.get_value(&y + &z) >= c(2).0);
+    }
+
+    #[test]
+    fn test_objective_coefficients() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).obj(c(2).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).obj(c(5).0).build();
+        model.maximize();
+        model.add_const((&x + c(4) * &y).le(c(24).0));
+        model.add_const((c(3) * &x + &y).le(c(21).0));
+        model.add_const((&x + &y).le(c(9).0));
+        assert!(model.to_lp_string().unwrap().contains(" obj: 2 x + 5 y\n"));
+
+        // the expression and the per-variable coefficients add up
+        model.set_objective(&x + c(1).0);
+        assert!(model.to_lp_string().unwrap().contains(" obj: 3 x + 5 y\n"));
+        model.set_objective_coefficient(&y, c(0).0).unwrap();
+        assert_eq!(model.to_string().lines().next(), Some("maximize 3 x + 1"));
+
+        let mut substituted = model.clone();
+        substituted
+            .substitute_variable(&x, &(Expression::from(c(9).0) - &y))
+            .unwrap();
+        assert_eq!(
+            substituted.to_string().lines().next(),
+            Some("maximize -3 y + 28")
+        );
+
+        let mut other = Model::<BigRational>::new();
+        assert_eq!(
+            other.set_objective_coefficient(&y, c(1).0),
+            Err(ModelError::UnknownVariable(1))
+        );
+
+        model.set_objective(Expression::from(c(1).0));
+        model.set_objective_coefficient(&y, c(5).0).unwrap();
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&x), c(4).0);
+        assert_eq!(solution.get_value(&y), c(5).0);
+        assert_eq!(solution.objective_value(), Some(&c(34).0));
+    }
+
+    #[test]
+    fn test_substitute_variable() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").ub(c(5).0).build();
+        let b = model.add_var().name("b").binary().build();
+        model.set_objective(&x + c(2) * &y);
+        model.add_const((&x + &y).le(c(10).0));
+        model
+            .add_indicator(b.clone(), true, y.clone().ge(c(1).0))
+            .unwrap();
+
+        assert_eq!(
+            model.substitute_variable(&y, &(&y + &x)),
+            Err(ModelError::CannotSubstitute("y".to_string()))
+        );
+        assert_eq!(
+            model.substitute_variable(&b, &Expression::from(c(1).0)),
+            Err(ModelError::CannotSubstitute("b".to_string()))
+        );
+        model
+            .substitute_variable(&y, &(c(2) * &x + c(1).0))
+            .unwrap();
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Minimize\n obj: 5 x\n\
+             Subject To\n c0: 3 x <= 9\n c1: b = 1 -> 2 x >= 0\n c2: -1 <= 2 x <= 4\n\
+             Bounds\n 0 <= x <= +inf\n\
+             General\nBinary\n b\n\
+             End\n"
+        );
+        let mut mps = Vec::new();
+        model.export_mps(&mut mps).unwrap();
+        assert!(!String::from_utf8(mps).unwrap().contains(" y "));
+
+        let n = model.add_var().name("n").integer().build();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .ub(c(3).0)
+            .build();
+        for v in [&n, &s] {
+            assert_eq!(
+                model.substitute_variable(v, &Expression::from(&x)),
+                Err(ModelError::CannotSubstitute(v.name()))
+            );
+        }
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").free().build();
+        let y = model.add_var().name("y").free().build();
+        model.set_quadratic_objective(&x * &y + Expression::from(&y));
+        model
+            .substitute_variable(&y, &(c(2) * &x - c(1).0))
+            .unwrap();
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Minimize\n obj: x + [ 4 x ^2 ] / 2\n\
+             Subject To\n\
+             Bounds\n x free\n\
+             General\nBinary\nEnd\n"
+        );
+
+        // values of substituted variables are filled in, latest first
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").lb(c(1).0).build();
+        model.set_objective(&x + &y);
+        model.substitute_variable(&x, &(&y + c(1).0)).unwrap();
+        model
+            .substitute_variable(&y, &Expression::from(c(2).0))
+            .unwrap();
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&x), c(3).0);
+        assert_eq!(solution.get_value(&y), c(2).0);
+        assert_eq!(solution.objective_value(), Some(&c(5).0));
+    }
+
+    #[test]
+    fn test_var_accessors() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(1).0).ub(c(5).0).build();
+        let n = model.add_var().name("n").integer().build();
+        let b = model.add_var().name("b").binary().build();
+        assert_eq!(model.var_bounds(&x), Ok((Some(&c(1).0), Some(&c(5).0))));
+        assert_eq!(model.var_bounds(&n), Ok((Some(&c(0).0), None)));
+        assert_eq!(model.var_type(&x), Ok(VariableType::Continuous));
+        assert_eq!(model.var_type(&n), Ok(VariableType::Integer));
+        assert_eq!(model.var_type(&b), Ok(VariableType::Binary));
+
+        model.set_var_bounds(&x, Some(c(2).0), None).unwrap();
+        model.set_var_type(&n, VariableType::Binary).unwrap();
+        assert_eq!(model.var_bounds(&x), Ok((Some(&c(2).0), None)));
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.contains(" 2 <= x <= +inf\n"), "{lp}");
+        assert!(lp.contains("Binary\n n\n b\n"), "{lp}");
+
+        assert_eq!(
+            model.set_var_bounds(&b, Some(c(0).0), Some(c(2).0)),
+            Err(ModelError::InvalidBinaryValue("b".to_string()))
+        );
+        assert_eq!(model.var_bounds(&b), Ok((Some(&c(0).0), None)));
+        assert_eq!(
+            model.set_var_type(&x, VariableType::SemiContinuous),
+            Err(ModelError::MissingUpperBound("x".to_string()))
+        );
+        assert_eq!(model.var_type(&x), Ok(VariableType::Continuous));
+
+        let mut other = Model::<BigRational>::new();
+        let y = other.add_var().build();
+        other.add_var().build();
+        other.add_var().build();
+        let unknown = other.add_var().build();
+        assert_eq!(model.var_type(&y), Ok(VariableType::Continuous));
+        assert_eq!(
+            model.var_type(&unknown),
+            Err(ModelError::UnknownVariable(3))
+        );
+        assert_eq!(
+            model.var_bounds(&unknown),
+            Err(ModelError::UnknownVariable(3))
+        );
+        assert_eq!(
+            model.set_var_bounds(&unknown, None, None),
+            Err(ModelError::UnknownVariable(3))
+        );
+        assert_eq!(
+            model.set_var_type(&unknown, VariableType::Integer),
+            Err(ModelError::UnknownVariable(3))
+        );
+    }
+
+    #[test]
+    fn test_fix_variable() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(4).0).build();
+        let y = model.add_var().name("y").fix(c(2).0).build();
+        let b = model.add_var().name("b").binary().build();
+        assert_eq!(
+            model
+                .add_var()
+                .name("a")
+                .binary()
+                .fix(c(2).0)
+                .try_build()
+                .err(),
+            Some(ModelError::InvalidBinaryValue("a".to_string()))
+        );
+        assert_eq!(model.is_fixed(&x), Ok(None));
+        assert_eq!(model.is_fixed(&y), Ok(Some(c(2).0)));
+
+        assert_eq!(
+            model.fix_variable(&b, c(2).0),
+            Err(ModelError::InvalidBinaryValue("b".to_string()))
+        );
+        model.fix_variable(&b, c(1).0).unwrap();
+        model.fix_variable(&x, c(3).0).unwrap();
+        assert_eq!(model.is_fixed(&x), Ok(Some(c(3).0)));
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains("\n 3 <= x <= 3\n 2 <= y <= 2\n 1 <= b <= 1\n"),
+            "{lp}"
+        );
+        let mut mps = Vec::new();
+        model.export_mps(&mut mps).unwrap();
+        let mps = String::from_utf8(mps).unwrap();
+        assert!(
+            mps.contains("\n FX BND x 3\n FX BND y 2\n FX BND b 1\n"),
+            "{mps}"
+        );
+
+        model
+            .unfix_variable(&x, Some(c(0).0), Some(c(4).0))
+            .unwrap();
+        assert_eq!(model.is_fixed(&x), Ok(None));
+
+        let mut other = Model::<BigRational>::new();
+        let unknown = (0..4).map(|_| other.add_var().build()).last().unwrap();
+        assert_eq!(
+            model.fix_variable(&unknown, c(1).0),
+            Err(ModelError::UnknownVariable(3))
+        );
+        assert_eq!(
+            model.unfix_variable(&unknown, None, None),
+            Err(ModelError::UnknownVariable(3))
+        );
+        assert_eq!(
+            model.is_fixed(&unknown),
+            Err(ModelError::UnknownVariable(3))
+        );
+        let unknown_binary = (0..4).map(|_| other.add_var().binary().build()).last();
+        assert_eq!(
+            model.add_indicator(unknown_binary.clone().unwrap(), true, x.clone().le(c(1).0)),
+            Err(ModelError::UnknownVariable(7))
+        );
+        assert_eq!(
+            model.add_exactly_one(&[b.clone(), unknown_binary.unwrap()]),
+            Err(ModelError::UnknownVariable(7))
+        );
+
+        // the unfixed optimum is x = 4
+        model.maximize();
+        model.set_objective(&x + &y + &b);
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), c(4).0);
+        model.fix_variable(&x, c(1).0).unwrap();
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), c(1).0);
+        assert_eq!(solution.objective_value(), Some(&c(4).0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_solver_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let opts = SolveOptions {
+            quiet: true,
+            ..Default::default()
+        };
+
+        let mut exact = Model::<BigRational>::new();
+        exact.add_var().name("x").build();
+        let mut float = Model::<f64>::new();
+        float.add_var().name("x").build();
+        let stub = write_stub(dir.path(), "echo 'license expired' >&2\nexit 1\n");
+        exact.set_solver_path(Solver::Scip, &stub);
+        float.set_solver_path(Solver::Scip, &stub);
+        for result in [
+            exact.solve_with(&opts).map(|_| ()),
+            float.solve_with(&opts).map(|_| ()),
+        ] {
+            match result {
+                Err(SolveError::SolverFailed { status, stderr }) => {
+                    assert_eq!(status.code(), Some(1));
+                    assert_eq!(stderr, "license expired\n");
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+
+        let stub = write_stub(
+            dir.path(),
+            "echo 'SCIP Status        : problem is solved [infeasible]'\n\
+             echo 'Solving Time (sec) : 0.00'\n",
+        );
+        exact.set_solver_path(Solver::Scip, &stub);
+        float.set_solver_path(Solver::Scip, &stub);
+        for result in [
+            exact.solve_with(&opts).map(|_| ()),
+            float.solve_with(&opts).map(|_| ()),
+        ] {
+            let error = result.unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "solver exited successfully but produced no solution file; \
+                 status line was `SCIP Status        : problem is solved [infeasible]`"
+            );
+        }
     }
 }
@@ -0,0 +1,664 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use num::{Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintId, ConstraintSense},
+    error::ModelError,
+    expression::Expression,
+    merge::unused_name,
+    variable::Variable,
+    Model, OptimizationDirection, VariableType,
+};
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// Adds `c` as ordinary rows relaxed by `big_m` unless the binary
+    /// variable `b` equals `when`, e.g. `lhs - rhs <= big_m * (1 - b)` for
+    /// `lhs <= rhs`. Equalities and ranges take two rows, whose ids are
+    /// returned. `big_m` has to be at least the largest violation of `c`,
+    /// a smaller one cuts off solutions.
+    pub fn add_implication(
+        &mut self,
+        b: Variable<N>,
+        when: bool,
+        c: Constraint<N>,
+        big_m: N,
+    ) -> Result<Vec<ConstraintId>, ModelError> {
+        self.check_binary(&b)?;
+        Ok(implication_rows(c)
+            .into_iter()
+            .map(|g| self.push_implication(&b, when, g, big_m.clone()))
+            .collect())
+    }
+
+    /// Like `add_implication`, but each row gets the smallest valid big-M,
+    /// computed from the variable bounds. Every variable in `c` needs finite
+    /// bounds in the direction that matters.
+    pub fn add_implication_auto(
+        &mut self,
+        b: Variable<N>,
+        when: bool,
+        c: Constraint<N>,
+    ) -> Result<Vec<ConstraintId>, ModelError> {
+        self.check_binary(&b)?;
+        let rows = implication_rows(c)
+            .into_iter()
+            .map(|g| {
+                let m = self.max_value(&g)?;
+                Ok((g, if m.is_positive() { m } else { N::zero() }))
+            })
+            .collect::<Result<Vec<_>, ModelError>>()?;
+        Ok(rows
+            .into_iter()
+            .map(|(g, m)| self.push_implication(&b, when, g, m))
+            .collect())
+    }
+
+    /// Variable `y` equal to the piecewise-linear interpolation of
+    /// `breakpoints`, given as `(x, y)` pairs with strictly increasing `x`,
+    /// at the value of `x`. Uses the incremental formulation: a fill level
+    /// in `[0, 1]` per segment and a binary per inner breakpoint, so a
+    /// segment only fills once the previous one is full. `x` is restricted
+    /// to the range of the breakpoints, which has to contain its bounds.
+    pub fn add_piecewise_linear(
+        &mut self,
+        x: &Variable<N>,
+        breakpoints: &[(N, N)],
+    ) -> Result<Variable<N>, ModelError> {
+        if breakpoints.len() < 2 {
+            return Err(ModelError::InvalidBreakpoints(
+                "at least two are needed".to_string(),
+            ));
+        }
+        // also fails for NaN
+        if !breakpoints.windows(2).all(|w| w[0].0 < w[1].0) {
+            return Err(ModelError::InvalidBreakpoints(
+                "x values have to increase strictly".to_string(),
+            ));
+        }
+        let (x0, y0) = &breakpoints[0];
+        let xn = &breakpoints[breakpoints.len() - 1].0;
+        let (lb, ub) = self.domain(x.id());
+        if lb.is_some_and(|lb| lb < *x0) || ub.is_some_and(|ub| ub > *xn) {
+            return Err(ModelError::InvalidBreakpoints(format!(
+                "bounds of `{}` exceed them",
+                x.name()
+            )));
+        }
+
+        let mut x_sum = Expression::from(x0.clone());
+        let mut y_sum = Expression::from(y0.clone());
+        let mut previous: Option<Variable<N>> = None;
+        for w in breakpoints.windows(2) {
+            let delta = self
+                .add_auxiliary("pwl_delta")
+                .lb(N::zero())
+                .ub(N::one())
+                .build();
+            if let Some(previous) = previous {
+                let full = self.add_auxiliary("pwl_segment").binary().build();
+                self.add_const(full.clone().le(previous));
+                self.add_const(delta.clone().le(full));
+            }
+            x_sum += delta.clone() * (w[1].0.clone() - w[0].0.clone());
+            y_sum += delta.clone() * (w[1].1.clone() - w[0].1.clone());
+            previous = Some(delta);
+        }
+        let y = self.add_auxiliary("pwl").build();
+        self.add_const(x.clone().eq(x_sum));
+        self.add_const(y.clone().eq(y_sum));
+        Ok(y)
+    }
+
+    /// Minimizes the largest of `exprs`, e.g. a makespan, through a
+    /// variable `t` named `minimax_{n}` with `t >= e` for every expression,
+    /// which is returned. An objective set before is kept, negated if it was
+    /// maximized, and `t` is added to it, so scale it to weigh the two. `t`
+    /// gets the bounds that finite variable bounds imply.
+    pub fn minimize_max(&mut self, exprs: Vec<Expression<N>>) -> Variable<N> {
+        let (lows, highs) = self.value_ranges(&exprs);
+        // t is at least every expression, and the largest is all it needs
+        let lb = lows.into_iter().flatten().reduce(larger);
+        let ub = highs.into_iter().reduce(|a, b| Some(larger(a?, b?)));
+        let t = self.add_bounded_auxiliary("minimax", lb, ub.flatten());
+        for e in exprs {
+            self.add_const(t.clone().ge(e));
+        }
+        self.add_to_objective(&t, OptimizationDirection::Minimize);
+        t
+    }
+
+    /// Maximizes the smallest of `exprs` through a variable `t` named
+    /// `maximin_{n}` with `t <= e` for every expression, like
+    /// `minimize_max`.
+    pub fn maximize_min(&mut self, exprs: Vec<Expression<N>>) -> Variable<N> {
+        let (lows, highs) = self.value_ranges(&exprs);
+        let ub = highs.into_iter().flatten().reduce(smaller);
+        let lb = lows.into_iter().reduce(|a, b| Some(smaller(a?, b?)));
+        let t = self.add_bounded_auxiliary("maximin", lb.flatten(), ub);
+        for e in exprs {
+            self.add_const(t.clone().le(e));
+        }
+        self.add_to_objective(&t, OptimizationDirection::Maximize);
+        t
+    }
+
+    /// Smallest and largest value of each expression within the variable
+    /// bounds, `None` where a bound that matters is missing.
+    fn value_ranges(&self, exprs: &[Expression<N>]) -> (Vec<Option<N>>, Vec<Option<N>>) {
+        exprs
+            .iter()
+            .map(|e| {
+                let low = self.max_value(&-e).ok().map(|m| -m);
+                (low, self.max_value(e).ok())
+            })
+            .unzip()
+    }
+
+    /// Adds `g <= m * (1 - b)`, or `g <= m * b` when `when` is false.
+    fn push_implication(
+        &mut self,
+        b: &Variable<N>,
+        when: bool,
+        g: Expression<N>,
+        m: N,
+    ) -> ConstraintId {
+        let relaxation = if when {
+            Expression::from(m.clone()) - b.clone() * m
+        } else {
+            b.clone() * m
+        };
+        self.add_const(g.le(relaxation))
+    }
+
+    /// Largest value `g` takes within the variable bounds.
+    fn max_value(&self, g: &Expression<N>) -> Result<N, ModelError> {
+        g.0.iter().try_fold(N::zero(), |acc, (w, v)| {
+            let Some(v) = v else {
+                return Ok(acc + w.clone());
+            };
+            let (lb, ub) = self.domain(v.id());
+            let bound = if w.is_positive() { ub } else { lb };
+            bound
+                .map(|bound| acc + w.clone() * bound)
+                .ok_or_else(|| ModelError::UnboundedVariable(v.name()))
+        })
+    }
+
+    /// Smallest interval containing every value variable `i` can take.
+    fn domain(&self, i: usize) -> (Option<N>, Option<N>) {
+        let v = &self.variables[i];
+        match v.v_type {
+            VariableType::Binary => (Some(N::zero()), Some(N::one())),
+            // zero is always allowed
+            VariableType::SemiContinuous | VariableType::SemiInteger => (
+                Some(match v.lb.finite() {
+                    Some(lb) if lb.is_negative() => lb.clone(),
+                    _ => N::zero(),
+                }),
+                Some(match v.ub.finite() {
+                    Some(ub) if ub.is_positive() => ub.clone(),
+                    _ => N::zero(),
+                }),
+            ),
+            _ => (v.lb.finite().cloned(), v.ub.finite().cloned()),
+        }
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + PartialOrd,
+{
+    /// Variable `t` with `t >= e` and `t >= -e`, which equals `|e|` as long
+    /// as `t` is being minimized, e.g. as part of a minimized objective.
+    /// Otherwise `t` can take any value above `|e|`.
+    pub fn abs(&mut self, e: impl Into<Expression<N>>) -> Variable<N> {
+        let e = e.into();
+        let t = self.add_auxiliary("abs").lb(N::zero()).build();
+        self.add_const(t.clone().ge(e.clone()));
+        self.add_const(t.clone().ge(-e));
+        t
+    }
+
+    /// Variable `t` with `t >= e` for every expression, which equals their
+    /// maximum as long as `t` is being minimized.
+    pub fn max_of(&mut self, exprs: Vec<Expression<N>>) -> Variable<N> {
+        let t = self.add_auxiliary("max").build();
+        for e in exprs {
+            self.add_const(t.clone().ge(e));
+        }
+        t
+    }
+
+    /// Variable `t` with `t <= e` for every expression, which equals their
+    /// minimum as long as `t` is being maximized.
+    pub fn min_of(&mut self, exprs: Vec<Expression<N>>) -> Variable<N> {
+        let t = self.add_auxiliary("min").build();
+        for e in exprs {
+            self.add_const(t.clone().le(e));
+        }
+        t
+    }
+
+    /// Requires exactly one of the binary variables `vars` to be 1, in a
+    /// constraint named `exactly_one_{n}`. A variable given more than once
+    /// counts once, here as in `add_at_most`, `add_at_least` and
+    /// `add_exactly_k`.
+    pub fn add_exactly_one(&mut self, vars: &[Variable<N>]) -> Result<ConstraintId, ModelError> {
+        let sum = self.binary_sum(vars)?;
+        Ok(self.add_counted_const("exactly_one", sum.eq(N::one())))
+    }
+
+    /// Requires at most `k` of the binary variables `vars` to be 1, in a
+    /// constraint named `at_most_{k}_{n}`.
+    pub fn add_at_most(
+        &mut self,
+        vars: &[Variable<N>],
+        k: usize,
+    ) -> Result<ConstraintId, ModelError> {
+        let sum = self.binary_sum(vars)?;
+        Ok(self.add_counted_const(&format!("at_most_{k}"), sum.le(count::<N>(k))))
+    }
+
+    /// Requires at least `k` of the binary variables `vars` to be 1, in a
+    /// constraint named `at_least_{k}_{n}`.
+    pub fn add_at_least(
+        &mut self,
+        vars: &[Variable<N>],
+        k: usize,
+    ) -> Result<ConstraintId, ModelError> {
+        let sum = self.binary_sum(vars)?;
+        Ok(self.add_counted_const(&format!("at_least_{k}"), sum.ge(count::<N>(k))))
+    }
+
+    /// Requires exactly `k` of the binary variables `vars` to be 1, in a
+    /// constraint named `exactly_{k}_{n}`.
+    pub fn add_exactly_k(
+        &mut self,
+        vars: &[Variable<N>],
+        k: usize,
+    ) -> Result<ConstraintId, ModelError> {
+        let sum = self.binary_sum(vars)?;
+        Ok(self.add_counted_const(&format!("exactly_{k}"), sum.eq(count::<N>(k))))
+    }
+
+    fn check_binary(&self, b: &Variable<N>) -> Result<(), ModelError> {
+        match self.var_type(b)? {
+            VariableType::Binary => Ok(()),
+            _ => Err(ModelError::NotBinary(b.name())),
+        }
+    }
+
+    /// Sum of `vars` counting a repeated variable once, failing on the
+    /// first variable that is not binary.
+    fn binary_sum(&self, vars: &[Variable<N>]) -> Result<Expression<N>, ModelError> {
+        let mut sum = Expression::with_capacity(vars.len());
+        let mut seen = BTreeSet::new();
+        for v in vars {
+            self.check_binary(v)?;
+            if seen.insert(v.id()) {
+                sum.0.push((N::one(), Some(v.clone())));
+            }
+        }
+        Ok(sum)
+    }
+
+    /// Adds `c` named `{kind}_{n}`, numbered like auxiliary variables, with
+    /// a suffix if a constraint has that name already.
+    fn add_counted_const(&mut self, kind: &str, c: Constraint<N>) -> ConstraintId {
+        let taken = self
+            .live_constraints()
+            .filter_map(|(_, c)| c.name.clone())
+            .collect::<BTreeSet<_>>();
+        let name = unused_name(&taken, format!("{}_{}", kind, self.auxiliaries));
+        self.auxiliaries += 1;
+        self.add_const_named(name, c)
+            .expect("generated names are valid and unused")
+    }
+
+    /// Auxiliary variable named `{kind}_{n}` with the bounds that are given.
+    fn add_bounded_auxiliary(&mut self, kind: &str, lb: Option<N>, ub: Option<N>) -> Variable<N> {
+        let mut builder = self.add_auxiliary(kind);
+        if let Some(lb) = lb {
+            builder = builder.lb(lb);
+        }
+        if let Some(ub) = ub {
+            builder = builder.ub(ub);
+        }
+        builder.build()
+    }
+
+    /// Adds `t` to the objective, which is negated first unless it is
+    /// optimized in `direction` already.
+    fn add_to_objective(&mut self, t: &Variable<N>, direction: OptimizationDirection) {
+        if self.direction != direction {
+            let minus = N::zero() - N::one();
+            self.objective = std::mem::take(&mut self.objective) * minus.clone();
+            for v in &mut self.variables {
+                v.obj = v.obj.take().map(|w| w * minus.clone());
+            }
+            for q in &mut self.quadratic_objective {
+                *q = q.map_variables(minus.clone(), &Variable::clone);
+            }
+            self.direction = direction;
+        }
+        self.objective += t;
+    }
+
+    /// Builder for a free auxiliary variable named `{kind}_{n}`, numbered
+    /// across all helpers. A taken name gets a suffix like any other.
+    fn add_auxiliary(&mut self, kind: &str) -> crate::VariableBuilder<'_, N> {
+        let name = format!("{}_{}", kind, self.auxiliaries);
+        self.auxiliaries += 1;
+        self.add_var().name(name).free()
+    }
+}
+
+/// The larger of `a` and `b`.
+fn larger<N: PartialOrd>(a: N, b: N) -> N {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// The smaller of `a` and `b`.
+fn smaller<N: PartialOrd>(a: N, b: N) -> N {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// `k` as a coefficient.
+fn count<N>(k: usize) -> N
+where
+    N: Num,
+{
+    (0..k).fold(N::zero(), |acc, _| acc + N::one())
+}
+
+/// Expressions `g` such that `c` holds exactly when every `g <= 0`.
+fn implication_rows<N>(c: Constraint<N>) -> Vec<Expression<N>>
+where
+    N: Num + Clone,
+{
+    let Constraint { lhs, sense, rhs } = c;
+    let mut g = lhs - rhs;
+    g.simplify();
+    match sense {
+        ConstraintSense::Le => vec![g],
+        ConstraintSense::Ge => vec![-g],
+        ConstraintSense::Eq => vec![g.clone(), -g],
+        ConstraintSense::Range { lb, ub } => vec![g.clone() - ub, -g + lb],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, rat_frac, DebugArtifacts, Expression, Model, ModelError, Variable};
+
+    type V = Variable<BigRational>;
+
+    fn implication_model() -> (Model<BigRational>, V, V, V) {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(10).0).build();
+        let y = model.add_var().name("y").lb(c(-2).0).ub(c(4).0).build();
+        let b = model.add_var().name("b").binary().build();
+        (model, x, y, b)
+    }
+
+    #[test]
+    fn test_implication_rows() {
+        let (mut model, x, y, b) = implication_model();
+
+        assert_eq!(
+            model.add_implication(x.clone(), true, y.clone().le(c(1).0), c(5).0),
+            Err(ModelError::NotBinary("x".to_string()))
+        );
+        let ids = model
+            .add_implication(b.clone(), true, (&x + &y).le(c(3).0), c(11).0)
+            .unwrap();
+        assert_eq!(ids.len(), 1);
+        let ids = model
+            .add_implication_auto(b.clone(), false, (&x - &y).eq(c(1).0))
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        model
+            .add_implication_auto(b.clone(), true, x.clone().between(c(2).0, c(4).0))
+            .unwrap();
+        let z = model.add_var().name("z").lb_neg_inf().ub(c(1).0).build();
+        assert_eq!(
+            model.add_implication_auto(b.clone(), true, z.clone().ge(c(0).0)),
+            Err(ModelError::UnboundedVariable("z".to_string()))
+        );
+        model
+            .add_implication_auto(b.clone(), true, z.le(c(0).0))
+            .unwrap();
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(
+                "Subject To\n \
+                 c0: x + y + 11 b <= 14\n \
+                 c1: x - y - 11 b <= 1\n \
+                 c2: -x + y - 5 b <= -1\n \
+                 c3: x + 6 b <= 10\n \
+                 c4: -x + 2 b <= 0\n \
+                 c5: z + b <= 1\n"
+            ),
+            "{lp}"
+        );
+    }
+
+    #[test]
+    fn test_implication() {
+        let (mut model, x, _, b) = implication_model();
+        model.maximize();
+        model.set_objective(&x + &b * c(0).0);
+        model
+            .add_implication_auto(b.clone(), true, x.clone().le(c(3).0))
+            .unwrap();
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x.clone()), c(10).0);
+        model.add_const(b.clone().eq(c(1).0));
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(x), c(3).0);
+    }
+
+    #[test]
+    fn test_auxiliary_variables() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("max_1").build();
+        let t = model.abs(&x - c(3).0);
+        let m = model.max_of(vec![(&x).into(), &y * c(2).0]);
+        let n = model.min_of(vec![(&x).into()]);
+        assert_eq!(t.name(), "abs_0");
+        assert_eq!(m.name(), "max_1_1");
+        assert_eq!(n.name(), "min_2");
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(
+                "Subject To\n \
+                 c0: abs_0 - x >= -3\n \
+                 c1: abs_0 + x >= 3\n \
+                 c2: max_1_1 - x >= 0\n \
+                 c3: max_1_1 - 2 max_1 >= 0\n \
+                 c4: min_2 - x <= 0\n"
+            ),
+            "{lp}"
+        );
+    }
+
+    #[test]
+    fn test_cardinality() {
+        let mut model = Model::<BigRational>::new();
+        let b = (0..3)
+            .map(|i| model.add_var().name(format!("b{i}")).binary().build())
+            .collect::<Vec<_>>();
+        let x = model.add_var().name("x").build();
+        model
+            .add_const_named("at_most_2_1", b[0].clone().le(c(1).0))
+            .unwrap();
+
+        let one = model.add_exactly_one(&b).unwrap();
+        let most = model.add_at_most(&b, 2).unwrap();
+        let least = model.add_at_least(&[b[0].clone(), b[1].clone(), b[0].clone()], 1);
+        let exact = model.add_exactly_k(&b[1..], 2).unwrap();
+        assert_eq!(least.as_ref().map(|id| id.index()), Ok(3));
+        assert_eq!(
+            model.add_at_most(&[b[0].clone(), x.clone()], 1),
+            Err(ModelError::NotBinary("x".to_string()))
+        );
+        assert_eq!(
+            model.add_exactly_one(&[x]),
+            Err(ModelError::NotBinary("x".to_string()))
+        );
+        assert_eq!(model.num_constraints(), 5);
+        assert_eq!([one.index(), most.index(), exact.index()], [1, 2, 4]);
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(
+                "Subject To\n \
+                 at_most_2_1: b0 <= 1\n \
+                 exactly_one_0: b0 + b1 + b2 = 1\n \
+                 at_most_2_1_1: b0 + b1 + b2 <= 2\n \
+                 at_least_1_2: b0 + b1 >= 1\n \
+                 exactly_2_3: b1 + b2 = 2\n"
+            ),
+            "{lp}"
+        );
+    }
+
+    #[test]
+    fn test_abs() {
+        let mut model = Model::<BigRational>::new();
+        let x = model
+            .add_var()
+            .name("x")
+            .integer()
+            .lb(c(0).0)
+            .ub(c(10).0)
+            .build();
+        let t = model.abs(&x - c(3).0);
+        model.set_objective((&t).into());
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(0).0));
+        assert_eq!(solution.get_value(x), c(3).0);
+    }
+
+    #[test]
+    fn test_minimax() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).ub(c(4).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).ub(c(4).0).build();
+        model.maximize();
+        model.set_objective(c(0) * &x);
+        let t = model.minimize_max(vec![
+            (&x).into(),
+            (&y).into(),
+            Expression::from(c(4).0) - &x - &y,
+        ]);
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.starts_with("Minimize\n"), "{lp}");
+        assert!(lp.contains(" 0 <= minimax_0 <= 4\n"), "{lp}");
+        assert!(lp.contains(" minimax_0 + x + y >= 4\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&t), rat_frac(4, 3));
+        assert_eq!(solution.objective_value(), Some(&rat_frac(4, 3)));
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).ub(c(3).0).build();
+        model.add_const((&x + &y).le(c(5).0));
+        model.set_objective(c(-2) * &y);
+        let t = model.maximize_min(vec![(&x).into(), (&y).into()]);
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.starts_with("Maximize\n obj: 2 y + maximin_0\n"), "{lp}");
+        assert!(lp.contains(" 0 <= maximin_0 <= 3\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&t), c(2).0);
+        assert_eq!(solution.get_value(&y), c(3).0);
+    }
+
+    #[test]
+    fn test_piecewise_linear() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(0.0).ub(6.0).build();
+        assert_eq!(
+            model.add_piecewise_linear(&x, &[(0.0, 1.0)]).err(),
+            Some(ModelError::InvalidBreakpoints(
+                "at least two are needed".to_string()
+            ))
+        );
+        assert_eq!(
+            model
+                .add_piecewise_linear(&x, &[(0.0, 1.0), (f64::NAN, 0.0), (6.0, 2.0)])
+                .err(),
+            Some(ModelError::InvalidBreakpoints(
+                "x values have to increase strictly".to_string()
+            ))
+        );
+        assert_eq!(
+            model
+                .add_piecewise_linear(&x, &[(0.0, 1.0), (5.0, 2.0)])
+                .err()
+                .map(|e| e.to_string()),
+            Some("invalid breakpoints: bounds of `x` exceed them".to_string())
+        );
+        assert_eq!(model.num_vars(), 1);
+        let y = model
+            .add_piecewise_linear(&x, &[(0.0, 5.0), (2.5, 0.0), (6.0, 7.0)])
+            .unwrap();
+        model.set_objective((&y).into());
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), 2.5);
+        assert_eq!(solution.get_value(&y), 0.0);
+    }
+
+    #[test]
+    fn test_piecewise_linear_exact() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let kink = rat_frac(7, 3);
+        let y = model
+            .add_piecewise_linear(
+                &x,
+                &[(c(0).0, c(5).0), (kink.clone(), c(0).0), (c(6).0, c(7).0)],
+            )
+            .unwrap();
+        model.set_objective((&y).into());
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(
+                "Subject To\n \
+                 c0: pwl_segment_2 - pwl_delta_0 <= 0\n \
+                 c1: pwl_delta_1 - pwl_segment_2 <= 0\n \
+                 c2: x - 7/3 pwl_delta_0 - 11/3 pwl_delta_1 = 0\n \
+                 c3: pwl_3 + 5 pwl_delta_0 - 7 pwl_delta_1 = 5\n"
+            ),
+            "{lp}"
+        );
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.get_value(&x), kink);
+        assert_eq!(solution.get_value(&y), c(0).0);
+        assert_eq!(solution.objective_value(), Some(&c(0).0));
+    }
+}
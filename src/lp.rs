@@ -0,0 +1,677 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    io::BufRead,
+};
+
+use num::{BigRational, Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    error::LpParseError,
+    expression::Expression,
+    is_reserved_constraint_name, is_valid_lp_name,
+    solution::{parse_f64, parse_rational},
+    Model,
+};
+
+/// Terms of an expression as read, with variables given by name.
+type RawExpression<N> = Vec<(N, Option<String>)>;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Start,
+    Objective,
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+    SemiContinuous,
+    End,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Relation {
+    Le,
+    Eq,
+    Ge,
+}
+
+struct RawConstraint<N>
+where
+    N: Num + Clone,
+{
+    line: usize,
+    name: Option<String>,
+    indicator: Option<(String, bool)>,
+    lhs: RawExpression<N>,
+    sense: ConstraintSense<N>,
+    rhs: RawExpression<N>,
+}
+
+/// State of reading an LP file, turned into a model once `End` is reached.
+struct LpReader<N, F>
+where
+    N: Num + Clone,
+{
+    parse: F,
+    line: usize,
+    maximize: bool,
+    objective: String,
+    /// First line of the objective, which can span several lines.
+    objective_line: usize,
+    constraints: Vec<RawConstraint<N>>,
+    /// Row read so far with its first line, as rows can continue on the
+    /// following lines.
+    row: Option<(usize, String)>,
+    bounds: BTreeMap<String, (Option<N>, Option<N>)>,
+    /// Variables in the order of the Bounds section, followed by the ones
+    /// that only appear elsewhere.
+    bounds_order: Vec<String>,
+    other_order: Vec<String>,
+    general: BTreeSet<String>,
+    binary: BTreeSet<String>,
+    /// Semi-continuous variables, with the line declaring them.
+    semi: BTreeMap<String, usize>,
+}
+
+impl<N, F> LpReader<N, F>
+where
+    N: Num + Clone + Display + PartialOrd + Signed,
+    F: Fn(&str) -> Option<N>,
+{
+    fn new(parse: F) -> Self {
+        Self {
+            parse,
+            line: 0,
+            maximize: false,
+            objective: String::new(),
+            objective_line: 0,
+            constraints: vec![],
+            row: None,
+            bounds: BTreeMap::new(),
+            bounds_order: vec![],
+            other_order: vec![],
+            general: BTreeSet::new(),
+            binary: BTreeSet::new(),
+            semi: BTreeMap::new(),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> LpParseError {
+        LpParseError::Syntax {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn read(mut self, r: impl BufRead) -> Result<Model<N>, LpParseError> {
+        let mut section = Section::Start;
+        for line in r.lines() {
+            let line = line?;
+            self.line += 1;
+            let line = line.trim();
+            // `\` starts a comment
+            let line = line.split_once('\\').map_or(line, |(line, _)| line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(next) = self.section(line) {
+                self.finish_row()?;
+                section = next;
+                continue;
+            }
+            match section {
+                Section::Start => return Err(self.error("expected `Maximize` or `Minimize`")),
+                Section::Objective => {
+                    if self.objective.is_empty() {
+                        self.objective_line = self.line;
+                    }
+                    self.objective.push(' ');
+                    self.objective.push_str(line);
+                }
+                Section::Constraints => match &mut self.row {
+                    Some((_, row)) if continues_row(row, line) => {
+                        row.push(' ');
+                        row.push_str(line);
+                    }
+                    _ => {
+                        self.finish_row()?;
+                        self.row = Some((self.line, line.to_string()));
+                    }
+                },
+                Section::Bounds => self.bound(line)?,
+                Section::General | Section::Binary | Section::SemiContinuous => {
+                    for name in line.split_whitespace() {
+                        self.variable(name)?;
+                        let name = name.to_string();
+                        match section {
+                            Section::General => self.general.insert(name),
+                            Section::Binary => self.binary.insert(name),
+                            _ => self.semi.insert(name, self.line).is_none(),
+                        };
+                    }
+                }
+                Section::End => return Err(self.error("text after `End`")),
+            }
+        }
+        if section != Section::End {
+            return Err(self.error("missing `End`"));
+        }
+        self.build()
+    }
+
+    /// Parses the row read so far, reporting errors at its first line.
+    fn finish_row(&mut self) -> Result<(), LpParseError> {
+        let Some((first, row)) = self.row.take() else {
+            return Ok(());
+        };
+        let line = std::mem::replace(&mut self.line, first);
+        let constraint = self.constraint(&row);
+        self.line = line;
+        self.constraints.push(constraint?);
+        Ok(())
+    }
+
+    /// The section `line` starts, if it is a section header.
+    fn section(&mut self, line: &str) -> Option<Section> {
+        let header = line.to_ascii_lowercase();
+        let section = match header.as_str() {
+            "maximize" | "maximise" | "maximum" | "max" => {
+                self.maximize = true;
+                Section::Objective
+            }
+            "minimize" | "minimise" | "minimum" | "min" => Section::Objective,
+            "subject to" | "such that" | "st" | "s.t." => Section::Constraints,
+            "bounds" | "bound" => Section::Bounds,
+            "general" | "generals" | "gen" => Section::General,
+            "binary" | "binaries" | "bin" => Section::Binary,
+            "semi-continuous" | "semis" | "semi" => Section::SemiContinuous,
+            "end" => Section::End,
+            _ => return None,
+        };
+        Some(section)
+    }
+
+    /// Records the first appearance of a variable outside the Bounds
+    /// section.
+    fn variable(&mut self, name: &str) -> Result<(), LpParseError> {
+        if !is_valid_lp_name(name) {
+            return Err(self.error(format!("`{}` is not a valid name", name)));
+        }
+        if !self.bounds.contains_key(name) && !self.other_order.iter().any(|n| n == name) {
+            self.other_order.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    fn number(&self, s: &str) -> Result<N, LpParseError> {
+        (self.parse)(s).ok_or_else(|| self.error(format!("`{}` is not a number", s)))
+    }
+
+    /// A bound value, `None` for an infinite one.
+    fn bound_value(&self, s: &str) -> Result<Option<N>, LpParseError> {
+        let unsigned = s.trim_start_matches(['+', '-']);
+        if unsigned.eq_ignore_ascii_case("inf") || unsigned.eq_ignore_ascii_case("infinity") {
+            Ok(None)
+        } else {
+            self.number(s).map(Some)
+        }
+    }
+
+    /// Parses terms such as `2 x - 5/7 y + 3`.
+    fn expression(&mut self, s: &str) -> Result<RawExpression<N>, LpParseError> {
+        let mut tokens = vec![];
+        for token in s.split_whitespace() {
+            // a sign can be attached to the term, as in `-x`
+            match token.find(|c| c != '+' && c != '-') {
+                Some(1) => tokens.extend([&token[..1], &token[1..]]),
+                _ => tokens.push(token),
+            }
+        }
+        if tokens.is_empty() {
+            return Err(self.error("expected an expression"));
+        }
+
+        let mut terms = vec![];
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(mut token) = tokens.next() {
+            let mut negative = false;
+            if token == "+" || token == "-" {
+                negative = token == "-";
+                token = tokens
+                    .next()
+                    .ok_or_else(|| self.error(format!("expected a term after `{}`", token)))?;
+            } else if !terms.is_empty() {
+                return Err(self.error(format!("expected `+` or `-` before `{}`", token)));
+            }
+            let (coefficient, name) = if token.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+            {
+                let coefficient = self.number(token)?;
+                match tokens.next_if(|t| *t != "+" && *t != "-") {
+                    Some(name) => (coefficient, Some(name)),
+                    None => (coefficient, None),
+                }
+            } else {
+                (N::one(), Some(token))
+            };
+            if let Some(name) = name {
+                self.variable(name)?;
+            }
+            let coefficient = if negative { -coefficient } else { coefficient };
+            terms.push((coefficient, name.map(str::to_string)));
+        }
+        Ok(terms)
+    }
+
+    /// Parses a row such as `c0: x + y <= 3`, `c1: 1 <= x <= 2` or
+    /// `c2: b = 1 -> x >= 2`.
+    fn constraint(&mut self, line: &str) -> Result<RawConstraint<N>, LpParseError> {
+        let (name, body) = match line.split_once(':') {
+            Some((name, body)) => (Some(name.trim().to_string()), body),
+            None => (None, line),
+        };
+        let (indicator, body) = match body.split_once("->") {
+            Some((condition, body)) => {
+                let (variable, value) = match split_relation(condition) {
+                    Some((variable, Relation::Eq, value)) => (variable.trim(), value.trim()),
+                    _ => return Err(self.error("expected an indicator like `b = 1`")),
+                };
+                let active_when = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(self.error("indicator values are 0 or 1")),
+                };
+                self.variable(variable)?;
+                (Some((variable.to_string(), active_when)), body)
+            }
+            None => (None, body),
+        };
+
+        let Some((lhs, relation, rest)) = split_relation(body) else {
+            return Err(self.error("expected `<=`, `>=` or `=`"));
+        };
+        let (lhs, sense, rhs) = match split_relation(rest) {
+            Some((middle, second, rhs)) => {
+                let (lb, ub) = match (relation, second) {
+                    (Relation::Le, Relation::Le) => (lhs, rhs),
+                    (Relation::Ge, Relation::Ge) => (rhs, lhs),
+                    _ => return Err(self.error("ranges need two `<=` or two `>=`")),
+                };
+                let sense = ConstraintSense::Range {
+                    lb: self.number(lb.trim())?,
+                    ub: self.number(ub.trim())?,
+                };
+                (self.expression(middle)?, sense, vec![])
+            }
+            None => {
+                let sense = match relation {
+                    Relation::Le => ConstraintSense::Le,
+                    Relation::Eq => ConstraintSense::Eq,
+                    Relation::Ge => ConstraintSense::Ge,
+                };
+                (self.expression(lhs)?, sense, self.expression(rest)?)
+            }
+        };
+        Ok(RawConstraint {
+            line: self.line,
+            name,
+            indicator,
+            lhs,
+            sense,
+            rhs,
+        })
+    }
+
+    /// Parses a bound such as `x free`, `0 <= x <= 4`, `x >= 2` or `x = 1`.
+    fn bound(&mut self, line: &str) -> Result<(), LpParseError> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let (name, lb, ub) = if let [name, free] = words[..] {
+            if !free.eq_ignore_ascii_case("free") {
+                return Err(self.error("expected a bound"));
+            }
+            (name, None, None)
+        } else {
+            let Some((first, relation, rest)) = split_relation(line) else {
+                return Err(self.error("expected a bound"));
+            };
+            let (first, rest) = (first.trim(), rest.trim());
+            match split_relation(rest) {
+                Some((name, second, last)) => {
+                    let (lb, ub) = match (relation, second) {
+                        (Relation::Le, Relation::Le) => (first, last.trim()),
+                        (Relation::Ge, Relation::Ge) => (last.trim(), first),
+                        _ => return Err(self.error("ranges need two `<=` or two `>=`")),
+                    };
+                    (name.trim(), self.bound_value(lb)?, self.bound_value(ub)?)
+                }
+                None => {
+                    let (name, value, relation) = if is_valid_lp_name(first) {
+                        (first, rest, relation)
+                    } else {
+                        // `2 <= x` is `x >= 2`
+                        let flipped = match relation {
+                            Relation::Le => Relation::Ge,
+                            Relation::Eq => Relation::Eq,
+                            Relation::Ge => Relation::Le,
+                        };
+                        (rest, first, flipped)
+                    };
+                    let value = self.bound_value(value)?;
+                    let (lb, ub) = self
+                        .bounds
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(default_bounds);
+                    match relation {
+                        Relation::Le => (name, lb, value),
+                        Relation::Ge => (name, value, ub),
+                        Relation::Eq => (name, value.clone(), value),
+                    }
+                }
+            }
+        };
+        if !is_valid_lp_name(name) {
+            return Err(self.error(format!("`{}` is not a valid name", name)));
+        }
+        if !self.bounds.contains_key(name) {
+            self.bounds_order.push(name.to_string());
+        }
+        self.bounds.insert(name.to_string(), (lb, ub));
+        Ok(())
+    }
+
+    fn build(mut self) -> Result<Model<N>, LpParseError> {
+        let objective = match self.objective.split_once(':') {
+            Some((_, objective)) => objective.to_string(),
+            None => self.objective.clone(),
+        };
+        self.line = self.objective_line;
+        let objective = if objective.trim().is_empty() {
+            vec![]
+        } else {
+            self.expression(&objective)?
+        };
+
+        let mut model = Model::new();
+        if self.maximize {
+            model.maximize();
+        }
+
+        let mut variables = BTreeMap::new();
+        let order = self.bounds_order.iter().chain(
+            self.other_order
+                .iter()
+                .filter(|name| !self.bounds.contains_key(*name)),
+        );
+        for name in order {
+            let binary = self.binary.contains(name);
+            let (lb, ub) = match self.bounds.get(name) {
+                Some(bounds) => bounds.clone(),
+                None => default_bounds(),
+            };
+            let mut builder = model.add_var().name(name.clone());
+            if binary {
+                builder = builder.binary();
+            } else if self.semi.contains_key(name) {
+                builder = if self.general.contains(name) {
+                    builder.semi_integer()
+                } else {
+                    builder.semi_continuous()
+                };
+            } else if self.general.contains(name) {
+                builder = builder.integer();
+            }
+            builder = match lb {
+                Some(lb) => builder.lb(lb),
+                None => builder.lb_neg_inf(),
+            };
+            if let Some(ub) = ub {
+                builder = builder.ub(ub);
+            }
+            let variable = builder.try_build().map_err(|e| LpParseError::Syntax {
+                line: self.semi.get(name).copied().unwrap_or(self.line),
+                message: e.to_string(),
+            })?;
+            variables.insert(name.clone(), variable);
+        }
+        let expression = |terms: RawExpression<N>| {
+            Expression(
+                terms
+                    .into_iter()
+                    .map(|(w, name)| (w, name.map(|name| variables[&name].clone())))
+                    .collect(),
+            )
+        };
+
+        model.set_objective(expression(objective));
+
+        for (index, c) in self.constraints.into_iter().enumerate() {
+            let error = |e: crate::ModelError| LpParseError::Syntax {
+                line: c.line,
+                message: e.to_string(),
+            };
+            let constraint = Constraint {
+                lhs: expression(c.lhs),
+                sense: c.sense,
+                rhs: expression(c.rhs),
+            };
+            // default names are left to the model, and so are names it
+            // reserves, which another row or the objective would share
+            let name = c.name.filter(|name| !is_reserved_constraint_name(name));
+            let id = match (c.indicator, name) {
+                (Some((indicator, active_when)), name) => {
+                    let id = model
+                        .add_indicator(variables[&indicator].clone(), active_when, constraint)
+                        .map_err(error)?;
+                    if let Some(name) = name {
+                        if model
+                            .live_constraints()
+                            .any(|(_, c)| c.name.as_deref() == Some(name.as_str()))
+                        {
+                            return Err(error(crate::ModelError::DuplicateName(name)));
+                        }
+                        model.constraints[id.0]
+                            .as_mut()
+                            .expect("constraint was just added")
+                            .name = Some(name);
+                    }
+                    id
+                }
+                (None, Some(name)) => model.add_const_named(name, constraint).map_err(error)?,
+                (None, None) => model.add_const(constraint),
+            };
+            debug_assert_eq!(id.index(), index);
+        }
+
+        Ok(model)
+    }
+}
+
+/// Bounds of a variable the Bounds section does not mention.
+fn default_bounds<N: Num>() -> (Option<N>, Option<N>) {
+    (Some(N::zero()), None)
+}
+
+/// Whether `line` continues `row` rather than starting a new row: it does
+/// when `row` has no relation yet, or when `line` starts with a sign or
+/// relation and `row` does not end in a relation and a number.
+fn continues_row(row: &str, line: &str) -> bool {
+    let body = row.rsplit_once("->").map_or(row, |(_, body)| body);
+    let Some((_, _, mut rhs)) = split_relation(body) else {
+        return true;
+    };
+    while let Some((_, _, rest)) = split_relation(rhs) {
+        rhs = rest;
+    }
+    let rhs = rhs.split_whitespace().collect::<String>();
+    let complete = rhs.contains(|c: char| c.is_ascii_digit())
+        && rhs
+            .chars()
+            .all(|c| c.is_ascii_digit() || "+-./eE".contains(c));
+    !complete && line.starts_with(['+', '-', '<', '>', '='])
+}
+
+/// Splits `s` at its first comparison operator.
+fn split_relation(s: &str) -> Option<(&str, Relation, &str)> {
+    let start = s.find(['<', '>', '='])?;
+    let rest = &s[start..];
+    let (relation, len) = if rest.starts_with("<=") || rest.starts_with("=<") {
+        (Relation::Le, 2)
+    } else if rest.starts_with(">=") || rest.starts_with("=>") {
+        (Relation::Ge, 2)
+    } else {
+        match rest.as_bytes()[0] {
+            b'<' => (Relation::Le, 1),
+            b'>' => (Relation::Ge, 1),
+            _ => (Relation::Eq, 1),
+        }
+    };
+    Some((&s[..start], relation, &s[start + len..]))
+}
+
+impl Model<BigRational> {
+    /// Reads a model in the subset of the LP format written by `write_lp`:
+    /// one row per line, or continued on lines starting with a sign or
+    /// relation as wrapped by `LpWriter`, with coefficients given as
+    /// integers, fractions or decimals, which are read exactly. Variables
+    /// the Bounds section does not mention get the LP format's default
+    /// lower bound of zero.
+    pub fn read_lp(r: impl BufRead) -> Result<Self, LpParseError> {
+        LpReader::new(parse_rational).read(r)
+    }
+}
+
+impl Model<f64> {
+    /// Like `Model::<BigRational>::read_lp`, rounding coefficients to the
+    /// nearest `f64`.
+    pub fn read_lp(r: impl BufRead) -> Result<Self, LpParseError> {
+        LpReader::new(parse_f64).read(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, LpParseError, Model};
+
+    fn r(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model
+            .add_var()
+            .name("y")
+            .integer()
+            .lb(r(-1, 3))
+            .ub(c(10).0)
+            .build();
+        let b = model.add_var().name("b").binary().build();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .lb(c(2).0)
+            .ub(c(6).0)
+            .build();
+        let t = model.add_var().semi_integer().ub(c(6).0).build();
+        let _free = model.add_var().ub(c(6).0).build();
+        model.maximize();
+        model.set_objective(c(2) * &x - r(5, 7) * &y + &s + &t);
+        model
+            .add_const_named("cap", (&x + c(4) * &y).le(c(24).0))
+            .unwrap();
+        model.add_const((&x - &b).ge(c(-1).0));
+        model.add_const((&x + &y + c(1).0).between(c(2).0, c(8).0));
+        model.add_const((-&x).eq(c(0).0));
+        model.add_indicator(b.clone(), false, x.le(c(3).0)).unwrap();
+
+        let lp = model.to_lp_string().unwrap();
+        let read = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(read.to_lp_string().unwrap(), lp);
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(0.5).build();
+        model.set_objective(&x * 0.1);
+        model.add_const((&x * 1e20).le(3.25));
+        let lp = model.to_lp_string().unwrap();
+        let read = Model::<f64>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(read.to_lp_string().unwrap(), lp);
+    }
+
+    #[test]
+    fn test_read_lp() {
+        let lp = "\\ written by hand\n\
+                  MAXIMIZE\n\
+                  \x20value: 0.25 x\n\
+                  \x20+ 3 y - z\n\
+                  subject to\n\
+                  \x20x + y <= 4.5\n\
+                  \x20limit: 1e1 >= 2 x - y\n\
+                  bounds\n\
+                  \x20y <= 3\n\
+                  \x20-inf <= x <= +inf\n\
+                  generals\n\
+                  \x20y\n\
+                  end\n";
+        let model = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Maximize\n obj: 1/4 x + 3 y - z\n\
+             Subject To\n c0: x + y <= 9/2\n limit: -2 x + y >= -10\n\
+             Bounds\n 0 <= y <= 3\n x free\n 0 <= z <= +inf\n\
+             General\n y\nBinary\nEnd\n"
+        );
+
+        // rows numbered from 1, as other writers do, would share names
+        let lp = "Minimize\n obj: x\nSubject To\n c1: x >= 1\n c2: x <= 4\n obj: x >= 0\nEnd\n";
+        let model = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains("Subject To\n c0: x >= 1\n c1: x <= 4\n c2: x >= 0\n"),
+            "{lp}"
+        );
+    }
+
+    #[test]
+    fn test_read_lp_errors() {
+        let error = |lp: &str| match Model::<BigRational>::read_lp(lp.as_bytes()) {
+            Err(LpParseError::Syntax { line, message }) => (line, message),
+            Err(e) => panic!("{e}"),
+            Ok(_) => panic!("accepted `{lp}`"),
+        };
+        assert_eq!(
+            error(" x <= 1\n"),
+            (1, "expected `Maximize` or `Minimize`".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: x\nSubject To\n c0: x + 2 <= y\n c1: x y <= 2\nEnd\n"),
+            (5, "expected `+` or `-` before `y`".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: x\nSubject To\n c0: x + 1/0x <= 2\nEnd\n"),
+            (4, "`1/0x` is not a number".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: x\nSubject To\n c0: x + y\nEnd\n"),
+            (4, "expected `<=`, `>=` or `=`".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: x\nSubject To\n c0: b = 1 -> x <= 2\nEnd\n"),
+            (4, "variable `b` is not binary".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: s\nSubject To\nSemi-continuous\n s\nEnd\n"),
+            (5, "variable `s` needs an upper bound".to_string())
+        );
+        assert_eq!(
+            error("Minimize\n obj: x\nSubject To\n"),
+            (3, "missing `End`".to_string())
+        );
+    }
+}
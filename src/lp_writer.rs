@@ -0,0 +1,293 @@
+use std::{fmt::Display, io::Write};
+
+use num::{Num, Signed};
+
+use crate::{Bound, ExportOptions, Model, OptimizationDirection, VariableType};
+
+/// Column `LpWriter` wraps at by default, below the 255 characters some LP
+/// readers cut lines at.
+const DEFAULT_LINE_WIDTH: usize = 250;
+
+/// Indentation of the lines a wrapped row continues on.
+const CONTINUATION_INDENT: &str = "   ";
+
+/**
+ * Writes models in the LP format, wrapping rows and the objective between
+ * terms so that lines stay within a width
+ *
+ * A term is never split, so a line only exceeds the width if a single term,
+ * with its coefficient and a relation or bound next to it, does. Continued
+ * lines are indented and start with a sign or relation, which is how
+ * `read_lp` tells them from new rows.
+ *
+ * ```
+ * use exact_lp::{LpWriter, Model};
+ *
+ * let mut model = Model::<f64>::new();
+ * let x = model.add_var().name("x").build();
+ * let mut out = vec![];
+ * LpWriter::new(&mut out).line_width(80).write_model(&model).unwrap();
+ * ```
+ */
+pub struct LpWriter<W>
+where
+    W: Write,
+{
+    inner: W,
+    line_width: usize,
+    options: ExportOptions,
+}
+
+impl<W> LpWriter<W>
+where
+    W: Write,
+{
+    /// Writer wrapping at column 250.
+    pub fn new(inner: W) -> Self {
+        LpWriter {
+            inner,
+            line_width: DEFAULT_LINE_WIDTH,
+            options: ExportOptions::default(),
+        }
+    }
+
+    /// Wraps lines longer than `width` characters.
+    pub fn line_width(mut self, width: usize) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    pub fn options(mut self, options: ExportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes `head` followed by `terms`, separated by spaces and continued
+    /// on a new line before a term that would end past the line width.
+    fn write_wrapped(
+        &mut self,
+        head: String,
+        terms: impl IntoIterator<Item = String>,
+    ) -> std::io::Result<()> {
+        let mut line = head;
+        for term in terms {
+            if line.len() + 1 + term.len() > self.line_width && !line.trim().is_empty() {
+                writeln!(self.inner, "{line}")?;
+                line = CONTINUATION_INDENT.to_string();
+            } else {
+                line.push(' ');
+            }
+            line.push_str(&term);
+        }
+        writeln!(self.inner, "{line}")
+    }
+
+    /// Writes `model` like `Model::write_lp`.
+    pub fn write_model<N>(&mut self, model: &Model<N>) -> std::io::Result<()>
+    where
+        N: Num + Clone + Display + Signed,
+    {
+        match model.direction {
+            OptimizationDirection::Maximize => writeln!(self.inner, "Maximize")?,
+            OptimizationDirection::Minimize => writeln!(self.inner, "Minimize")?,
+        };
+        let canonical = self.options.canonical;
+        let mut objective = model.full_objective_terms();
+        if canonical {
+            objective.canonicalize();
+        }
+        match model.live_variables().next() {
+            // readers differ on objectives without a variable, a zero term
+            // is understood by all of them
+            Some((i, _)) if objective.is_zero() => {
+                writeln!(self.inner, " obj: 0 {}", model.variable_name_ref(i))?
+            }
+            _ => self.write_wrapped(" obj:".to_string(), objective.lp_terms())?,
+        }
+        // the header is required even when there are no rows
+        writeln!(self.inner, "Subject To")?;
+        for (i, indicator, mut constraint) in model.export_rows()? {
+            if canonical {
+                constraint.canonicalize();
+            }
+            let head = format!(" {}:", model.constraint_name(i));
+            let mut terms = constraint.lp_terms();
+            if let Some((indicator, active_when)) = indicator {
+                let condition = format!("{} = {} ->", indicator, u8::from(*active_when));
+                terms[0] = format!("{condition} {}", terms[0]);
+            }
+            self.write_wrapped(head, terms)?;
+        }
+        let w = &mut self.inner;
+        model.write_variable_section(w, "Bounds", canonical, |w, name, v| {
+            if let VariableType::Binary = v.v_type {
+                // the Binary section implies [0, 1], so only fixings are written
+                let zero = N::zero();
+                let one = N::one();
+                let lb = v.lb.finite().unwrap_or(&zero);
+                let ub = v.ub.finite().unwrap_or(&one);
+                if !lb.is_zero() || !ub.is_one() {
+                    writeln!(w, " {} <= {} <= {}", lb, name, ub)?;
+                }
+                return Ok(());
+            }
+            match (&v.lb, &v.ub) {
+                // the lower bound of semi-continuous variables must be finite
+                (Bound::NegInf, ub) if v.v_type.is_semi() => {
+                    writeln!(w, " 0 <= {} <= {}", name, ub)
+                }
+                (Bound::NegInf, Bound::PosInf) => writeln!(w, " {} free", name),
+                (lb, ub) => writeln!(w, " {} <= {} <= {}", lb, name, ub),
+            }
+        })?;
+        model.write_variable_section(w, "General", canonical, |w, name, v| {
+            if matches!(v.v_type, VariableType::Integer | VariableType::SemiInteger) {
+                writeln!(w, " {name}")?;
+            }
+            Ok(())
+        })?;
+        model.write_variable_section(w, "Binary", canonical, |w, name, v| {
+            if matches!(v.v_type, VariableType::Binary) {
+                writeln!(w, " {name}")?;
+            }
+            Ok(())
+        })?;
+        if model.live_variables().any(|(_, v)| v.v_type.is_semi()) {
+            model.write_variable_section(w, "Semi-continuous", canonical, |w, name, v| {
+                if v.v_type.is_semi() {
+                    writeln!(w, " {name}")?;
+                }
+                Ok(())
+            })?;
+        }
+        writeln!(w, "End")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, DebugArtifacts, ExportOptions, Expression, LpWriter, Model};
+
+    #[test]
+    fn test_wrapped_rows() {
+        let mut model = Model::<BigRational>::new();
+        let x = (0..4)
+            .map(|i| model.add_var().name(format!("x{i}")).lb(c(0).0).build())
+            .collect::<Vec<_>>();
+        let b = model.add_var().name("b").binary().build();
+        model.set_objective(x.iter().map(|x| c(-3) * x).sum::<Expression<_>>());
+        model
+            .add_const_named("long", (&x[0] + &x[1] + &x[2] + &x[3]).le(c(10).0))
+            .unwrap();
+        model
+            .add_const_named(
+                "range",
+                (c(2) * &x[0] - &x[1] + &x[2] - &x[3]).between(c(-1).0, c(1).0),
+            )
+            .unwrap();
+        model
+            .add_indicator(b, true, (&x[0] + &x[1] + &x[2]).ge(c(2).0))
+            .unwrap();
+
+        let mut out = vec![];
+        LpWriter::new(&mut out)
+            .line_width(20)
+            .write_model(&model)
+            .unwrap();
+        let lp = String::from_utf8(out).unwrap();
+        assert_eq!(
+            lp,
+            "Minimize\n obj: -3 x0 - 3 x1\n   - 3 x2 - 3 x3\n\
+             Subject To\n long: x0 + x1 + x2\n   + x3 <= 10\n \
+             range: -1 <= 2 x0\n   - x1 + x2 - x3\n   <= 1\n \
+             c2: b = 1 -> x0\n   + x1 + x2 >= 2\n\
+             Bounds\n 0 <= x0 <= +inf\n 0 <= x1 <= +inf\n 0 <= x2 <= +inf\n 0 <= x3 <= +inf\n\
+             General\nBinary\n b\nEnd\n"
+        );
+        let read = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(read.to_lp_string().unwrap(), model.to_lp_string().unwrap());
+    }
+
+    #[test]
+    fn test_dense_row() {
+        let mut model = Model::<BigRational>::new();
+        let x = (0..300)
+            .map(|i| {
+                let name = format!("x{i}");
+                model.add_var().name(name).lb(c(0).0).ub(c(1).0).build()
+            })
+            .collect::<Vec<_>>();
+        let sum = x.iter().map(Expression::from).sum::<Expression<_>>();
+        model.maximize();
+        model.set_objective(sum.clone());
+        let weighted = x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| c(i as i64 + 1) * x)
+            .sum::<Expression<_>>();
+        model
+            .add_const_named("dense", weighted.le(c(1275).0))
+            .unwrap();
+
+        let lp = model.to_lp_string().unwrap();
+        assert!(lp.lines().all(|line| line.len() <= 250), "{lp}");
+        let continued = lp.lines().filter(|line| line.starts_with("   ")).count();
+        assert!(continued > 10, "{lp}");
+        let read = Model::<BigRational>::read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(read.to_lp_string().unwrap(), lp);
+
+        // the 50 cheapest variables use up the capacity
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(50).0));
+    }
+
+    #[test]
+    fn test_canonical_export() {
+        let build = |same: bool| {
+            let mut model = Model::<BigRational>::new();
+            let y = model.add_var().name("y").integer().build();
+            let x = model.add_var().name("x").integer().build();
+            let row = if same {
+                (&x + &y + &x + c(1).0).le(c(5).0)
+            } else {
+                (&y + c(1).0 + c(2) * &x).le(c(5).0)
+            };
+            let objective = if same {
+                &x * &y + &y * &x + (&x - &x + &y)
+            } else {
+                (c(2) * &y) * x.clone() + Expression::from(&y)
+            };
+            model.set_quadratic_objective(objective);
+            model.add_const(row.clone());
+            (model, row)
+        };
+        let (a, mut row_a) = build(true);
+        let (b, mut row_b) = build(false);
+        assert_ne!(row_a.to_string(), row_b.to_string());
+        assert_ne!(a.to_lp_string().unwrap(), b.to_lp_string().unwrap());
+
+        row_a.canonicalize();
+        row_b.canonicalize();
+        assert_eq!(row_a.to_string(), "y + 2 x + 1 <= 5");
+        assert_eq!(row_b.to_string(), row_a.to_string());
+
+        let options = ExportOptions::default().canonical(true);
+        let lp = a.to_lp_string_with(&options).unwrap();
+        assert_eq!(
+            lp,
+            "Minimize\n obj: y + [ 4 y * x ] / 2\n\
+             Subject To\n c0: y + 2 x <= 4\n\
+             Bounds\n 0 <= x <= +inf\n 0 <= y <= +inf\n\
+             General\n x\n y\nBinary\nEnd\n"
+        );
+        assert_eq!(b.to_lp_string_with(&options).unwrap(), lp);
+    }
+}
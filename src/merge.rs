@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use num::Num;
+
+use crate::{constraint::ConstraintId, variable::Variable, InternalConstraint, Model};
+
+/// Prepended to the names of merged variables and constraints that are
+/// taken in the model merged into.
+const MERGE_PREFIX: &str = "merged_";
+
+/**
+ * Where `Model::merge` put the variables and constraints of the merged model
+ *
+ * Variables keep their order and are shifted by `offset`, and so are
+ * constraints by `constraint_offset`. Use `variable` to translate a handle of
+ * the merged model, as it also picks up a changed name.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariableIdOffset {
+    variables: usize,
+    constraints: usize,
+    /// Names the merged variables have now, by their old id.
+    names: Vec<String>,
+}
+
+impl VariableIdOffset {
+    /// Amount added to the id of every merged variable.
+    pub fn offset(&self) -> usize {
+        self.variables
+    }
+
+    /// Amount added to the index of every merged constraint.
+    pub fn constraint_offset(&self) -> usize {
+        self.constraints
+    }
+
+    /// The variable `v` of the merged model became.
+    ///
+    /// # Panics
+    ///
+    /// If `v` is not a variable of the merged model.
+    pub fn variable<N>(&self, v: &Variable<N>) -> Variable<N>
+    where
+        N: Num + Clone,
+    {
+        Variable::new(self.variables + v.id(), Some(self.names[v.id()].clone()))
+    }
+
+    /// The id the constraint `id` of the merged model has now.
+    pub fn constraint(&self, id: ConstraintId) -> ConstraintId {
+        ConstraintId(self.constraints + id.0)
+    }
+}
+
+/// `base`, or `base` with the first numeric suffix that is not in `taken`.
+pub(crate) fn unused_name(taken: &BTreeSet<String>, base: String) -> String {
+    let mut candidate = base.clone();
+    let mut suffix = 1;
+    while taken.contains(&candidate) {
+        candidate = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Appends the variables and constraints of `other`, e.g. to solve
+    /// independent parts together. Names that are taken here get the prefix
+    /// `merged_`, and a suffix if that is taken as well. The objective of
+    /// `other` is added to this one, negated if its direction differs, and
+    /// its further objectives, start values and constraint groups are kept,
+    /// with groups disabled in either model disabled in both; solver
+    /// settings are this model's.
+    pub fn merge(&mut self, other: Model<N>) -> VariableIdOffset {
+        let sign = if other.direction == self.direction {
+            N::one()
+        } else {
+            N::zero() - N::one()
+        };
+        let mut names = Vec::with_capacity(other.variables.len());
+        let mut renamed = BTreeMap::new();
+        for (i, mut v) in other.variables.into_iter().enumerate() {
+            let old = v.name.take().unwrap_or_else(|| format!("v{i}"));
+            let name = if self.variable_names.contains(&old) {
+                self.unused_variable_name(format!("{MERGE_PREFIX}{old}"))
+            } else {
+                old.clone()
+            };
+            renamed.insert(old, name.clone());
+            self.variable_names.insert(name.clone());
+            v.name = Some(name.clone());
+            v.obj = v.obj.map(|w| w * sign.clone());
+            self.variables.push(v);
+            names.push(name);
+        }
+        let offset = VariableIdOffset {
+            variables: self.variables.len() - names.len(),
+            constraints: self.constraints.len(),
+            names,
+        };
+        let remap = |v: &Variable<N>| offset.variable(v);
+
+        self.objective += other.objective.map_variables(&remap) * sign.clone();
+        self.quadratic_objective.extend(
+            other
+                .quadratic_objective
+                .iter()
+                .map(|t| t.map_variables(sign.clone(), &remap)),
+        );
+        self.objectives
+            .extend(other.objectives.iter().map(|o| o.map_variables(&remap)));
+
+        let mut constraint_names = self
+            .live_constraints()
+            .filter_map(|(_, c)| c.name.clone())
+            .collect::<BTreeSet<_>>();
+        for c in other.constraints {
+            let c = c.map(|c| {
+                let name = c.name.map(|name| {
+                    let name = if constraint_names.contains(&name) {
+                        unused_name(&constraint_names, format!("{MERGE_PREFIX}{name}"))
+                    } else {
+                        name
+                    };
+                    constraint_names.insert(name.clone());
+                    name
+                });
+                InternalConstraint {
+                    name,
+                    constraint: c.constraint.map_variables(&remap),
+                    indicator: c.indicator.map(|(y, active)| (remap(&y), active)),
+                    group: c.group,
+                }
+            });
+            self.constraints.push(c);
+        }
+
+        if let Some(start) = other.start {
+            let merged = self.start.get_or_insert_with(Default::default);
+            // start values of unknown names never matched a variable
+            for (name, value) in start {
+                if let Some(name) = renamed.get(&name) {
+                    merged.insert(name.clone(), value);
+                }
+            }
+        }
+        self.disabled_groups.extend(other.disabled_groups);
+        self.substitutions.extend(
+            other
+                .substitutions
+                .iter()
+                .map(|(v, e)| (remap(v), e.map_variables(&remap))),
+        );
+        self.auxiliaries += other.auxiliaries;
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, SolveOptions};
+
+    #[test]
+    fn test_clone_scenarios() {
+        let mut base = Model::<BigRational>::new();
+        let x = base.add_var().name("x").lb(c(0).0).build();
+        base.maximize();
+        base.set_objective((&x).into());
+        base.add_const_named("cap", x.clone().le(c(10).0)).unwrap();
+
+        let mut low = base.clone();
+        low.add_const_named("scenario", x.clone().le(c(4).0))
+            .unwrap();
+        let mut high = base.clone();
+        high.add_var().name("y").build();
+
+        let lp = base.to_lp_string().unwrap();
+        assert!(!lp.contains("scenario") && !lp.contains(" y"), "{lp}");
+        assert!(low.to_lp_string().unwrap().contains(" scenario: x <= 4\n"));
+        assert!(!high.to_lp_string().unwrap().contains("scenario"));
+        assert_eq!(high.num_vars(), 2);
+        assert_eq!(low.num_vars(), 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut first = Model::<BigRational>::new();
+        let x = first.add_var().name("x").lb(c(0).0).build();
+        first.maximize();
+        first.set_objective(c(2) * &x);
+        first.add_const_named("cap", x.clone().le(c(3).0)).unwrap();
+
+        let mut second = Model::<BigRational>::new();
+        let y = second.add_var().name("x").lb(c(0).0).build();
+        let z = second.add_var().name("z").lb(c(0).0).ub(c(5).0).build();
+        second.set_objective(&y - &z);
+        second.add_const_named("cap", (&y + &z).ge(c(5).0)).unwrap();
+        let limit = second.add_const(y.clone().le(c(1).0));
+
+        let offset = first.merge(second);
+        assert_eq!(offset.offset(), 1);
+        let y = offset.variable(&y);
+        let z = offset.variable(&z);
+        assert_eq!((y.id(), y.name().as_str()), (1, "merged_x"));
+        assert_eq!((z.id(), z.name().as_str()), (2, "z"));
+        assert_eq!(offset.constraint(limit).index(), 2);
+        let lp = first.to_lp_string().unwrap();
+        assert!(lp.contains(" obj: 2 x - merged_x + z\n"), "{lp}");
+        assert!(lp.contains(" merged_cap: merged_x + z >= 5\n"), "{lp}");
+        assert!(lp.contains(" c2: merged_x <= 1\n"), "{lp}");
+
+        let solution = first.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&x), c(3).0);
+        assert_eq!(solution.get_value(&y), c(0).0);
+        assert_eq!(solution.get_value(&z), c(5).0);
+        assert_eq!(solution.objective_value(), Some(&c(11).0));
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::Display;
+
+use num::Num;
+
+use crate::{expression::Expression, variable::Variable, Model};
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + PartialOrd,
+{
+    /// Model assigning each of `n` agents to exactly one of `n` tasks and
+    /// each task to exactly one agent at least total cost, where
+    /// `costs[i][j]` is the cost of giving task `j` to agent `i`. The binary
+    /// variable `x_{i}_{j}`, returned as `x[i][j]`, is one if agent `i`
+    /// does task `j`, and the rows are called `agent_{i}` and `task_{j}`.
+    ///
+    /// # Panics
+    ///
+    /// If `costs` is not square.
+    pub fn assignment(costs: &[Vec<N>]) -> (Model<N>, Vec<Vec<Variable<N>>>) {
+        let n = costs.len();
+        assert!(
+            costs.iter().all(|row| row.len() == n),
+            "assignment costs must be a square matrix"
+        );
+        let mut model = Model::new();
+        let x = model.add_var_matrix(n, n).binary().name_prefix("x").build();
+        model.set_objective(cost_objective(costs, &x));
+        for (i, row) in x.iter().enumerate() {
+            let agent = row.iter().cloned().sum::<Expression<N>>();
+            model
+                .add_const_named(format!("agent_{i}"), agent.eq(N::one()))
+                .expect("row names are valid and unique");
+        }
+        for j in 0..n {
+            let task = x.iter().map(|row| row[j].clone()).sum::<Expression<N>>();
+            model
+                .add_const_named(format!("task_{j}"), task.eq(N::one()))
+                .expect("row names are valid and unique");
+        }
+        (model, x)
+    }
+
+    /// Model shipping `demand[j]` to every customer `j` from suppliers that
+    /// can ship up to `supply[i]` each, at least total cost, where
+    /// `costs[i][j]` is the cost per unit from `i` to `j`. The nonnegative
+    /// variable `x_{i}_{j}`, returned as `x[i][j]`, is the amount shipped,
+    /// and the rows are called `supply_{i}` and `demand_{j}`. Supply that is
+    /// not needed stays with the suppliers, so the supply rows are `<=`
+    /// rows, tight when total supply equals total demand; when demand
+    /// exceeds supply the model is infeasible.
+    ///
+    /// # Panics
+    ///
+    /// If `costs` does not have a row per supplier and a column per
+    /// customer.
+    pub fn transportation(
+        supply: &[N],
+        demand: &[N],
+        costs: &[Vec<N>],
+    ) -> (Model<N>, Vec<Vec<Variable<N>>>) {
+        assert!(
+            costs.len() == supply.len() && costs.iter().all(|row| row.len() == demand.len()),
+            "transportation costs must have a row per supplier and a column per customer"
+        );
+        let mut model = Model::new();
+        let x = model
+            .add_var_matrix(supply.len(), demand.len())
+            .lb(N::zero())
+            .name_prefix("x")
+            .build();
+        model.set_objective(cost_objective(costs, &x));
+        for (i, (row, available)) in x.iter().zip(supply).enumerate() {
+            let shipped = row.iter().cloned().sum::<Expression<N>>();
+            model
+                .add_const_named(format!("supply_{i}"), shipped.le(available.clone()))
+                .expect("row names are valid and unique");
+        }
+        for (j, needed) in demand.iter().enumerate() {
+            let received = x.iter().map(|row| row[j].clone()).sum::<Expression<N>>();
+            model
+                .add_const_named(format!("demand_{j}"), received.eq(needed.clone()))
+                .expect("row names are valid and unique");
+        }
+        (model, x)
+    }
+}
+
+/// Sum of `costs[i][j] * x[i][j]`.
+fn cost_objective<N>(costs: &[Vec<N>], x: &[Vec<Variable<N>>]) -> Expression<N>
+where
+    N: Num + Clone,
+{
+    costs
+        .iter()
+        .zip(x)
+        .flat_map(|(costs, x)| costs.iter().zip(x))
+        .map(|(w, v)| Expression::from(v) * w.clone())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, DebugArtifacts, Model};
+
+    #[test]
+    fn test_assignment() {
+        let costs = [[4, 1, 3], [2, 0, 5], [3, 2, 2]]
+            .map(|row| row.map(|w| c(w).0).to_vec())
+            .to_vec();
+        let (model, x) = Model::<BigRational>::assignment(&costs);
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.starts_with(
+                "Minimize\n obj: 4 x_0_0 + x_0_1 + 3 x_0_2 + 2 x_1_0 + 5 x_1_2 + 3 x_2_0 \
+                 + 2 x_2_1 + 2 x_2_2\n"
+            ),
+            "{lp}"
+        );
+        assert!(lp.contains(" agent_1: x_1_0 + x_1_1 + x_1_2 = 1\n"), "{lp}");
+        assert!(lp.contains(" task_2: x_0_2 + x_1_2 + x_2_2 = 1\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(5).0));
+        let matching = x
+            .iter()
+            .map(|row| row.iter().position(|v| solution.get_value(v) == c(1).0))
+            .collect::<Vec<_>>();
+        assert_eq!(matching, [Some(1), Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn test_transportation() {
+        let costs = [[8, 6, 10], [9, 12, 13]]
+            .map(|row| row.map(|w| c(w).0).to_vec())
+            .to_vec();
+        let supply = [c(20).0, c(30).0];
+        let demand = [c(10).0, c(25).0, c(15).0];
+        let (model, x) = Model::<BigRational>::transportation(&supply, &demand, &costs);
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(" supply_0: x_0_0 + x_0_1 + x_0_2 <= 20\n"),
+            "{lp}"
+        );
+        assert!(lp.contains(" demand_1: x_0_1 + x_1_1 = 25\n"), "{lp}");
+        assert!(lp.contains(" 0 <= x_1_2 <= +inf\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(465).0));
+        assert_eq!(solution.get_value(&x[0][1]), c(20).0);
+    }
+}
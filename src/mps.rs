@@ -0,0 +1,431 @@
+use std::fmt::Display;
+
+use num::{BigRational, Num, Signed};
+
+use crate::{
+    constraint::ConstraintSense, merge::unused_name, ExportRow, Model, ModelError,
+    OptimizationDirection, VariableType,
+};
+
+impl Model<f64> {
+    /// Writes the model in free-form MPS, using the same row and column names
+    /// as the LP export. The objective row is `obj`, or `obj_1` and so on if
+    /// a constraint has that name.
+    pub fn export_mps(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_mps(self.export_rows()?, w)
+    }
+}
+
+impl Model<BigRational> {
+    /// Writes the model in free-form MPS like `Model::<f64>::export_mps`.
+    /// MPS has no fractions, so a fractional coefficient, right-hand side or
+    /// bound fails the export with `ModelError::FractionalNumber`.
+    pub fn export_mps(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let rows = self.export_rows()?;
+        self.check_integral(&rows)?;
+        self.write_mps(rows, w)
+    }
+
+    /// Fails with the first coefficient or constant of the objective, number
+    /// of `rows` or bound of a live variable that is not an integer.
+    fn check_integral(&self, rows: &[ExportRow<'_, BigRational>]) -> Result<(), ModelError> {
+        let objective = self.objective_expression();
+        let rows = rows.iter().flat_map(|(_, _, c)| {
+            let range = match &c.sense {
+                ConstraintSense::Range { lb, ub } => vec![lb, ub],
+                _ => vec![],
+            };
+            c.lhs
+                .0
+                .iter()
+                .chain(c.rhs.0.iter())
+                .map(|(w, _)| w)
+                .chain(range)
+        });
+        let bounds = self
+            .live_variables()
+            .flat_map(|(_, v)| v.lb.finite().into_iter().chain(v.ub.finite()));
+        let fraction = objective
+            .0
+            .iter()
+            .map(|(w, _)| w)
+            .chain(rows)
+            .chain(bounds)
+            .find(|number| !number.is_integer());
+        match fraction {
+            Some(number) => Err(ModelError::FractionalNumber(number.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Writes the model with the exported `constraints` in free-form MPS.
+    fn write_mps(
+        &self,
+        constraints: Vec<ExportRow<'_, N>>,
+        w: &mut impl std::io::Write,
+    ) -> std::io::Result<()> {
+        if self.has_quadratic_objective() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "quadratic objectives cannot be written as MPS",
+            ));
+        }
+        let constant = self.objective_constant();
+        let row_names = constraints
+            .iter()
+            .map(|(i, _, _)| self.constraint_name(*i))
+            .collect();
+        let objective = unused_name(&row_names, "obj".to_string());
+
+        writeln!(w, "NAME exact_lp")?;
+        if let OptimizationDirection::Maximize = self.direction {
+            writeln!(w, "OBJSENSE")?;
+            writeln!(w, "    MAX")?;
+        }
+
+        writeln!(w, "ROWS")?;
+        writeln!(w, " N {}", objective)?;
+        for (i, _, c) in constraints.iter() {
+            let row_type = match c.sense {
+                ConstraintSense::Le | ConstraintSense::Range { .. } => "L",
+                ConstraintSense::Eq => "E",
+                ConstraintSense::Ge => "G",
+            };
+            writeln!(w, " {} {}", row_type, self.constraint_name(*i))?;
+        }
+
+        let mut columns = vec![vec![]; self.variables.len()];
+        for (coefficient, v) in self.objective_terms().0 {
+            if let Some(v) = v {
+                columns[v.id()].push((objective.clone(), coefficient));
+            }
+        }
+        for (i, _, c) in constraints.iter() {
+            for (coefficient, v) in c.lhs.0.iter() {
+                if let Some(v) = v {
+                    columns[v.id()].push((self.constraint_name(*i), coefficient.clone()));
+                }
+            }
+        }
+
+        writeln!(w, "COLUMNS")?;
+        for (i, entries) in columns.into_iter().enumerate() {
+            if self.variables[i].eliminated {
+                continue;
+            }
+            let integral = self.variables[i].v_type.is_integral();
+            if integral {
+                writeln!(w, "    MARKER MARKER INTORG")?;
+            }
+            let name = self.variable_name_ref(i);
+            if entries.is_empty() {
+                // every column has to appear for its bounds to be valid
+                writeln!(w, "    {} {} 0", name, objective)?;
+            }
+            for (row, coefficient) in entries {
+                writeln!(w, "    {} {} {}", name, row, coefficient)?;
+            }
+            if integral {
+                writeln!(w, "    MARKER MARKER INTEND")?;
+            }
+        }
+
+        writeln!(w, "RHS")?;
+        if !constant.is_zero() {
+            // the right-hand side of the objective row is its negated constant
+            writeln!(w, "    RHS {} {}", objective, -constant)?;
+        }
+        for (i, _, c) in constraints.iter() {
+            let rhs = match &c.sense {
+                ConstraintSense::Range { ub, .. } => ub.clone(),
+                _ => c.rhs.0.iter().fold(N::zero(), |r, (b, _)| r + b.clone()),
+            };
+            if !rhs.is_zero() {
+                writeln!(w, "    RHS {} {}", self.constraint_name(*i), rhs)?;
+            }
+        }
+
+        writeln!(w, "RANGES")?;
+        for (i, _, c) in constraints.iter() {
+            if let ConstraintSense::Range { lb, ub } = &c.sense {
+                writeln!(
+                    w,
+                    "    RNG {} {}",
+                    self.constraint_name(*i),
+                    ub.clone() - lb.clone()
+                )?;
+            }
+        }
+
+        if constraints
+            .iter()
+            .any(|(_, indicator, _)| indicator.is_some())
+        {
+            writeln!(w, "INDICATORS")?;
+            for (i, indicator, _) in constraints.iter() {
+                if let Some((indicator, active_when)) = indicator {
+                    writeln!(
+                        w,
+                        " IF {} {} {}",
+                        self.constraint_name(*i),
+                        indicator,
+                        u8::from(*active_when)
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "BOUNDS")?;
+        for (i, v) in self.live_variables() {
+            let name = self.variable_name_ref(i);
+            if let VariableType::Binary = v.v_type {
+                let lb = v.lb.clone().into_finite().unwrap_or_else(N::zero);
+                let ub = v.ub.clone().into_finite().unwrap_or_else(N::one);
+                if lb == ub {
+                    writeln!(w, " FX BND {} {}", name, lb)?;
+                } else if lb.is_zero() && ub.is_one() {
+                    writeln!(w, " BV BND {}", name)?;
+                } else {
+                    writeln!(w, " LO BND {} {}", name, lb)?;
+                    writeln!(w, " UP BND {} {}", name, ub)?;
+                }
+                continue;
+            }
+            if v.v_type.is_semi() {
+                if let Some(lb) = v.lb.finite() {
+                    writeln!(w, " LO BND {} {}", name, lb)?;
+                }
+                let Some(ub) = v.ub.finite() else {
+                    return Err(ModelError::MissingUpperBound(self.variable_name(i)).into());
+                };
+                writeln!(w, " SC BND {} {}", name, ub)?;
+                continue;
+            }
+            match (v.lb.finite(), v.ub.finite()) {
+                (Some(lb), Some(ub)) if lb == ub => {
+                    writeln!(w, " FX BND {} {}", name, lb)?;
+                }
+                (Some(lb), Some(ub)) => {
+                    writeln!(w, " LO BND {} {}", name, lb)?;
+                    writeln!(w, " UP BND {} {}", name, ub)?;
+                }
+                (Some(lb), None) => {
+                    writeln!(w, " LO BND {} {}", name, lb)?;
+                    if let VariableType::Integer = v.v_type {
+                        // some readers default integer columns to binary
+                        writeln!(w, " PL BND {}", name)?;
+                    }
+                }
+                (None, Some(ub)) => {
+                    writeln!(w, " MI BND {}", name)?;
+                    writeln!(w, " UP BND {} {}", name, ub)?;
+                }
+                (None, None) => {
+                    writeln!(w, " FR BND {}", name)?;
+                }
+            }
+        }
+        writeln!(w, "ENDATA")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use num::BigRational;
+
+    use crate::{c, rat_frac, Bound, Model, ModelError, SolveOptions, SolveStats};
+
+    #[test]
+    fn test_export_mps() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model
+            .add_var()
+            .name("y")
+            .integer()
+            .lb(c(0).0)
+            .ub(c(10).0)
+            .build();
+        let b = model.add_var().name("b").binary().build();
+        let z = model.add_var().name("z").free().build();
+        let _unused = model.add_var().lb_neg_inf().ub(c(3).0).build();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_integer()
+            .lb(c(2).0)
+            .ub(c(6).0)
+            .build();
+
+        model.maximize();
+        model.set_objective(c(2) * x.clone() + c(5) * y.clone() + c(7).0);
+        model
+            .add_const_named("cap", (x.clone() + c(4) * y.clone()).le(c(24).0))
+            .unwrap();
+        model.add_const((x.clone() - b.clone()).ge(c(-1).0));
+        model.add_const((y.clone() + z.clone() + s).eq(c(3).0));
+        model.add_const((x.clone() + z.clone() + c(1).0).between(c(2).0, c(8).0));
+
+        let mut out = Vec::new();
+        model.export_mps(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "NAME exact_lp\n\
+             OBJSENSE\n    MAX\n\
+             ROWS\n N obj\n L cap\n G c1\n E c2\n L c3\n\
+             COLUMNS\n\
+             \x20   x obj 2\n    x cap 1\n    x c1 1\n    x c3 1\n\
+             \x20   MARKER MARKER INTORG\n\
+             \x20   y obj 5\n    y cap 4\n    y c2 1\n\
+             \x20   MARKER MARKER INTEND\n\
+             \x20   MARKER MARKER INTORG\n\
+             \x20   b c1 -1\n\
+             \x20   MARKER MARKER INTEND\n\
+             \x20   z c2 1\n    z c3 1\n\
+             \x20   v4 obj 0\n\
+             \x20   MARKER MARKER INTORG\n\
+             \x20   s c2 1\n\
+             \x20   MARKER MARKER INTEND\n\
+             RHS\n    RHS obj -7\n    RHS cap 24\n    RHS c1 -1\n    RHS c2 3\n    RHS c3 7\n\
+             RANGES\n    RNG c3 6\n\
+             BOUNDS\n LO BND x 0\n LO BND y 0\n UP BND y 10\n BV BND b\n FR BND z\n\
+             \x20MI BND v4\n UP BND v4 3\n\
+             \x20LO BND s 2\n SC BND s 6\n\
+             ENDATA\n"
+        );
+    }
+
+    #[test]
+    fn test_objective_row_name() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        model.set_objective(c(3) * &x + c(1).0);
+        let row = model.add_const(x.clone().le(c(4).0));
+        // names read from other files are not checked like `add_const_named`
+        model.constraints[row.index()].as_mut().unwrap().name = Some("obj".to_string());
+
+        let mut out = Vec::new();
+        model.export_mps(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "NAME exact_lp\n\
+             ROWS\n N obj_1\n L obj\n\
+             COLUMNS\n    x obj_1 3\n    x obj 1\n\
+             RHS\n    RHS obj_1 -1\n    RHS obj 4\n\
+             RANGES\n\
+             BOUNDS\n LO BND x 0\n\
+             ENDATA\n"
+        );
+    }
+
+    #[test]
+    fn test_semi_continuous_bounds() {
+        let mut model = Model::<f64>::new();
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .lb(1.0)
+            .ub(3.0)
+            .build();
+        assert_eq!(
+            model.unfix_variable(&s, Some(1.0), None),
+            Err(ModelError::MissingUpperBound("s".to_string()))
+        );
+        let mut out = Vec::new();
+        model.export_mps(&mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains(" SC BND s 3\n"));
+
+        // only models that were read can lack the upper bound
+        model.variables[s.id()].ub = Bound::PosInf;
+        let e = model.export_mps(&mut Vec::new()).unwrap_err();
+        assert_eq!(
+            e.get_ref().unwrap().downcast_ref::<ModelError>(),
+            Some(&ModelError::MissingUpperBound("s".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_fractions() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        model.add_const((&x * rat_frac(1, 3)).le(c(1).0));
+
+        let mut out = Vec::new();
+        let e = model.export_mps(&mut out).unwrap_err();
+        assert_eq!(
+            e.get_ref().unwrap().downcast_ref::<ModelError>(),
+            Some(&ModelError::FractionalNumber("1/3".to_string()))
+        );
+        assert!(out.is_empty());
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(rat_frac(1, 2)).build();
+        model.set_objective(x.into());
+        let e = model.export_mps(&mut Vec::new()).unwrap_err();
+        assert_eq!(
+            e.get_ref().unwrap().downcast_ref::<ModelError>(),
+            Some(&ModelError::FractionalNumber("1/2".to_string()))
+        );
+        // the LP format has fractions
+        assert!(model
+            .to_lp_string()
+            .unwrap()
+            .contains(" 1/2 <= x <= +inf\n"));
+    }
+
+    #[test]
+    fn test_mps_solves_like_lp() {
+        let mut model = Model::<BigRational>::new();
+        let x = model
+            .add_var()
+            .name("x")
+            .integer()
+            .lb(c(0).0)
+            .ub(c(10).0)
+            .build();
+        let y = model.add_var().name("y").integer().lb(c(0).0).build();
+        let b = model.add_var().name("b").binary().build();
+        let z = model.add_var().name("z").lb(c(-2).0).ub(c(2).0).build();
+        model.maximize();
+        model.set_objective(c(3) * &x + c(2) * &y - c(4) * &b + &z + c(5).0);
+        model.add_const((c(2) * &x + c(3) * &y).le(c(17).0));
+        model.add_const((&x - c(10) * &b).le(c(2).0));
+        model.add_const((&x + &y - &z).between(c(1).0, c(7).0));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let lp = dir.path().join("model.lp");
+        let mps = dir.path().join("model.mps");
+        model
+            .write_lp(&mut std::fs::File::create(&lp).unwrap())
+            .unwrap();
+        model
+            .export_mps(&mut std::fs::File::create(&mps).unwrap())
+            .unwrap();
+        let opts = SolveOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let primal_bound = |path: &Path| {
+            let script = vec![
+                format!("read {}", path.display()),
+                "optimize".to_string(),
+                "quit".to_string(),
+            ];
+            let out = model.run_scip(script, &opts).unwrap();
+            SolveStats::from_scip_log(&out.log, Duration::ZERO).primal_bound
+        };
+        // the LP format drops the objective constant that MPS keeps
+        let bound = primal_bound(&lp).unwrap();
+        assert_eq!(primal_bound(&mps), Some(bound + 5.0));
+    }
+}
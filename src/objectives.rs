@@ -0,0 +1,318 @@
+use std::fmt::Display;
+
+use num::{BigRational, Num, Signed, Zero};
+
+use crate::{
+    error::SolveError,
+    expression::Expression,
+    options::SolveOptions,
+    solution::{Solution, SolveStatus},
+    variable::Variable,
+    Model, OptimizationDirection,
+};
+
+/// Relative slack by which an `f64` objective may move away from its optimum
+/// in later stages of `solve_lexicographic`, so round-off in the reported
+/// optimum does not make the next stage infeasible.
+const LEXICOGRAPHIC_TOLERANCE: f64 = 1e-6;
+
+/**
+ * Objective added with `Model::add_objective`
+ */
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+pub(crate) struct Objective<N>
+where
+    N: Num + Clone,
+{
+    pub(crate) expression: Expression<N>,
+    pub(crate) direction: OptimizationDirection,
+    pub(crate) priority: u32,
+}
+
+impl<N> Objective<N>
+where
+    N: Num + Clone,
+{
+    /// Whether the variable with id `i` is in the objective.
+    pub(crate) fn uses(&self, i: usize) -> bool {
+        self.expression.terms().any(|(_, v)| v.id() == i)
+    }
+
+    /// Copy of the objective with every variable replaced by `f` of it.
+    pub(crate) fn map_variables(&self, f: &impl Fn(&Variable<N>) -> Variable<N>) -> Objective<N> {
+        Objective {
+            expression: self.expression.map_variables(f),
+            direction: self.direction,
+            priority: self.priority,
+        }
+    }
+
+    /// Copy of the objective with every coefficient converted by `f`.
+    pub(crate) fn map_coefficients<M>(&self, f: &impl Fn(&N) -> M) -> Objective<M>
+    where
+        M: Num + Clone,
+    {
+        Objective {
+            expression: self.expression.map_coefficients(f),
+            direction: self.direction,
+            priority: self.priority,
+        }
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Adds another objective, returning its index. The objective set with
+    /// `set_objective` has index 0 and priority 0; `solve_lexicographic`
+    /// optimizes lower priorities first.
+    pub fn add_objective(
+        &mut self,
+        expr: Expression<N>,
+        direction: OptimizationDirection,
+        priority: u32,
+    ) -> usize {
+        self.objectives.push(Objective {
+            expression: expr,
+            direction,
+            priority,
+        });
+        self.objectives.len()
+    }
+
+    /// Number of objectives, including the one set with `set_objective`.
+    pub fn num_objectives(&self) -> usize {
+        self.objectives.len() + 1
+    }
+
+    /// The `index`-th objective with its direction.
+    fn objective_at(&self, index: usize) -> (Expression<N>, OptimizationDirection) {
+        match index {
+            0 => (self.objective_expression(), self.direction),
+            i => (
+                self.objectives[i - 1].expression.clone(),
+                self.objectives[i - 1].direction,
+            ),
+        }
+    }
+
+    /// Indices of the objectives in the order `solve_lexicographic` optimizes
+    /// them. An objective 0 without variables is left out when there are
+    /// others, as it was never set.
+    fn lexicographic_order(&self) -> Vec<usize> {
+        let mut order = (0..self.num_objectives()).collect::<Vec<_>>();
+        if !self.objectives.is_empty() && self.objective_terms().0.is_empty() {
+            order.remove(0);
+        }
+        order.sort_by_key(|&i| match i {
+            0 => 0,
+            i => self.objectives[i - 1].priority,
+        });
+        order
+    }
+
+    /// Minimized sum of the given objectives times their weights, with
+    /// maximized objectives negated.
+    fn weighted_objective(&self, weights: &[(N, usize)]) -> Expression<N> {
+        let mut combined = Expression::default();
+        for (weight, index) in weights {
+            let (expr, direction) = self.objective_at(*index);
+            let weight = match direction {
+                OptimizationDirection::Minimize => weight.clone(),
+                OptimizationDirection::Maximize => N::zero() - weight.clone(),
+            };
+            combined += expr * weight;
+        }
+        combined.simplify();
+        combined
+    }
+
+    /// `solution` with the value of every objective attached.
+    fn with_objective_values(&self, solution: Solution<N>) -> Solution<N> {
+        let values = (0..self.num_objectives())
+            .map(|i| self.objective_at(i).0.evaluate_or_zero(solution.values()))
+            .collect();
+        solution.with_objective_values(values)
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Optimizes the objectives one after another, keeping each optimized
+    /// objective within `slack` of its optimum in the following stages.
+    /// Stops early at a stage that does not end optimal.
+    fn solve_in_stages(
+        &self,
+        solve: impl Fn(&Model<N>) -> Result<Solution<N>, SolveError>,
+        slack: impl Fn(&N) -> N,
+    ) -> Result<Solution<N>, SolveError> {
+        self.check_linear_objective()?;
+        let mut stage = self.clone();
+        stage.objectives.clear();
+        stage.clear_objective_coefficients();
+        let mut solution: Option<Solution<N>> = None;
+        for i in self.lexicographic_order() {
+            if let Some(previous) = solution.take() {
+                let Some(optimum) = previous.objective_value().cloned() else {
+                    return Ok(self.with_objective_values(previous));
+                };
+                let allowed = slack(&optimum);
+                let pin = match stage.direction {
+                    OptimizationDirection::Minimize => {
+                        stage.objective.clone().le(optimum + allowed)
+                    }
+                    OptimizationDirection::Maximize => {
+                        stage.objective.clone().ge(optimum - allowed)
+                    }
+                };
+                stage.add_const(pin);
+            }
+            let (expr, direction) = self.objective_at(i);
+            stage.objective = expr;
+            stage.direction = direction;
+            let current = solve(&stage)?;
+            if current.status() != SolveStatus::Optimal {
+                return Ok(self.with_objective_values(current));
+            }
+            solution = Some(current);
+        }
+        Ok(self.with_objective_values(solution.expect("there is at least one objective")))
+    }
+
+    /// Fails for a quadratic objective, which cannot be pinned to its
+    /// optimum or weighted.
+    fn check_linear_objective(&self) -> Result<(), SolveError> {
+        if self.has_quadratic_objective() {
+            return Err(SolveError::UnsupportedQuadraticObjective(
+                "multi-objective solves".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Model minimizing the weighted sum of objectives, see `solve_weighted`.
+    fn weighted_model(&self, weights: &[(N, usize)]) -> Model<N> {
+        let mut combined = self.clone();
+        combined.clear_objective_coefficients();
+        combined.objective = self.weighted_objective(weights);
+        combined.direction = OptimizationDirection::Minimize;
+        combined.objectives.clear();
+        combined
+    }
+}
+
+impl Model<BigRational> {
+    /// Optimizes the objectives in priority order, ties in index order.
+    /// After each stage the objective is constrained to its exact optimum.
+    /// The solution reports the objective value of the last stage and every
+    /// objective through `Solution::objective_values`.
+    pub fn solve_lexicographic(
+        &self,
+        opts: &SolveOptions,
+    ) -> Result<Solution<BigRational>, SolveError> {
+        self.solve_in_stages(|m| m.solve_with(opts), |_| BigRational::zero())
+    }
+
+    /// Minimizes the sum of the objectives given by index, each times its
+    /// weight; maximized objectives enter negated. Panics on an index of
+    /// an objective that does not exist.
+    pub fn solve_weighted(
+        &self,
+        weights: &[(BigRational, usize)],
+        opts: &SolveOptions,
+    ) -> Result<Solution<BigRational>, SolveError> {
+        self.check_linear_objective()?;
+        let solution = self.weighted_model(weights).solve_with(opts)?;
+        Ok(self.with_objective_values(solution))
+    }
+}
+
+impl Model<f64> {
+    /// Like the exact `solve_lexicographic`, but an optimized objective may
+    /// move away from its optimum by a relative tolerance of 1e-6.
+    pub fn solve_lexicographic(&self, opts: &SolveOptions) -> Result<Solution<f64>, SolveError> {
+        self.solve_in_stages(
+            |m| m.solve_with(opts),
+            |optimum| LEXICOGRAPHIC_TOLERANCE * optimum.abs().max(1.0),
+        )
+    }
+
+    /// Like the exact `solve_weighted`.
+    pub fn solve_weighted(
+        &self,
+        weights: &[(f64, usize)],
+        opts: &SolveOptions,
+    ) -> Result<Solution<f64>, SolveError> {
+        self.check_linear_objective()?;
+        let solution = self.weighted_model(weights).solve_with(opts)?;
+        Ok(self.with_objective_values(solution))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, OptimizationDirection, SolveOptions, Variable};
+
+    type V = Variable<BigRational>;
+
+    /// `x + y <= 10` with `x` maximized at priority 0 and `y` maximized at
+    /// priority 1.
+    fn two_objective_model() -> (Model<BigRational>, V, V) {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        model.add_const((&x + &y).le(c(10).0));
+        model.maximize();
+        model.set_objective((&x).into());
+        let index = model.add_objective((&y).into(), OptimizationDirection::Maximize, 1);
+        assert_eq!(index, 1);
+        (model, x, y)
+    }
+
+    #[test]
+    fn test_objective_order() {
+        let (mut model, x, y) = two_objective_model();
+        model.add_objective(&x - &y, OptimizationDirection::Minimize, 0);
+        assert_eq!(model.num_objectives(), 3);
+        assert_eq!(model.lexicographic_order(), vec![0, 2, 1]);
+        assert_eq!(
+            model
+                .weighted_objective(&[(c(1).0, 0), (c(2).0, 1), (c(3).0, 2)])
+                .to_string(),
+            "2 x - 5 y"
+        );
+
+        let mut model = Model::<BigRational>::new();
+        model.add_objective((&x).into(), OptimizationDirection::Minimize, 3);
+        assert_eq!(model.lexicographic_order(), vec![1]);
+    }
+
+    #[test]
+    fn test_multiple_objectives() {
+        let (model, x, y) = two_objective_model();
+        let opts = SolveOptions::default();
+
+        let solution = model.solve_lexicographic(&opts).unwrap();
+        assert_eq!(solution.get_value(x.clone()), c(10).0);
+        assert_eq!(solution.get_value(y.clone()), c(0).0);
+        assert_eq!(solution.objective_values(), &[c(10).0, c(0).0]);
+
+        let solution = model
+            .solve_weighted(&[(c(1).0, 0), (c(2).0, 1)], &opts)
+            .unwrap();
+        assert_eq!(solution.get_value(x), c(0).0);
+        assert_eq!(solution.get_value(y), c(10).0);
+        assert_eq!(solution.objective_values(), &[c(0).0, c(10).0]);
+        assert_eq!(solution.objective_value(), Some(&c(-20).0));
+    }
+}
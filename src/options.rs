@@ -0,0 +1,359 @@
+use std::{path::PathBuf, time::Duration};
+
+/**
+ * Solver executable used to solve a model
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Solver {
+    #[default]
+    Scip,
+    /// The `highs` command-line solver, only for `Model<f64>`.
+    Highs,
+    /// The `cbc` command-line solver, only for `Model<f64>`.
+    Cbc,
+    /// GLPK's `glpsol` command-line solver, only for `Model<f64>`.
+    Glpk,
+    /// CPLEX's interactive optimizer `cplex`, only for `Model<f64>`.
+    Cplex,
+    /// Gurobi's `gurobi_cl` command-line solver, only for `Model<f64>`.
+    Gurobi,
+}
+
+impl Solver {
+    /// Environment variable overriding the executable.
+    pub(crate) fn executable_variable(&self) -> &'static str {
+        match self {
+            Solver::Scip => "SCIP_EXECUTABLE",
+            Solver::Highs => "HIGHS_EXECUTABLE",
+            Solver::Cbc => "CBC_EXECUTABLE",
+            Solver::Glpk => "GLPSOL_EXECUTABLE",
+            Solver::Cplex => "CPLEX_EXECUTABLE",
+            Solver::Gurobi => "GUROBI_EXECUTABLE",
+        }
+    }
+
+    pub(crate) fn default_executable(&self) -> &'static str {
+        match self {
+            Solver::Scip => "scip",
+            Solver::Highs => "highs",
+            Solver::Cbc => "cbc",
+            Solver::Glpk => "glpsol",
+            Solver::Cplex => "cplex",
+            Solver::Gurobi => "gurobi_cl",
+        }
+    }
+}
+
+/**
+ * What the warm start file holds for variables the start solution has no
+ * value for
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingStartValues {
+    /// Leave them out and let the solver complete the start.
+    #[default]
+    Omit,
+    /// Use the variable's lower bound, leaving out variables without one.
+    LowerBound,
+}
+
+/**
+ * What `Model::write_sol` writes for variables the solution has no value for
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingSolutionValues {
+    /// Leave them out, which SCIP reads as zero.
+    #[default]
+    Omit,
+    /// Write them with value zero.
+    Zero,
+}
+
+/**
+ * What `Model::to_standard_form_with` does with equality rows
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EqualityRows {
+    /// Replace `a = b` by `a <= b` and `-a <= -b`.
+    #[default]
+    Split,
+    /// Keep them as equalities.
+    Keep,
+}
+
+/**
+ * Which values the exact solution importer accepts
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportPolicy {
+    /// Read decimals and scientific notation as the exact value they denote.
+    AcceptDecimals,
+    /// Like `AcceptDecimals`, but fail on decimals with 12 or more
+    /// significant digits, which usually are a rounded value such as
+    /// `0.333333333333` rather than an exact one.
+    #[default]
+    RejectRounded,
+    /// Only accept integers and fractions.
+    RequireFractions,
+}
+
+/**
+ * What happens to the formulation, solution and log files of a solve
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DebugArtifacts {
+    /// Write them to a temporary directory that is removed afterwards.
+    #[default]
+    None,
+    /// Keep them in a new temporary directory, reported by
+    /// `Solution::artifact_dir`.
+    Keep,
+    /// Write them to the given directory, creating it if needed.
+    KeepIn(PathBuf),
+}
+
+/**
+ * How the SCIP shell commands of a solve are passed to the executable
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommandInput {
+    /// One `-c` argument per command.
+    #[default]
+    Arguments,
+    /// One command per line on stdin, for long command sequences.
+    Stdin,
+}
+
+/**
+ * Options for `Model::write_lp_with` and `LpWriter::options`
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Canonicalize every row and the objective, see
+    /// `Expression::canonicalize`, and list the variables of the `Bounds`,
+    /// `General` and `Binary` sections by name, so that models with the same
+    /// rows export the same file however they were built.
+    pub canonical: bool,
+}
+
+impl ExportOptions {
+    pub fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+}
+
+/**
+ * Options for a single call to `solve_with`
+ */
+#[derive(Clone, Debug, Default)]
+pub struct SolveOptions {
+    /// How SCIP receives its commands.
+    pub command_input: CommandInput,
+    /// Where the formulation, solution and log files end up.
+    pub debug_artifacts: DebugArtifacts,
+    /// Do not echo the solver log to stdout; it is still available through
+    /// `Solution::solver_log`.
+    pub quiet: bool,
+    pub time_limit: Option<Duration>,
+    /// Relative gap between primal and dual bound at which to stop.
+    pub gap: Option<f64>,
+    pub node_limit: Option<u64>,
+    pub threads: Option<u32>,
+    /// How the start set with `Model::set_start` is completed.
+    pub missing_start_values: MissingStartValues,
+    /// Which values are accepted when reading an exact solution.
+    pub import_policy: ImportPolicy,
+    /// Also read the dual solution from SCIP, only possible for LPs.
+    /// Presolving is disabled so the duals refer to the original constraints.
+    pub duals: bool,
+    /// Most rounds of cuts `Model::solve_with_cuts` adds, unlimited if
+    /// `None`.
+    pub cut_rounds: Option<usize>,
+    /// Total time the solves of `Model::find_iis` may take, unlimited if
+    /// `None`.
+    pub iis_time_budget: Option<Duration>,
+    /// Seed of the solver's random number generators, for SCIP also of the
+    /// permutation of the problem and of the LP solver.
+    pub random_seed: Option<u64>,
+    /// Turn off nondeterministic components: solve on one thread, which
+    /// overrides `threads`, and for SCIP without permuting variables and
+    /// constraints. With a `random_seed` this makes runs reproducible.
+    pub deterministic: bool,
+}
+
+impl SolveOptions {
+    pub fn with_duals(mut self, duals: bool) -> Self {
+        self.duals = duals;
+        self
+    }
+
+    pub fn with_random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Number of threads to request, one for deterministic solves.
+    fn thread_count(&self) -> Option<u32> {
+        if self.deterministic {
+            Some(1)
+        } else {
+            self.threads
+        }
+    }
+
+    /// SCIP shell commands setting the requested limits and seed.
+    pub(crate) fn scip_commands(&self) -> Vec<String> {
+        let mut commands = vec![];
+        if let Some(time_limit) = self.time_limit {
+            commands.push(format!("set limits time {}", time_limit.as_secs_f64()));
+        }
+        if let Some(gap) = self.gap {
+            commands.push(format!("set limits gap {}", gap));
+        }
+        if let Some(node_limit) = self.node_limit {
+            commands.push(format!("set limits nodes {}", node_limit));
+        }
+        if let Some(threads) = self.thread_count() {
+            commands.push(format!("set parallel maxnthreads {}", threads));
+        }
+        if self.deterministic {
+            commands.extend(
+                [
+                    "set randomization permutevars FALSE",
+                    "set randomization permuteconss FALSE",
+                ]
+                .map(String::from),
+            );
+        }
+        if let Some(seed) = self.random_seed {
+            commands.extend([
+                format!("set randomization randomseedshift {}", seed),
+                format!("set randomization permutationseed {}", seed),
+                format!("set randomization lpseed {}", seed),
+            ]);
+        }
+        if self.duals {
+            commands.extend(
+                [
+                    "set presolving emphasis off",
+                    "set propagating maxrounds 0",
+                    "set propagating maxroundsroot 0",
+                    "set misc allowstrongdualreds FALSE",
+                    "set misc allowweakdualreds FALSE",
+                ]
+                .map(String::from),
+            );
+        }
+        commands
+    }
+
+    /// HiGHS options file lines setting the requested limits and seed.
+    pub(crate) fn highs_options(&self) -> Vec<String> {
+        let mut options = vec![];
+        if let Some(time_limit) = self.time_limit {
+            options.push(format!("time_limit = {}", time_limit.as_secs_f64()));
+        }
+        if let Some(gap) = self.gap {
+            options.push(format!("mip_rel_gap = {}", gap));
+        }
+        if let Some(node_limit) = self.node_limit {
+            options.push(format!("mip_max_nodes = {}", node_limit));
+        }
+        if let Some(threads) = self.thread_count() {
+            options.push(format!("threads = {}", threads));
+        }
+        if let Some(seed) = self.random_seed {
+            options.push(format!("random_seed = {}", seed));
+        }
+        options
+    }
+
+    /// CBC command-line arguments setting the requested limits and seed.
+    pub(crate) fn cbc_arguments(&self) -> Vec<String> {
+        let mut arguments = vec![];
+        if let Some(time_limit) = self.time_limit {
+            arguments.extend(["seconds".to_string(), time_limit.as_secs_f64().to_string()]);
+        }
+        if let Some(gap) = self.gap {
+            arguments.extend(["ratioGap".to_string(), gap.to_string()]);
+        }
+        if let Some(node_limit) = self.node_limit {
+            arguments.extend(["maxNodes".to_string(), node_limit.to_string()]);
+        }
+        if let Some(threads) = self.thread_count() {
+            arguments.extend(["threads".to_string(), threads.to_string()]);
+        }
+        if let Some(seed) = self.random_seed {
+            arguments.extend(["randomCbcSeed".to_string(), seed.to_string()]);
+        }
+        arguments
+    }
+
+    /// glpsol command-line arguments setting the requested limits and seed.
+    /// GLPK has no node limit and runs on one thread, so those are ignored.
+    pub(crate) fn glpk_arguments(&self) -> Vec<String> {
+        let mut arguments = vec![];
+        if let Some(time_limit) = self.time_limit {
+            // whole seconds only
+            let seconds = time_limit.as_secs() + u64::from(time_limit.subsec_nanos() > 0);
+            arguments.extend(["--tmlim".to_string(), seconds.to_string()]);
+        }
+        if let Some(gap) = self.gap {
+            arguments.extend(["--mipgap".to_string(), gap.to_string()]);
+        }
+        if let Some(seed) = self.random_seed {
+            arguments.extend(["--seed".to_string(), seed.to_string()]);
+        }
+        arguments
+    }
+
+    /// CPLEX interactive optimizer commands setting the requested limits and
+    /// seed.
+    pub(crate) fn cplex_commands(&self) -> Vec<String> {
+        let mut commands = vec![];
+        if let Some(time_limit) = self.time_limit {
+            commands.push(format!("set timelimit {}", time_limit.as_secs_f64()));
+        }
+        if let Some(gap) = self.gap {
+            commands.push(format!("set mip tolerances mipgap {}", gap));
+        }
+        if let Some(node_limit) = self.node_limit {
+            commands.push(format!("set mip limits nodes {}", node_limit));
+        }
+        if let Some(threads) = self.thread_count() {
+            commands.push(format!("set threads {}", threads));
+        }
+        if let Some(seed) = self.random_seed {
+            commands.push(format!("set randomseed {}", seed));
+        }
+        commands
+    }
+
+    /// `gurobi_cl` parameter arguments setting the requested limits and seed.
+    pub(crate) fn gurobi_arguments(&self) -> Vec<String> {
+        let mut arguments = vec![];
+        if let Some(time_limit) = self.time_limit {
+            arguments.push(format!("TimeLimit={}", time_limit.as_secs_f64()));
+        }
+        if let Some(gap) = self.gap {
+            arguments.push(format!("MIPGap={}", gap));
+        }
+        if let Some(node_limit) = self.node_limit {
+            arguments.push(format!("NodeLimit={}", node_limit));
+        }
+        if let Some(threads) = self.thread_count() {
+            arguments.push(format!("Threads={}", threads));
+        }
+        if let Some(seed) = self.random_seed {
+            arguments.push(format!("Seed={}", seed));
+        }
+        arguments
+    }
+}
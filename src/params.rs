@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use crate::{error::SolveError, options::Solver};
+
+/**
+ * Search emphasis of SCIP, set with `Param::Emphasis`
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Emphasis {
+    Default,
+    /// Find feasible solutions quickly.
+    Feasibility,
+    /// Prove optimality quickly.
+    Optimality,
+    /// Settings for easy instances.
+    EasyCip,
+    /// Settings for instances with hard LP relaxations.
+    HardLp,
+}
+
+/**
+ * How much effort goes into primal heuristics, set with `Param::Heuristics`
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Aggressiveness {
+    Default,
+    Aggressive,
+    Fast,
+    Off,
+}
+
+/**
+ * Solver parameter set with `Model::set_param`, translated by each solver
+ * into its own syntax
+ */
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Param {
+    TimeLimit(Duration),
+    /// Relative gap between primal and dual bound at which to stop.
+    Gap(f64),
+    NodeLimit(u64),
+    Threads(u32),
+    RandomSeed(u64),
+    Presolve(bool),
+    Emphasis(Emphasis),
+    Heuristics(Aggressiveness),
+}
+
+impl Param {
+    /// Position of the parameter in the order parameters are applied in.
+    /// Setting a parameter replaces the one with the same rank.
+    pub(crate) fn rank(&self) -> usize {
+        match self {
+            // emphasis settings reset other parameters, so they come first
+            // and the limits, presolving and heuristics set after them stay
+            Param::Emphasis(_) => 0,
+            Param::TimeLimit(_) => 1,
+            Param::Gap(_) => 2,
+            Param::NodeLimit(_) => 3,
+            Param::Threads(_) => 4,
+            Param::RandomSeed(_) => 5,
+            Param::Presolve(_) => 6,
+            Param::Heuristics(_) => 7,
+        }
+    }
+
+    /// SCIP shell command setting the parameter.
+    pub(crate) fn scip_command(&self) -> String {
+        match self {
+            Param::TimeLimit(time_limit) => {
+                format!("set limits time {}", time_limit.as_secs_f64())
+            }
+            Param::Gap(gap) => format!("set limits gap {}", gap),
+            Param::NodeLimit(nodes) => format!("set limits nodes {}", nodes),
+            Param::Threads(threads) => format!("set parallel maxnthreads {}", threads),
+            Param::RandomSeed(seed) => format!("set randomization randomseedshift {}", seed),
+            Param::Presolve(on) => format!(
+                "set presolving emphasis {}",
+                if *on { "default" } else { "off" }
+            ),
+            Param::Emphasis(emphasis) => format!(
+                "set emphasis {}",
+                match emphasis {
+                    Emphasis::Default => "default",
+                    Emphasis::Feasibility => "feasibility",
+                    Emphasis::Optimality => "optimality",
+                    Emphasis::EasyCip => "easycip",
+                    Emphasis::HardLp => "hardlp",
+                }
+            ),
+            Param::Heuristics(aggressiveness) => format!(
+                "set heuristics emphasis {}",
+                match aggressiveness {
+                    Aggressiveness::Default => "default",
+                    Aggressiveness::Aggressive => "aggressive",
+                    Aggressiveness::Fast => "fast",
+                    Aggressiveness::Off => "off",
+                }
+            ),
+        }
+    }
+
+    /// HiGHS options file line setting the parameter.
+    pub(crate) fn highs_option(&self) -> Result<String, SolveError> {
+        Ok(match self {
+            Param::TimeLimit(time_limit) => format!("time_limit = {}", time_limit.as_secs_f64()),
+            Param::Gap(gap) => format!("mip_rel_gap = {}", gap),
+            Param::NodeLimit(nodes) => format!("mip_max_nodes = {}", nodes),
+            Param::Threads(threads) => format!("threads = {}", threads),
+            Param::RandomSeed(seed) => format!("random_seed = {}", seed),
+            Param::Presolve(on) => format!("presolve = {}", if *on { "on" } else { "off" }),
+            Param::Emphasis(_) | Param::Heuristics(_) => {
+                return Err(self.unsupported(Solver::Highs))
+            }
+        })
+    }
+
+    /// CBC command-line arguments setting the parameter.
+    pub(crate) fn cbc_arguments(&self) -> Result<[String; 2], SolveError> {
+        let (name, value) = match self {
+            Param::TimeLimit(time_limit) => ("seconds", time_limit.as_secs_f64().to_string()),
+            Param::Gap(gap) => ("ratioGap", gap.to_string()),
+            Param::NodeLimit(nodes) => ("maxNodes", nodes.to_string()),
+            Param::Threads(threads) => ("threads", threads.to_string()),
+            Param::RandomSeed(seed) => ("randomCbcSeed", seed.to_string()),
+            Param::Presolve(on) => ("presolve", if *on { "on" } else { "off" }.to_string()),
+            Param::Heuristics(Aggressiveness::Default) => ("heuristicsOnOff", "on".to_string()),
+            Param::Heuristics(Aggressiveness::Off) => ("heuristicsOnOff", "off".to_string()),
+            Param::Emphasis(_) | Param::Heuristics(_) => return Err(self.unsupported(Solver::Cbc)),
+        };
+        Ok([name.to_string(), value])
+    }
+
+    /// glpsol command-line arguments setting the parameter.
+    pub(crate) fn glpk_arguments(&self) -> Result<[String; 2], SolveError> {
+        let (name, value) = match self {
+            Param::TimeLimit(time_limit) => (
+                "--tmlim",
+                // whole seconds only
+                (time_limit.as_secs() + u64::from(time_limit.subsec_nanos() > 0)).to_string(),
+            ),
+            Param::Gap(gap) => ("--mipgap", gap.to_string()),
+            Param::RandomSeed(seed) => ("--seed", seed.to_string()),
+            Param::NodeLimit(_)
+            | Param::Threads(_)
+            | Param::Presolve(_)
+            | Param::Emphasis(_)
+            | Param::Heuristics(_) => return Err(self.unsupported(Solver::Glpk)),
+        };
+        Ok([name.to_string(), value])
+    }
+
+    /// CPLEX interactive optimizer command setting the parameter.
+    pub(crate) fn cplex_command(&self) -> Result<String, SolveError> {
+        Ok(match self {
+            Param::TimeLimit(time_limit) => format!("set timelimit {}", time_limit.as_secs_f64()),
+            Param::Gap(gap) => format!("set mip tolerances mipgap {}", gap),
+            Param::NodeLimit(nodes) => format!("set mip limits nodes {}", nodes),
+            Param::Threads(threads) => format!("set threads {}", threads),
+            Param::RandomSeed(seed) => format!("set randomseed {}", seed),
+            Param::Presolve(on) => {
+                format!("set preprocessing presolve {}", if *on { "y" } else { "n" })
+            }
+            Param::Emphasis(Emphasis::Default) => "set emphasis mip 0".to_string(),
+            Param::Emphasis(Emphasis::Feasibility) => "set emphasis mip 1".to_string(),
+            Param::Emphasis(Emphasis::Optimality) => "set emphasis mip 2".to_string(),
+            Param::Heuristics(Aggressiveness::Default) => {
+                "set mip strategy heuristicfreq 0".to_string()
+            }
+            Param::Heuristics(Aggressiveness::Off) => {
+                "set mip strategy heuristicfreq -1".to_string()
+            }
+            Param::Emphasis(_) | Param::Heuristics(_) => {
+                return Err(self.unsupported(Solver::Cplex))
+            }
+        })
+    }
+
+    /// `gurobi_cl` argument setting the parameter.
+    pub(crate) fn gurobi_argument(&self) -> Result<String, SolveError> {
+        Ok(match self {
+            Param::TimeLimit(time_limit) => format!("TimeLimit={}", time_limit.as_secs_f64()),
+            Param::Gap(gap) => format!("MIPGap={}", gap),
+            Param::NodeLimit(nodes) => format!("NodeLimit={}", nodes),
+            Param::Threads(threads) => format!("Threads={}", threads),
+            Param::RandomSeed(seed) => format!("Seed={}", seed),
+            Param::Presolve(on) => format!("Presolve={}", if *on { -1 } else { 0 }),
+            Param::Emphasis(Emphasis::Default) => "MIPFocus=0".to_string(),
+            Param::Emphasis(Emphasis::Feasibility) => "MIPFocus=1".to_string(),
+            Param::Emphasis(Emphasis::Optimality) => "MIPFocus=2".to_string(),
+            // fraction of the time spent in heuristics, 0.05 by default
+            Param::Heuristics(Aggressiveness::Default) => "Heuristics=0.05".to_string(),
+            Param::Heuristics(Aggressiveness::Aggressive) => "Heuristics=0.5".to_string(),
+            Param::Heuristics(Aggressiveness::Off) => "Heuristics=0".to_string(),
+            Param::Emphasis(_) | Param::Heuristics(_) => {
+                return Err(self.unsupported(Solver::Gurobi))
+            }
+        })
+    }
+
+    fn unsupported(&self, solver: Solver) -> SolveError {
+        SolveError::UnsupportedParameter {
+            solver,
+            param: format!("{:?}", self),
+        }
+    }
+}
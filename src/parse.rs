@@ -0,0 +1,472 @@
+use std::fmt::Display;
+
+use num::{BigRational, Num};
+
+use crate::{
+    constraint::Constraint,
+    error::ParseError,
+    expression::Expression,
+    solution::{parse_f64, parse_rational},
+    variable::Variable,
+    Model, LP_NAME_SYMBOLS,
+};
+
+/**
+ * What `Model::parse_expr_with` and `Model::parse_constraint_with` do with
+ * names of variables the model does not have
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownNames {
+    /// Fail with `ParseError::UnknownVariable`.
+    #[default]
+    Reject,
+    /// Add a continuous variable without bounds by that name.
+    Create,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(&'a str),
+    Name(&'a str),
+    Plus,
+    Minus,
+    Times,
+    Le,
+    Ge,
+    Eq,
+}
+
+impl Token<'_> {
+    fn is_relation(&self) -> bool {
+        matches!(self, Token::Le | Token::Ge | Token::Eq)
+    }
+}
+
+/// Splits `s` into tokens with their byte offsets.
+fn tokenize(s: &str) -> Result<Vec<(usize, Token<'_>)>, ParseError> {
+    let bytes = s.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let c = s[i..].chars().next().expect("not at the end");
+        let token = if c.is_whitespace() {
+            i += c.len_utf8();
+            continue;
+        } else if c.is_ascii_digit() || c == '.' {
+            i = number_end(bytes, i);
+            Token::Number(&s[start..i])
+        } else if c.is_ascii_alphabetic() || LP_NAME_SYMBOLS.contains(c) {
+            i += bytes[i..]
+                .iter()
+                .position(|&b| !(b.is_ascii_alphanumeric() || LP_NAME_SYMBOLS.contains(b as char)))
+                .unwrap_or(bytes.len() - i);
+            Token::Name(&s[start..i])
+        } else {
+            let (token, len) = match &s[i..] {
+                r if r.starts_with("<=") => (Token::Le, 2),
+                r if r.starts_with(">=") => (Token::Ge, 2),
+                r if r.starts_with('=') => (Token::Eq, 1),
+                r if r.starts_with('+') => (Token::Plus, 1),
+                r if r.starts_with('-') => (Token::Minus, 1),
+                r if r.starts_with('*') => (Token::Times, 1),
+                _ => {
+                    return Err(ParseError::UnexpectedToken {
+                        position: start,
+                        token: c.to_string(),
+                    })
+                }
+            };
+            i += len;
+            token
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+/// End of the number starting at `i`: digits and dots, an optional exponent
+/// and an optional `/` followed by a denominator. A letter `e` without digits
+/// after it is left for a variable name, as in `2e`.
+fn number_end(bytes: &[u8], mut i: usize) -> usize {
+    let digits = |i: usize| {
+        i + bytes[i..]
+            .iter()
+            .position(|b| !(b.is_ascii_digit() || *b == b'.'))
+            .unwrap_or(bytes.len() - i)
+    };
+    i = digits(i);
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'+' | b'-')));
+        if bytes.get(i + 1 + sign).is_some_and(u8::is_ascii_digit) {
+            i = digits(i + 1 + sign);
+        }
+    }
+    if bytes.get(i) == Some(&b'/') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        i = digits(i + 1);
+    }
+    i
+}
+
+/// Error for `token` appearing where it does not fit.
+fn unexpected(position: usize, token: Token) -> ParseError {
+    let token = match token {
+        Token::Number(s) | Token::Name(s) => s,
+        Token::Plus => "+",
+        Token::Minus => "-",
+        Token::Times => "*",
+        Token::Le => "<=",
+        Token::Ge => ">=",
+        Token::Eq => "=",
+    };
+    ParseError::UnexpectedToken {
+        position,
+        token: token.to_string(),
+    }
+}
+
+/// Reads linear expressions over the variables of a model.
+struct ExprParser<'m, 's, N, F>
+where
+    N: Num + Clone,
+{
+    model: &'m mut Model<N>,
+    parse: F,
+    unknown: UnknownNames,
+    tokens: std::iter::Peekable<std::vec::IntoIter<(usize, Token<'s>)>>,
+}
+
+impl<'s, N, F> ExprParser<'_, 's, N, F>
+where
+    N: Num + Clone + Display + PartialOrd,
+    F: Fn(&str) -> Option<N>,
+{
+    /// Parses terms such as `2 x - 3/4 y + 1` up to a relation or the end.
+    fn expression(&mut self) -> Result<Expression<N>, ParseError> {
+        let mut terms = vec![];
+        loop {
+            let negative = match self.tokens.peek() {
+                Some((_, Token::Plus)) => false,
+                Some((_, Token::Minus)) => true,
+                // only the first term can do without a sign
+                Some((position, token)) if !terms.is_empty() => {
+                    return match token {
+                        t if t.is_relation() => Ok(Expression(terms)),
+                        _ => Err(unexpected(*position, *token)),
+                    }
+                }
+                Some(_) => false,
+                None if terms.is_empty() => return Err(ParseError::UnexpectedEnd),
+                None => return Ok(Expression(terms)),
+            };
+            if matches!(self.tokens.peek(), Some((_, Token::Plus | Token::Minus))) {
+                self.tokens.next();
+            }
+            let (coefficient, variable) = self.term()?;
+            let coefficient = if negative {
+                N::zero() - coefficient
+            } else {
+                coefficient
+            };
+            terms.push((coefficient, variable));
+        }
+    }
+
+    /// Parses `3`, `x`, `3 x` or `3 * x`.
+    fn term(&mut self) -> Result<(N, Option<Variable<N>>), ParseError> {
+        match self.tokens.next() {
+            Some((position, Token::Number(s))) => {
+                let coefficient = (self.parse)(s).ok_or_else(|| ParseError::InvalidNumber {
+                    position,
+                    token: s.to_string(),
+                })?;
+                let times = self.tokens.next_if(|(_, t)| *t == Token::Times);
+                match self.tokens.peek() {
+                    Some(&(position, Token::Name(name))) => {
+                        self.tokens.next();
+                        Ok((coefficient, Some(self.variable(position, name)?)))
+                    }
+                    Some(&(position, token)) if times.is_some() => Err(unexpected(position, token)),
+                    None if times.is_some() => Err(ParseError::UnexpectedEnd),
+                    _ => Ok((coefficient, None)),
+                }
+            }
+            Some((position, Token::Name(name))) => {
+                Ok((N::one(), Some(self.variable(position, name)?)))
+            }
+            Some((position, token)) => Err(unexpected(position, token)),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    /// The variable called `name`, created if unknown and allowed.
+    fn variable(&mut self, position: usize, name: &str) -> Result<Variable<N>, ParseError> {
+        if let Some((i, v)) = self
+            .model
+            .live_variables()
+            .find(|(i, _)| self.model.variable_name(*i) == name)
+        {
+            return Ok(Variable::new(i, v.name.clone()));
+        }
+        match self.unknown {
+            UnknownNames::Reject => Err(ParseError::UnknownVariable {
+                position,
+                name: name.to_string(),
+            }),
+            UnknownNames::Create => {
+                self.model
+                    .add_var()
+                    .name(name)
+                    .free()
+                    .try_build()
+                    .map_err(|_| ParseError::InvalidName {
+                        position,
+                        name: name.to_string(),
+                    })
+            }
+        }
+    }
+
+    /// Parses the whole input as an expression.
+    fn whole_expression(mut self) -> Result<Expression<N>, ParseError> {
+        let expression = self.expression()?;
+        match self.tokens.next() {
+            Some((position, token)) => Err(unexpected(position, token)),
+            None => Ok(expression),
+        }
+    }
+
+    /// Parses the whole input as `expression relation expression`.
+    fn constraint(mut self) -> Result<Constraint<N>, ParseError> {
+        let lhs = self.expression()?;
+        let relation = match self.tokens.next() {
+            Some((_, token)) => token,
+            None => return Err(ParseError::MissingRelation),
+        };
+        let rhs = self.whole_expression()?;
+        Ok(match relation {
+            Token::Le => lhs.le(rhs),
+            Token::Ge => lhs.ge(rhs),
+            _ => lhs.eq(rhs),
+        })
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    fn expr_parser<'m, 's, F>(
+        &'m mut self,
+        s: &'s str,
+        parse: F,
+        unknown: UnknownNames,
+    ) -> Result<ExprParser<'m, 's, N, F>, ParseError> {
+        Ok(ExprParser {
+            model: self,
+            parse,
+            unknown,
+            tokens: tokenize(s)?.into_iter().peekable(),
+        })
+    }
+}
+
+impl Model<BigRational> {
+    /// Parses a linear expression such as `2 x - 3/4 y + 1`, with variables
+    /// given by name. Coefficients can be integers, fractions or decimals,
+    /// which are read exactly, and may be followed by `*`.
+    pub fn parse_expr(&mut self, s: &str) -> Result<Expression<BigRational>, ParseError> {
+        self.parse_expr_with(s, UnknownNames::Reject)
+    }
+
+    /// Like `parse_expr`, handling unknown names as `unknown` says.
+    pub fn parse_expr_with(
+        &mut self,
+        s: &str,
+        unknown: UnknownNames,
+    ) -> Result<Expression<BigRational>, ParseError> {
+        self.expr_parser(s, parse_rational, unknown)?
+            .whole_expression()
+    }
+
+    /// Parses a constraint such as `2 x + 3 y <= 10`: two expressions
+    /// joined by `<=`, `>=` or `=`, with variables allowed on both sides.
+    pub fn parse_constraint(&mut self, s: &str) -> Result<Constraint<BigRational>, ParseError> {
+        self.parse_constraint_with(s, UnknownNames::Reject)
+    }
+
+    /// Like `parse_constraint`, handling unknown names as `unknown` says.
+    pub fn parse_constraint_with(
+        &mut self,
+        s: &str,
+        unknown: UnknownNames,
+    ) -> Result<Constraint<BigRational>, ParseError> {
+        self.expr_parser(s, parse_rational, unknown)?.constraint()
+    }
+}
+
+impl Model<f64> {
+    /// Like `Model::<BigRational>::parse_expr`, rounding coefficients to the
+    /// nearest `f64`.
+    pub fn parse_expr(&mut self, s: &str) -> Result<Expression<f64>, ParseError> {
+        self.parse_expr_with(s, UnknownNames::Reject)
+    }
+
+    pub fn parse_expr_with(
+        &mut self,
+        s: &str,
+        unknown: UnknownNames,
+    ) -> Result<Expression<f64>, ParseError> {
+        self.expr_parser(s, parse_f64, unknown)?.whole_expression()
+    }
+
+    /// Like `Model::<BigRational>::parse_constraint`, rounding coefficients
+    /// to the nearest `f64`.
+    pub fn parse_constraint(&mut self, s: &str) -> Result<Constraint<f64>, ParseError> {
+        self.parse_constraint_with(s, UnknownNames::Reject)
+    }
+
+    pub fn parse_constraint_with(
+        &mut self,
+        s: &str,
+        unknown: UnknownNames,
+    ) -> Result<Constraint<f64>, ParseError> {
+        self.expr_parser(s, parse_f64, unknown)?.constraint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, ParseError, UnknownNames};
+
+    fn r(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let e = model.parse_expr("2 x - 3/4 y + 0.1 - x").unwrap();
+        assert_eq!(e.coefficient(&x), c(1).0);
+        assert_eq!(e.coefficient(&y), r(-3, 4));
+        assert_eq!(e.constant(), r(1, 10));
+        let e = model.parse_expr("-x+2*y").unwrap();
+        assert_eq!(e.to_string(), "-x + 2 y");
+        let e = model.parse_expr("1e-2x + 4").unwrap();
+        assert_eq!(e.coefficient(&x), r(1, 100));
+        assert_eq!(e.constant(), c(4).0);
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let e = model.parse_expr("1/4 x - 0.5").unwrap();
+        assert_eq!(e.coefficient(&x), 0.25);
+        assert_eq!(e.constant(), -0.5);
+    }
+
+    #[test]
+    fn test_parse_constraint() {
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("x").build();
+        model.add_var().name("y").build();
+        let constraint = model.parse_constraint("2 x + 3 <= y - 1/3").unwrap();
+        model.add_const(constraint);
+        let constraint = model.parse_constraint("x >= -y").unwrap();
+        model.add_const(constraint);
+        let constraint = model.parse_constraint("x = 1").unwrap();
+        model.add_const(constraint);
+        assert!(
+            model.to_lp_string().unwrap().contains(
+                "Subject To\n \
+                 c0: 2 x - y <= -10/3\n \
+                 c1: x + y >= 0\n \
+                 c2: x = 1\n"
+            ),
+            "{}",
+            model.to_lp_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut model = Model::<BigRational>::new();
+        model.add_var().name("x").build();
+
+        assert_eq!(
+            model.parse_expr("2 x + z").err(),
+            Some(ParseError::UnknownVariable {
+                position: 6,
+                name: "z".to_string()
+            })
+        );
+        assert_eq!(
+            model.parse_expr("2 x 3").err(),
+            Some(ParseError::UnexpectedToken {
+                position: 4,
+                token: "3".to_string()
+            })
+        );
+        assert_eq!(
+            model.parse_expr("x + 1.2.3").err(),
+            Some(ParseError::InvalidNumber {
+                position: 4,
+                token: "1.2.3".to_string()
+            })
+        );
+        assert_eq!(
+            model.parse_expr("x ^ 2").err(),
+            Some(ParseError::UnexpectedToken {
+                position: 2,
+                token: "^".to_string()
+            })
+        );
+        assert_eq!(
+            model.parse_expr("x -").err(),
+            Some(ParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            model.parse_expr("x <= 2").err(),
+            Some(ParseError::UnexpectedToken {
+                position: 2,
+                token: "<=".to_string()
+            })
+        );
+        assert_eq!(
+            model.parse_constraint("x + 2").err(),
+            Some(ParseError::MissingRelation)
+        );
+        assert_eq!(
+            model.parse_constraint("x <= 2 <= 3").err(),
+            Some(ParseError::UnexpectedToken {
+                position: 7,
+                token: "<=".to_string()
+            })
+        );
+        assert_eq!(model.num_vars(), 1);
+    }
+
+    #[test]
+    fn test_parse_create_variables() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let constraint = model
+            .parse_constraint_with("x + 2 y + y <= 4", UnknownNames::Create)
+            .unwrap();
+        assert_eq!(model.num_vars(), 2);
+        assert_eq!(model.variable_name(1), "y");
+        model.add_const(constraint);
+        let e = model.parse_expr("y - x").unwrap();
+        assert_eq!(e.coefficient(&x), c(-1).0);
+        assert_eq!(
+            model.parse_expr_with("x + end", UnknownNames::Create).err(),
+            Some(ParseError::InvalidName {
+                position: 4,
+                name: "end".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Everything needed to build and solve a model, with the number types from
+//! `num`:
+//!
+//! ```no_run
+//! use exact_lp::prelude::*;
+//!
+//! let mut model = Model::<BigRational>::new();
+//! let x = model.add_var().name("x").lb(rat(0)).build();
+//! model.maximize();
+//! model.set_objective(&x * 2);
+//! model.add_const((&x * 3).le(rat_frac(7, 2)));
+//! let solution = model.solve_with(&SolveOptions::default()).unwrap();
+//! assert_eq!(solution.get_value(&x), rat_frac(7, 6));
+//! ```
+
+pub use num::{BigInt, BigRational};
+
+pub use crate::{
+    c, rat, rat_frac, Constraint, ConstraintId, ConstraintSense, Expression, Model, ModelError,
+    QuadExpression, Solution, SolveError, SolveOptions, SolveStatus, Solver, Variable,
+    VariableType,
+};
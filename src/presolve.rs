@@ -0,0 +1,503 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use num::{Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintId, ConstraintSense},
+    expression::Expression,
+    quadratic::QuadExpression,
+    solution::{Solution, SolveStatus},
+    variable::Variable,
+    Bound, Model, VariableType,
+};
+
+/**
+ * What `Model::presolve` changed
+ *
+ * Solutions of the presolved model leave out the substituted variables;
+ * `postsolve` puts them back so the solution fits the original model.
+ */
+#[derive(Clone, Debug)]
+pub struct PresolveReport<N>
+where
+    N: Num + Clone,
+{
+    /// Substituted variables with their name and value.
+    fixed: Vec<(Variable<N>, String, N)>,
+    removed: Vec<ConstraintId>,
+    tightened: Vec<Variable<N>>,
+    infeasible: Vec<ConstraintId>,
+}
+
+impl<N> PresolveReport<N>
+where
+    N: Num + Clone,
+{
+    /// Variables whose bounds were equal, replaced by their value, or for
+    /// `Model::prune_unused` the unused variables with the value they get.
+    pub fn fixed(&self) -> impl Iterator<Item = (&Variable<N>, &N)> {
+        self.fixed.iter().map(|(v, _, value)| (v, value))
+    }
+
+    /// Constraints removed as they always hold or became bounds.
+    pub fn removed_constraints(&self) -> &[ConstraintId] {
+        &self.removed
+    }
+
+    /// Variables whose bounds were tightened from a constraint on them
+    /// alone.
+    pub fn tightened(&self) -> &[Variable<N>] {
+        &self.tightened
+    }
+
+    /// Constraints that can never hold, left in the model.
+    pub fn infeasible_constraints(&self) -> &[ConstraintId] {
+        &self.infeasible
+    }
+
+    /// Whether a constraint was found that can never hold, so the model is
+    /// infeasible.
+    pub fn is_infeasible(&self) -> bool {
+        !self.infeasible.is_empty()
+    }
+
+    /// Adds the values of the substituted variables to `solution`, a
+    /// solution of the presolved model, unless it has no values at all.
+    pub fn postsolve(&self, solution: &mut Solution<N>) {
+        if solution.is_empty() && solution.status() != SolveStatus::Optimal {
+            return;
+        }
+        for (_, name, value) in &self.fixed {
+            solution.insert_value(name.clone(), value.clone());
+        }
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// Leaves the variables of `unused_variables` out of the model, like
+    /// substituted ones, so the ids of all variables stay valid, and drops
+    /// their terms, whose coefficients add up to zero. Solutions
+    /// of the pruned model give each the bound closest to zero, or zero if
+    /// that is within its bounds, as any value within them would do. The
+    /// returned report lists them with these values.
+    pub fn prune_unused(&mut self) -> PresolveReport<N> {
+        let mut report = PresolveReport {
+            fixed: vec![],
+            removed: vec![],
+            tightened: vec![],
+            infeasible: vec![],
+        };
+        for v in self.unused_variables() {
+            let variable = &mut self.variables[v.id()];
+            let value = match (variable.lb.finite(), variable.ub.finite()) {
+                // a semi-continuous variable can always be zero
+                _ if variable.v_type.is_semi() => N::zero(),
+                (Some(lb), _) if lb.is_positive() => lb.clone(),
+                (_, Some(ub)) if ub.is_negative() => ub.clone(),
+                _ => N::zero(),
+            };
+            variable.eliminated = true;
+            self.substitutions
+                .push((v.clone(), Expression::from(value.clone())));
+            let name = self.variable_name(v.id());
+            report.fixed.push((v, name, value));
+        }
+
+        let pruned = report
+            .fixed
+            .iter()
+            .map(|(v, _, _)| v.id())
+            .collect::<BTreeSet<_>>();
+        let is_pruned = |v: &Variable<N>| pruned.contains(&v.id());
+        let prune = |e: &mut Expression<N>| e.0.retain(|(_, v)| !v.as_ref().is_some_and(is_pruned));
+        prune(&mut self.objective);
+        for o in &mut self.objectives {
+            prune(&mut o.expression);
+        }
+        let mut quadratic = QuadExpression::new();
+        for (w, a, b) in self.full_objective_terms().quadratic_terms() {
+            if !is_pruned(a) && !is_pruned(b) {
+                quadratic.add_term(w.clone(), a, b);
+            }
+        }
+        self.quadratic_objective = quadratic.quadratic;
+        for c in self.constraints.iter_mut().flatten() {
+            prune(&mut c.constraint.lhs);
+            prune(&mut c.constraint.rhs);
+        }
+        report
+    }
+
+    /// Simplifies the model in place until nothing changes: variables with
+    /// equal bounds are replaced by their value, constraints that always
+    /// hold are removed, and a constraint on a single variable becomes
+    /// bounds of that variable, e.g. `3 x <= 12` the upper bound 4.
+    /// Constraints that can never hold are reported and kept. Indicator
+    /// constraints and their indicators, variables of a quadratic or
+    /// further objective, and bounds of binary and semi-continuous
+    /// variables are left alone. Constraints of disabled groups are
+    /// skipped, and those of other groups never become bounds, which would
+    /// outlast turning their group off. Removed constraints have no duals.
+    pub fn presolve(&mut self) -> PresolveReport<N> {
+        let mut report = PresolveReport {
+            fixed: vec![],
+            removed: vec![],
+            tightened: vec![],
+            infeasible: vec![],
+        };
+        loop {
+            let fixed = self.substitute_fixed(&mut report);
+            let reduced = self.reduce_rows(&mut report);
+            if !fixed && !reduced {
+                return report;
+            }
+        }
+    }
+
+    /// Replaces the variables whose bounds are equal by their value, all in
+    /// one pass over the constraints.
+    fn substitute_fixed(&mut self, report: &mut PresolveReport<N>) -> bool {
+        let mut kept = self
+            .live_constraints()
+            .filter_map(|(_, c)| Some(c.indicator.as_ref()?.0.id()))
+            .collect::<BTreeSet<_>>();
+        for (_, a, b) in self.full_objective_terms().quadratic_terms() {
+            kept.extend([a.id(), b.id()]);
+        }
+        for (i, _) in self.live_variables() {
+            if self.objectives.iter().any(|o| o.uses(i)) {
+                kept.insert(i);
+            }
+        }
+
+        let values = self
+            .live_variables()
+            .filter(|(i, _)| !kept.contains(i))
+            .filter_map(|(i, v)| match (v.lb.finite(), v.ub.finite()) {
+                // a semi-continuous variable can still be zero
+                (Some(lb), Some(ub)) if lb == ub && (!v.v_type.is_semi() || lb.is_zero()) => {
+                    Some((i, lb.clone()))
+                }
+                _ => None,
+            })
+            .collect::<BTreeMap<_, _>>();
+        if values.is_empty() {
+            return false;
+        }
+
+        let substitute = |e: &Expression<N>| {
+            Expression(
+                e.0.iter()
+                    .map(
+                        |(w, v)| match v.as_ref().and_then(|v| values.get(&v.id())) {
+                            Some(value) => (w.clone() * value.clone(), None),
+                            None => (w.clone(), v.clone()),
+                        },
+                    )
+                    .collect(),
+            )
+        };
+        self.objective = substitute(&self.objective);
+        for c in self.constraints.iter_mut().flatten() {
+            c.constraint = Constraint {
+                lhs: substitute(&c.constraint.lhs),
+                sense: c.constraint.sense.clone(),
+                rhs: substitute(&c.constraint.rhs),
+            };
+        }
+        for (&i, value) in &values {
+            if let Some(coefficient) = self.variables[i].obj.take() {
+                self.objective += coefficient * value.clone();
+            }
+            self.variables[i].eliminated = true;
+            let v = Variable::new(i, self.variables[i].name.clone());
+            report.fixed.push((v, self.variable_name(i), value.clone()));
+        }
+        true
+    }
+
+    /// Removes constraints that always hold and turns constraints on a
+    /// single variable into bounds, recording constraints that never hold.
+    fn reduce_rows(&mut self, report: &mut PresolveReport<N>) -> bool {
+        let mut changed = false;
+        for i in 0..self.constraints.len() {
+            let Some(c) = &self.constraints[i] else {
+                continue;
+            };
+            let disabled = c
+                .group
+                .as_ref()
+                .is_some_and(|g| self.disabled_groups.contains(g));
+            if c.indicator.is_some() || disabled {
+                continue;
+            }
+            let grouped = c.group.is_some();
+            let row = c.constraint.normalized();
+            match row.normalized_trivially_holds() {
+                Some(true) => {
+                    self.constraints[i] = None;
+                    report.removed.push(ConstraintId(i));
+                    changed = true;
+                    continue;
+                }
+                Some(false) => {
+                    if !report.infeasible.contains(&ConstraintId(i)) {
+                        report.infeasible.push(ConstraintId(i));
+                    }
+                    continue;
+                }
+                None if grouped => continue,
+                None => {}
+            }
+            let [(a, Some(x))] = &row.lhs.0[..] else {
+                continue;
+            };
+            let variable = &self.variables[x.id()];
+            if matches!(variable.v_type, VariableType::Binary) || variable.v_type.is_semi() {
+                continue;
+            }
+
+            let rhs = row.rhs.constant();
+            let (lo, hi) = match row.sense {
+                ConstraintSense::Le => (None, Some(rhs)),
+                ConstraintSense::Ge => (Some(rhs), None),
+                ConstraintSense::Eq => (Some(rhs.clone()), Some(rhs)),
+                ConstraintSense::Range { lb, ub } => (Some(lb), Some(ub)),
+            };
+            // bounds of `a x` divided by a negative `a` swap
+            let (lo, hi) = (lo.map(|b| b / a.clone()), hi.map(|b| b / a.clone()));
+            let (lo, hi) = if a.is_negative() { (hi, lo) } else { (lo, hi) };
+            let (old_lb, old_ub) = (variable.lb.finite().cloned(), variable.ub.finite().cloned());
+            let lb = match (&old_lb, lo) {
+                (Some(lb), Some(lo)) if lo > *lb => Some(lo),
+                (None, lo) => lo,
+                (lb, _) => lb.clone(),
+            };
+            let ub = match (&old_ub, hi) {
+                (Some(ub), Some(hi)) if hi < *ub => Some(hi),
+                (None, hi) => hi,
+                (ub, _) => ub.clone(),
+            };
+            if matches!((&lb, &ub), (Some(lb), Some(ub)) if lb > ub) {
+                if !report.infeasible.contains(&ConstraintId(i)) {
+                    report.infeasible.push(ConstraintId(i));
+                }
+                continue;
+            }
+            if lb != old_lb || ub != old_ub {
+                if !report.tightened.iter().any(|v| v.id() == x.id()) {
+                    report.tightened.push(x.clone());
+                }
+                let variable = &mut self.variables[x.id()];
+                variable.lb = Bound::lower(lb);
+                variable.ub = Bound::upper(ub);
+            }
+            self.constraints[i] = None;
+            report.removed.push(ConstraintId(i));
+            changed = true;
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use num::BigRational;
+
+    use crate::{c, Model, Solution, SolveOptions, SolveStatus};
+
+    #[test]
+    fn test_presolve() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(2).0).ub(c(2).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        let z = model.add_var().name("z").lb(c(0).0).obj(c(1).0).build();
+        let w = model.add_var().name("w").lb(c(0).0).ub(c(9).0).build();
+        model.maximize();
+        model.set_objective(c(3) * &x + &y + &w);
+        let fixed = model.add_const((&x + &y).le(c(5).0));
+        let singleton = model.add_const((c(3) * &z).le(c(12).0));
+        let holds = model.add_const(x.clone().ge(c(1).0));
+        let redundant = model.add_const((c(-2) * &y).ge(c(-8).0));
+        let kept = model.add_const((&y + &z + &w).le(c(10).0));
+
+        let report = model.presolve();
+        assert_eq!(
+            report
+                .fixed()
+                .map(|(v, value)| (v.id(), value.clone()))
+                .collect::<Vec<_>>(),
+            [(x.id(), c(2).0)]
+        );
+        assert_eq!(
+            report.removed_constraints(),
+            [fixed, singleton, holds, redundant]
+        );
+        assert_eq!(report.tightened(), [y.clone(), z.clone()]);
+        assert!(!report.is_infeasible());
+        assert!(model.get_const(kept).is_some());
+        assert_eq!(
+            model.var_bounds(&y).unwrap(),
+            (Some(&c(0).0), Some(&c(3).0))
+        );
+        assert_eq!(
+            model.var_bounds(&z).unwrap(),
+            (Some(&c(0).0), Some(&c(4).0))
+        );
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Maximize\n obj: y + w + z\nSubject To\n c4: y + z + w <= 10\n\
+             Bounds\n 0 <= y <= 3\n 0 <= z <= 4\n 0 <= w <= 9\nGeneral\nBinary\nEnd\n"
+        );
+
+        let values = [("y", c(3).0), ("z", c(4).0), ("w", c(3).0)]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<BTreeMap<_, _>>();
+        let mut solution = Solution::new(SolveStatus::Optimal, Some(c(16).0), values);
+        report.postsolve(&mut solution);
+        assert_eq!(solution.get_value(&x), c(2).0);
+        assert_eq!(solution.len(), 4);
+        let mut infeasible = Solution::new(SolveStatus::Infeasible, None, BTreeMap::new());
+        report.postsolve(&mut infeasible);
+        assert!(infeasible.is_empty());
+    }
+
+    #[test]
+    fn test_prune_unused() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").ub(c(4).0).build();
+        let free = model.add_var().name("free").free().build();
+        let low = model.add_var().name("low").lb(c(2).0).ub(c(5).0).build();
+        let high = model
+            .add_var()
+            .name("high")
+            .lb_neg_inf()
+            .ub(c(-3).0)
+            .build();
+        let semi = model
+            .add_var()
+            .name("semi")
+            .semi_continuous()
+            .lb(c(1).0)
+            .ub(c(3).0)
+            .build();
+        model.maximize();
+        model.set_objective(&x + &low - &low);
+        model.add_const((&x + &free - &free).le(c(10).0));
+
+        let report = model.prune_unused();
+        assert_eq!(
+            report
+                .fixed()
+                .map(|(v, value)| (v.id(), value.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (free.id(), c(0).0),
+                (low.id(), c(2).0),
+                (high.id(), c(-3).0),
+                (semi.id(), c(0).0)
+            ]
+        );
+        assert!(model.unused_variables().is_empty());
+        assert_eq!(model.num_vars(), 1);
+        assert_eq!(
+            model.to_lp_string().unwrap(),
+            "Maximize\n obj: x\nSubject To\n c0: x <= 10\n\
+             Bounds\n 0 <= x <= 4\nGeneral\nBinary\nEnd\n"
+        );
+
+        let values = [("x".to_string(), c(4).0)].into_iter().collect();
+        let mut solution = Solution::new(SolveStatus::Optimal, Some(c(4).0), values);
+        model.add_substituted_values(&mut solution);
+        assert_eq!(solution.get_value(&low), c(2).0);
+        assert_eq!(solution.get_value(&semi), c(0).0);
+        assert_eq!(solution.len(), 5);
+
+        // the free variable no longer reaches the solver, which could
+        // report it at any value
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Optimal);
+        assert_eq!(solution.get_value(&x), c(4).0);
+        assert_eq!(solution.get_value(&free), c(0).0);
+        assert_eq!(solution.get_value(&low), c(2).0);
+        assert_eq!(solution.get_value(&high), c(-3).0);
+        assert_eq!(solution.missing_lookups(), 0);
+    }
+
+    #[test]
+    fn test_presolve_infeasible() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(1.0).ub(1.0).build();
+        let y = model.add_var().name("y").lb(5.0).build();
+        let fixed = model.add_const(x.clone().le(0.5));
+        let crossing = model.add_const((&y * -1.0).ge(-3.0));
+
+        let report = model.presolve();
+        assert!(report.is_infeasible());
+        assert_eq!(report.infeasible_constraints(), [fixed, crossing]);
+        assert!(report.removed_constraints().is_empty());
+        assert_eq!(model.var_bounds(&y).unwrap(), (Some(&5.0), None));
+    }
+
+    #[test]
+    fn test_presolve_groups() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(0.0).ub(10.0).build();
+        let y = model.add_var().name("y").lb(0.0).build();
+        let bound = model.add_const((&x * 2.0).le(8.0));
+        model.begin_group("caps");
+        let capped = model.add_const(y.clone().le(3.0));
+        let holds = model.add_const((&x - &x).le(1.0));
+        model.end_group();
+        model.begin_group("off");
+        let never = model.add_const((&y - &y).ge(1.0));
+        let off = model.add_const(x.clone().le(1.0));
+        model.end_group();
+        model.set_group_enabled("off", false);
+
+        let report = model.presolve();
+        assert_eq!(report.removed_constraints(), [bound, holds]);
+        assert!(!report.is_infeasible());
+        assert_eq!(report.tightened(), std::slice::from_ref(&x));
+        assert_eq!(model.var_bounds(&x).unwrap(), (Some(&0.0), Some(&4.0)));
+        assert_eq!(model.var_bounds(&y).unwrap(), (Some(&0.0), None));
+        assert_eq!(model.group_constraints("caps"), [capped]);
+        assert_eq!(model.group_constraints("off"), [never, off]);
+
+        model.set_group_enabled("caps", false);
+        assert!(model.to_lp_string().unwrap().contains(" 0 <= y <= +inf\n"));
+    }
+
+    #[test]
+    fn test_presolve_optimum() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).integer().build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        let z = model.add_var().name("z").lb(c(1).0).ub(c(1).0).build();
+        model.maximize();
+        model.set_objective(&x + c(4) * &y + &z);
+        model.add_const((&x + c(4) * &y + &z).le(c(25).0));
+        model.add_const((c(3) * &x + &y).le(c(21).0));
+        model.add_const((&x + &y + &z).le(c(10).0));
+        model.add_const((c(2) * &y).le(c(11).0));
+
+        let original = model.solve_with(&SolveOptions::default()).unwrap();
+        let mut presolved = model.clone();
+        let report = presolved.presolve();
+        assert_eq!(report.fixed().count(), 1);
+        let mut solution = presolved.solve_with(&SolveOptions::default()).unwrap();
+        report.postsolve(&mut solution);
+        assert_eq!(solution.objective_value(), original.objective_value());
+        assert_eq!(solution.get_value(&z), c(1).0);
+        assert!(model.check_feasibility(&solution).is_empty());
+    }
+}
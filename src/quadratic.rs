@@ -0,0 +1,471 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Display,
+    ops::{Add, Mul},
+};
+
+use num::{Num, Signed};
+
+use crate::{
+    error::ModelError,
+    expression::{lp_sign, Expression},
+    variable::Variable,
+    Model,
+};
+
+/**
+ * Product of two variables times a coefficient in a `QuadExpression`
+ */
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+pub(crate) struct QuadTerm<N>
+where
+    N: Num + Clone,
+{
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::coefficient"))]
+    coefficient: N,
+    a: Variable<N>,
+    b: Variable<N>,
+}
+
+/**
+ * Linear expression plus products of two variables, such as `x * x - 6 x + 9`
+ *
+ * Multiplying two variables or expressions gives a `QuadExpression`, which
+ * can only be used through `Model::set_quadratic_objective`. Constraints and
+ * `set_objective` take linear expressions, so accidental products do not
+ * compile:
+ *
+ * ```compile_fail
+ * use exact_lp::Model;
+ *
+ * let mut model = Model::<f64>::new();
+ * let x = model.add_var().name("x").build();
+ * let y = model.add_var().name("y").build();
+ * model.add_const((&x * &y).le(1.0));
+ * ```
+ *
+ * `Expression::try_from` turns a product back into a linear expression, and
+ * fails naming the variables of any product that is left.
+ */
+#[derive(Clone)]
+pub struct QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    pub(crate) linear: Expression<N>,
+    pub(crate) quadratic: Vec<QuadTerm<N>>,
+}
+
+impl<N> QuadTerm<N>
+where
+    N: Num + Clone,
+{
+    pub(crate) fn coefficient(&self) -> &N {
+        &self.coefficient
+    }
+
+    pub(crate) fn a(&self) -> &Variable<N> {
+        &self.a
+    }
+
+    pub(crate) fn b(&self) -> &Variable<N> {
+        &self.b
+    }
+
+    /// Copy of the term multiplied by `scale`, with both variables replaced
+    /// by `f` of them.
+    pub(crate) fn map_variables(
+        &self,
+        scale: N,
+        f: &impl Fn(&Variable<N>) -> Variable<N>,
+    ) -> QuadTerm<N> {
+        QuadTerm {
+            coefficient: self.coefficient.clone() * scale,
+            a: f(&self.a),
+            b: f(&self.b),
+        }
+    }
+
+    /// Copy of the term with the coefficient converted by `f`.
+    pub(crate) fn map_coefficients<M>(&self, f: &impl Fn(&N) -> M) -> QuadTerm<M>
+    where
+        M: Num + Clone,
+    {
+        QuadTerm {
+            coefficient: f(&self.coefficient),
+            a: self.a.cast(),
+            b: self.b.cast(),
+        }
+    }
+}
+
+impl<N> QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `coefficient * a * b`.
+    pub fn add_term(&mut self, coefficient: N, a: &Variable<N>, b: &Variable<N>) {
+        self.quadratic.push(QuadTerm {
+            coefficient,
+            a: a.clone(),
+            b: b.clone(),
+        });
+    }
+
+    /// The terms of degree zero and one.
+    pub fn linear(&self) -> &Expression<N> {
+        &self.linear
+    }
+
+    /// The terms of degree two, as coefficient and the two variables.
+    pub fn quadratic_terms(&self) -> impl Iterator<Item = (&N, &Variable<N>, &Variable<N>)> {
+        self.quadratic.iter().map(|t| (&t.coefficient, &t.a, &t.b))
+    }
+
+    /// Canonicalizes the linear part, see `Expression::canonicalize`, and
+    /// merges the products of the same two variables, dropping zero ones
+    /// and ordering the rest by the ids of their variables.
+    pub fn canonicalize(&mut self) {
+        self.linear.canonicalize();
+        let mut products = BTreeMap::<(usize, usize), QuadTerm<N>>::new();
+        for mut term in std::mem::take(&mut self.quadratic) {
+            if term.b.id() < term.a.id() {
+                std::mem::swap(&mut term.a, &mut term.b);
+            }
+            match products.entry((term.a.id(), term.b.id())) {
+                Entry::Occupied(mut e) => {
+                    let sum = &mut e.get_mut().coefficient;
+                    *sum = std::mem::replace(sum, N::zero()) + term.coefficient;
+                }
+                Entry::Vacant(e) => {
+                    e.insert(term);
+                }
+            }
+        }
+        self.quadratic = products
+            .into_values()
+            .filter(|t| !t.coefficient.is_zero())
+            .collect();
+    }
+
+    /// Whether every coefficient is zero.
+    pub(crate) fn is_zero(&self) -> bool {
+        self.linear.0.iter().all(|(w, _)| w.is_zero())
+            && self.quadratic.iter().all(|t| t.coefficient.is_zero())
+    }
+}
+
+impl<N> QuadExpression<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// The terms as `Display` writes them, with the brackets around the
+    /// products as terms of their own, e.g. `2 x`, `+ [`, `4 x ^2`,
+    /// `+ 2 x * y` and `] / 2`.
+    pub(crate) fn lp_terms(&self) -> Vec<String> {
+        let terms = self
+            .quadratic
+            .iter()
+            .filter(|t| !t.coefficient.is_zero())
+            .collect::<Vec<_>>();
+        let has_linear = self.linear.0.iter().any(|(w, _)| !w.is_zero());
+        let mut tokens = if has_linear || terms.is_empty() {
+            self.linear.lp_terms()
+        } else {
+            vec![]
+        };
+        if terms.is_empty() {
+            return tokens;
+        }
+        tokens.push(if has_linear { "+ [" } else { "[" }.to_string());
+        for (i, t) in terms.into_iter().enumerate() {
+            let doubled = t.coefficient.clone() + t.coefficient.clone();
+            let sign = lp_sign(i, &doubled);
+            let magnitude = doubled.abs();
+            let magnitude = if magnitude.is_one() {
+                String::new()
+            } else {
+                format!("{:.64} ", magnitude)
+            };
+            tokens.push(if t.a.id() == t.b.id() {
+                format!("{sign}{magnitude}{} ^2", t.a)
+            } else {
+                format!("{sign}{magnitude}{} * {}", t.a, t.b)
+            });
+        }
+        tokens.push("] / 2".to_string());
+        tokens
+    }
+
+    /// The expression as a linear one, failing with the first product whose
+    /// coefficient is not zero.
+    pub fn into_linear(self) -> Result<Expression<N>, ModelError> {
+        match self.quadratic.iter().find(|t| !t.coefficient.is_zero()) {
+            Some(t) => Err(ModelError::Nonlinear(format!("{} * {}", t.a, t.b))),
+            None => Ok(self.linear),
+        }
+    }
+}
+
+impl<N> TryFrom<QuadExpression<N>> for Expression<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    type Error = ModelError;
+    fn try_from(expr: QuadExpression<N>) -> Result<Self, Self::Error> {
+        expr.into_linear()
+    }
+}
+
+impl<N> Default for QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    fn default() -> Self {
+        Self {
+            linear: Expression::default(),
+            quadratic: vec![],
+        }
+    }
+}
+
+impl<N> From<Expression<N>> for QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    fn from(linear: Expression<N>) -> Self {
+        Self {
+            linear,
+            quadratic: vec![],
+        }
+    }
+}
+
+impl<N> Add<Expression<N>> for QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn add(mut self, rhs: Expression<N>) -> Self::Output {
+        self.linear += rhs;
+        self
+    }
+}
+
+impl<N> Add<QuadExpression<N>> for QuadExpression<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn add(mut self, rhs: QuadExpression<N>) -> Self::Output {
+        self.linear += rhs.linear;
+        self.quadratic.extend(rhs.quadratic);
+        self
+    }
+}
+
+impl<N> Mul<Expression<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: Expression<N>) -> Self::Output {
+        let mut product = QuadExpression::new();
+        for (w, v) in &self.0 {
+            for (u, y) in &rhs.0 {
+                let coefficient = w.clone() * u.clone();
+                match (v, y) {
+                    (Some(v), Some(y)) => product.add_term(coefficient, v, y),
+                    (Some(v), None) | (None, Some(v)) => {
+                        product.linear.0.push((coefficient, Some(v.clone())))
+                    }
+                    (None, None) => product.linear.0.push((coefficient, None)),
+                }
+            }
+        }
+        product.linear.simplify();
+        product
+    }
+}
+
+impl<N> Mul<Variable<N>> for Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: Variable<N>) -> Self::Output {
+        self * Expression::from(rhs)
+    }
+}
+
+impl<N> Mul<Expression<N>> for Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: Expression<N>) -> Self::Output {
+        Expression::from(self) * rhs
+    }
+}
+
+impl<N> Mul<Variable<N>> for Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: Variable<N>) -> Self::Output {
+        Expression::from(self) * Expression::from(rhs)
+    }
+}
+
+impl<N> Mul<&Variable<N>> for &Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: &Variable<N>) -> Self::Output {
+        Expression::from(self) * Expression::from(rhs)
+    }
+}
+
+impl<N> Mul<&Expression<N>> for &Expression<N>
+where
+    N: Num + Clone,
+{
+    type Output = QuadExpression<N>;
+    fn mul(self, rhs: &Expression<N>) -> Self::Output {
+        self.clone() * rhs.clone()
+    }
+}
+
+impl<N> Display for QuadExpression<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Writes the expression in LP syntax, with the products in brackets
+    /// and doubled as the format halves them, e.g.
+    /// `2 x + [ 4 x ^2 + 2 x * y ] / 2` for `2 x + 2 x^2 + x y`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lp_terms().join(" "))
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Sets a quadratic objective, which replaces the current objective
+    /// like `set_objective`. SCIP and HiGHS solve quadratic objectives in
+    /// floating point; exact solves, CBC and multi-objective solves reject
+    /// them.
+    pub fn set_quadratic_objective(&mut self, obj: QuadExpression<N>) {
+        self.objective = obj.linear;
+        self.quadratic_objective = obj.quadratic;
+    }
+
+    pub(crate) fn has_quadratic_objective(&self) -> bool {
+        !self.quadratic_objective.is_empty()
+    }
+
+    /// The objective without its constant term, with the products of a
+    /// quadratic objective.
+    pub(crate) fn full_objective_terms(&self) -> QuadExpression<N> {
+        QuadExpression {
+            linear: self.objective_terms(),
+            quadratic: self.quadratic_objective.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use super::QuadExpression;
+    use crate::{c, Expression, Model, ModelError, SolveError, SolveOptions};
+
+    #[test]
+    fn test_quadratic_export() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let mut objective = QuadExpression::from(c(2) * &x);
+        objective.add_term(c(2).0, &x, &x);
+        objective.add_term(c(1).0, &x, &y);
+        model.set_quadratic_objective(objective);
+        let lp = model.to_lp_string().unwrap();
+        assert!(
+            lp.contains(" obj: 2 x + [ 4 x ^2 + 2 x * y ] / 2\n"),
+            "{lp}"
+        );
+        assert!(model.export_mps(&mut vec![]).is_err());
+        assert!(matches!(
+            model.solve_with(&SolveOptions::default()),
+            Err(SolveError::UnsupportedQuadraticObjective(_))
+        ));
+
+        let square = (&x - c(3).0) * (&x - c(3).0);
+        assert_eq!(square.to_string(), "-6 x + 9 + [ 2 x ^2 ] / 2");
+        model.set_quadratic_objective(square + Expression::from(&y));
+        assert_eq!(
+            model.to_string().lines().next(),
+            Some("minimize -6 x + y + 9 + [ 2 x ^2 ] / 2")
+        );
+        model.set_objective((&y).into());
+        assert!(model.to_lp_string().unwrap().contains(" obj: y\n"));
+
+        let mut only_products = QuadExpression::new();
+        only_products.add_term(c(-1).0, &x, &y);
+        assert_eq!(only_products.to_string(), "[ -2 x * y ] / 2");
+    }
+
+    #[test]
+    fn test_products() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let product = &x * &y;
+        assert_eq!(product.to_string(), "[ 2 x * y ] / 2");
+        assert_eq!(
+            Expression::try_from(product).err(),
+            Some(ModelError::Nonlinear("x * y".to_string()))
+        );
+        assert_eq!(
+            ModelError::Nonlinear("x * y".to_string()).to_string(),
+            "`x * y` is not linear"
+        );
+
+        let scaled = (c(2) * &x + c(1).0) * Expression::from(c(3).0);
+        let linear = Expression::try_from(scaled).unwrap();
+        model.add_const(linear.le(c(9).0));
+        assert!(model.to_lp_string().unwrap().contains(" c0: 6 x <= 6\n"));
+
+        let mut cancelled = x.clone() * y.clone();
+        cancelled.add_term(c(-1).0, &x, &y);
+        assert!(cancelled.into_linear().is_err());
+        let mut zero = QuadExpression::from(Expression::from(&x));
+        zero.add_term(c(0).0, &x, &y);
+        assert_eq!(zero.into_linear().unwrap().to_string(), "x");
+        assert_eq!((x.clone() * (&y + c(1).0)).quadratic_terms().count(), 1);
+    }
+
+    #[test]
+    fn test_quadratic_objective() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").lb(0.0).ub(10.0).build();
+        model.set_quadratic_objective((&x - 3.0) * (&x - 3.0));
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert!((solution.get_value(&x) - 3.0).abs() < 1e-6);
+        assert!(solution.objective_value().unwrap().abs() < 1e-6);
+    }
+}
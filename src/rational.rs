@@ -0,0 +1,133 @@
+use num::{rational::Ratio, BigInt, BigRational, Num};
+
+use crate::{
+    backend::{Backend, ScipBackend},
+    error::SolveError,
+    options::{DebugArtifacts, SolveOptions},
+    solution::Solution,
+    InternalConstraint, InternalVariable, Model,
+};
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Copy of the model with every coefficient, bound and start value
+    /// converted by `f`.
+    pub(crate) fn map_coefficients<M>(&self, f: &impl Fn(&N) -> M) -> Model<M>
+    where
+        M: Num + Clone,
+    {
+        Model {
+            solver_paths: self.solver_paths.clone(),
+            solver_args: self.solver_args.clone(),
+            commands: self.commands.clone(),
+            params: self.params.clone(),
+            objective: self.objective.map_coefficients(f),
+            direction: self.direction,
+            quadratic_objective: self
+                .quadratic_objective
+                .iter()
+                .map(|t| t.map_coefficients(f))
+                .collect(),
+            objectives: self
+                .objectives
+                .iter()
+                .map(|o| o.map_coefficients(f))
+                .collect(),
+            variables: self
+                .variables
+                .iter()
+                .map(|v| InternalVariable {
+                    v_type: v.v_type,
+                    name: v.name.clone(),
+                    lb: v.lb.as_ref().map(f),
+                    ub: v.ub.as_ref().map(f),
+                    eliminated: v.eliminated,
+                    obj: v.obj.as_ref().map(f),
+                })
+                .collect(),
+            variable_names: self.variable_names.clone(),
+            constraints: self
+                .constraints
+                .iter()
+                .map(|c| {
+                    c.as_ref().map(|c| InternalConstraint {
+                        name: c.name.clone(),
+                        constraint: c.constraint.map_coefficients(f),
+                        indicator: c.indicator.as_ref().map(|(y, active)| (y.cast(), *active)),
+                        group: c.group.clone(),
+                    })
+                })
+                .collect(),
+            start: self.start.as_ref().map(|start| {
+                start
+                    .iter()
+                    .map(|(name, value)| (name.clone(), f(value)))
+                    .collect()
+            }),
+            auxiliaries: self.auxiliaries,
+            exact: self.exact,
+            current_group: self.current_group.clone(),
+            disabled_groups: self.disabled_groups.clone(),
+            substitutions: self
+                .substitutions
+                .iter()
+                .map(|(v, e)| (v.cast(), e.map_coefficients(f)))
+                .collect(),
+            cancellation: self.cancellation.clone(),
+        }
+    }
+}
+
+/// Solves of models over rationals of a fixed-width integer type, which SCIP
+/// solves exactly as a `BigRational` copy of the model. Only converting the
+/// solution back is checked: arithmetic on the coefficients while building
+/// the model overflows like the integer type does, panicking in debug builds.
+macro_rules! impl_fixed_width_rational {
+    ($($int:ty),*) => {$(
+        impl Backend<Ratio<$int>> for ScipBackend {
+            fn solve(
+                &self,
+                model: &Model<Ratio<$int>>,
+                opts: &SolveOptions,
+            ) -> Result<Solution<Ratio<$int>>, SolveError> {
+                let exact = model.map_coefficients(&|w| {
+                    BigRational::new(BigInt::from(*w.numer()), BigInt::from(*w.denom()))
+                });
+                exact
+                    .solve_using(self, opts)?
+                    .try_map(|w| {
+                        let numer = <$int>::try_from(w.numer()).ok()?;
+                        Some(Ratio::new_raw(numer, <$int>::try_from(w.denom()).ok()?))
+                    })
+                    .map_err(|w| SolveError::Overflow(w.to_string()))
+            }
+        }
+
+        impl Model<Ratio<$int>> {
+            /// Solves the model exactly with SCIP, failing with
+            /// `SolveError::Overflow` if a number of the solution does not
+            /// fit. Arithmetic while building the model is not checked, see
+            /// the integer type for what happens on overflow.
+            pub fn solve(
+                &self,
+                debug_artifacts: DebugArtifacts,
+            ) -> Result<Solution<Ratio<$int>>, SolveError> {
+                self.solve_with(&SolveOptions {
+                    debug_artifacts,
+                    ..Default::default()
+                })
+            }
+
+            pub fn solve_with(
+                &self,
+                opts: &SolveOptions,
+            ) -> Result<Solution<Ratio<$int>>, SolveError> {
+                self.solve_using(&ScipBackend, opts)
+            }
+        }
+    )*};
+}
+
+impl_fixed_width_rational!(i32, i64);
@@ -0,0 +1,312 @@
+use num::{BigInt, BigRational, Integer, Num, One, Zero};
+
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    error::{ModelError, SolveError},
+    Bound, Model,
+};
+
+/**
+ * Factors the objective and the rows of a formulation were multiplied by
+ */
+pub(crate) struct Scaling<N> {
+    pub(crate) objective: N,
+    /// By constraint index, rows without an entry are unscaled.
+    rows: Vec<N>,
+}
+
+impl<N> Scaling<N>
+where
+    N: Num + Clone,
+{
+    pub(crate) fn identity() -> Self {
+        Scaling {
+            objective: N::one(),
+            rows: vec![],
+        }
+    }
+
+    pub(crate) fn row(&self, i: usize) -> N {
+        self.rows.get(i).cloned().unwrap_or_else(N::one)
+    }
+}
+
+/// Smallest positive factor that makes every number of `values` an integer
+/// with no common divisor left: the least common multiple of the
+/// denominators over the greatest common divisor of the numerators it
+/// gives, or that multiple alone if every value is zero.
+fn integer_scale<'a>(values: impl Iterator<Item = &'a BigRational>) -> BigRational {
+    let values = values.collect::<Vec<_>>();
+    let lcm = values
+        .iter()
+        .fold(BigInt::one(), |lcm, value| lcm.lcm(value.denom()));
+    let gcd = values.iter().fold(BigInt::zero(), |gcd, value| {
+        gcd.gcd(&(value.numer() * (&lcm / value.denom())))
+    });
+    if gcd.is_zero() {
+        BigRational::from_integer(lcm)
+    } else {
+        BigRational::new(lcm, gcd)
+    }
+}
+
+impl Constraint<BigRational> {
+    /// The constraint normalized and multiplied by the smallest positive
+    /// factor that leaves only integers without a common divisor, with that
+    /// factor, e.g. `2 x + y <= 3` and `6` for `1/3 x + 1/6 y <= 1/2`. The
+    /// factor is below one for rows like `2 x + 4 y <= 6`.
+    pub fn to_integer_scaled(&self) -> (Constraint<BigRational>, BigRational) {
+        let c = self.normalized();
+        let bounds = match &c.sense {
+            ConstraintSense::Range { lb, ub } => vec![lb, ub],
+            _ => vec![],
+        };
+        let scale = integer_scale(
+            c.lhs
+                .0
+                .iter()
+                .chain(c.rhs.0.iter())
+                .map(|(w, _)| w)
+                .chain(bounds),
+        );
+        let sense = match c.sense {
+            ConstraintSense::Range { lb, ub } => ConstraintSense::Range {
+                lb: lb * scale.clone(),
+                ub: ub * scale.clone(),
+            },
+            sense => sense,
+        };
+        let constraint = Constraint {
+            lhs: c.lhs * scale.clone(),
+            sense,
+            rhs: c.rhs * scale.clone(),
+        };
+        (constraint, scale)
+    }
+}
+
+impl Model<BigRational> {
+    /// Equivalent model in which every coefficient, right-hand side and
+    /// bound is an integer, as the LP format has no fractions. Rows and the
+    /// objective are scaled as in `Constraint::to_integer_scaled`, so that
+    /// their integers have no common divisor, bounds of integral variables
+    /// are rounded inwards, and fractional bounds of continuous variables
+    /// become rows. Semi-continuous variables cannot have fractional bounds.
+    pub(crate) fn integral_formulation(
+        &self,
+    ) -> Result<(Model<BigRational>, Scaling<BigRational>), SolveError> {
+        let mut formulation = self.clone();
+
+        let objective_scale = integer_scale(self.objective_terms().0.iter().map(|(w, _)| w));
+        formulation.clear_objective_coefficients();
+        formulation.objective = formulation.objective.clone() * objective_scale.clone();
+
+        let mut rows = vec![BigRational::one(); self.constraints.len()];
+        for (i, c) in formulation.constraints.iter_mut().enumerate() {
+            if let Some(c) = c {
+                let (constraint, scale) = c.constraint.to_integer_scaled();
+                c.constraint = constraint;
+                rows[i] = scale;
+            }
+        }
+
+        for i in 0..formulation.variables.len() {
+            let v = &mut formulation.variables[i];
+            if v.eliminated {
+                continue;
+            }
+            if v.v_type.is_integral() && !v.v_type.is_semi() {
+                v.lb = v.lb.as_ref().map(BigRational::ceil);
+                v.ub = v.ub.as_ref().map(BigRational::floor);
+                continue;
+            }
+            let fractional = |bound: &Bound<BigRational>| {
+                bound.finite().is_some_and(|bound| !bound.is_integer())
+            };
+            let (lb, ub) = (fractional(&v.lb), fractional(&v.ub));
+            if !lb && !ub {
+                continue;
+            }
+            let name = self.variable_name(i);
+            if v.v_type.is_semi() {
+                return Err(SolveError::FractionalBound(name));
+            }
+            let variable = crate::Variable::new(i, v.name.clone());
+            let lb = if lb {
+                std::mem::replace(&mut v.lb, Bound::NegInf).into_finite()
+            } else {
+                None
+            };
+            let ub = if ub {
+                std::mem::replace(&mut v.ub, Bound::PosInf).into_finite()
+            } else {
+                None
+            };
+            if let Some(lb) = lb {
+                let (row, _) = variable.clone().ge(lb).to_integer_scaled();
+                formulation.add_bound_row(&name, "lb", row);
+            }
+            if let Some(ub) = ub {
+                let (row, _) = variable.le(ub).to_integer_scaled();
+                formulation.add_bound_row(&name, "ub", row);
+            }
+        }
+
+        Ok((
+            formulation,
+            Scaling {
+                objective: objective_scale,
+                rows,
+            },
+        ))
+    }
+
+    /// Adds a row standing in for a bound, named `{variable}_{kind}` or with
+    /// a numeric suffix if that is taken.
+    fn add_bound_row(&mut self, variable: &str, kind: &str, row: Constraint<BigRational>) {
+        let base = format!("{}_{}", variable, kind);
+        let mut name = base.clone();
+        let mut suffix = 1;
+        loop {
+            match self.add_const_named(name.clone(), row.clone()) {
+                Ok(_) => return,
+                Err(ModelError::DuplicateName(_)) => {
+                    name = format!("{}_{}", base, suffix);
+                    suffix += 1;
+                }
+                Err(e) => unreachable!("bound rows get valid names: {e}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, SolveError, SolveOptions};
+
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_integral_formulation() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(ratio(1, 3)).build();
+        let n = model
+            .add_var()
+            .name("n")
+            .integer()
+            .lb(ratio(-1, 3))
+            .ub(ratio(22, 7))
+            .build();
+        model.set_objective(ratio(1, 3) * &x + ratio(22, 7) * &n + ratio(1, 2));
+        model
+            .add_const_named("x_lb", (&x + &n).le(c(10).0))
+            .unwrap();
+        model.add_const((ratio(1, 2) * &x).between(ratio(1, 3), ratio(7, 4)));
+
+        let (formulation, scaling) = model.integral_formulation().unwrap();
+        assert_eq!(scaling.objective, c(21).0);
+        assert_eq!(scaling.row(0), c(1).0);
+        assert_eq!(scaling.row(1), c(12).0);
+        assert_eq!(scaling.row(2), c(1).0);
+        let lp = formulation.to_lp_string().unwrap();
+        assert!(!lp.contains('/'), "{lp}");
+        assert!(lp.contains(" obj: 7 x + 66 n\n"), "{lp}");
+        assert!(
+            lp.contains(
+                " c1: 4 <= 6 x <= 21
+"
+            ),
+            "{lp}"
+        );
+        assert!(lp.contains(" x_lb_1: 3 x >= 1\n"), "{lp}");
+        assert!(lp.contains(" 0 <= n <= 3\n"), "{lp}");
+        assert!(lp.contains(" x free\n"), "{lp}");
+
+        let s = model
+            .add_var()
+            .name("s")
+            .semi_continuous()
+            .lb(c(1).0)
+            .ub(ratio(5, 2))
+            .build();
+        assert!(matches!(
+            model.integral_formulation(),
+            Err(SolveError::FractionalBound(name)) if name == s.name()
+        ));
+    }
+
+    #[test]
+    fn test_fractional_coefficients() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model.add_var().name("y").lb(c(0).0).build();
+        model.maximize();
+        model.set_objective(ratio(1, 3) * &x + ratio(22, 7) * &y);
+        model.add_const((&x + &y).le(ratio(7, 2)));
+        model.add_const((ratio(1, 3) * &y).le(ratio(1, 3)));
+
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.get_value(&x), ratio(5, 2));
+        assert_eq!(solution.get_value(&y), c(1).0);
+        assert_eq!(solution.objective_value(), Some(&ratio(167, 42)));
+    }
+
+    #[test]
+    fn test_integer_scaled() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+
+        let (row, scale) = (ratio(1, 3) * &x + ratio(1, 6) * &y)
+            .le(ratio(1, 2))
+            .to_integer_scaled();
+        assert_eq!(row.to_string(), "2 x + y <= 3");
+        assert_eq!(scale, c(6).0);
+
+        let (row, scale) = (c(2) * &x + c(4) * &y + c(2).0)
+            .ge(c(8).0)
+            .to_integer_scaled();
+        assert_eq!(row.to_string(), "x + 2 y >= 3");
+        assert_eq!(scale, ratio(1, 2));
+
+        let (row, scale) = (ratio(3, 4) * &x)
+            .between(ratio(3, 2), c(3).0)
+            .to_integer_scaled();
+        assert_eq!(row.to_string(), "2 <= x <= 4");
+        assert_eq!(scale, ratio(4, 3));
+    }
+
+    #[test]
+    fn test_gcd_scaled_optimum() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        model.maximize();
+        model.set_objective(c(2) * &x + c(4) * &y);
+        model.add_const((c(2) * &x + c(4) * &y).le(c(6).0));
+        model.add_const((ratio(3, 2) * &x).le(ratio(3, 2)));
+
+        let (formulation, scaling) = model.integral_formulation().unwrap();
+        assert_eq!(scaling.objective, ratio(1, 2));
+        assert_eq!(scaling.row(0), ratio(1, 2));
+        assert_eq!(scaling.row(1), ratio(2, 3));
+        let lp = formulation.to_lp_string().unwrap();
+        assert!(lp.contains(" obj: x + 2 y\n"), "{lp}");
+        assert!(lp.contains(" c0: x + 2 y <= 3\n"), "{lp}");
+        assert!(lp.contains(" c1: x <= 1\n"), "{lp}");
+
+        let solution = model.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.objective_value(), Some(&c(6).0));
+        let scaled = formulation.solve_with(&SolveOptions::default()).unwrap();
+        assert_eq!(
+            scaled
+                .objective_value()
+                .map(|v| v / scaling.objective.clone()),
+            solution.objective_value().cloned()
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! `serde` support, enabled by the `serde` feature. Exact coefficients are
+//! written as strings such as `"3/7"`, floating point ones as numbers.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use num::{
+    rational::{Rational32, Rational64},
+    BigRational,
+};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::variable::Variable;
+
+/**
+ * Coefficient type that can be serialized
+ */
+pub trait SerdeCoefficient: Sized {
+    fn serialize_coefficient<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error>;
+    fn deserialize_coefficient<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error>;
+}
+
+macro_rules! impl_as_string {
+    ($($n:ty),*) => {$(
+        impl SerdeCoefficient for $n {
+            fn serialize_coefficient<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                s.collect_str(self)
+            }
+
+            fn deserialize_coefficient<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(d)?;
+                <$n>::from_str(&s)
+                    .map_err(|_| D::Error::custom(format!("invalid rational `{s}`")))
+            }
+        }
+    )*};
+}
+
+impl_as_string!(BigRational, Rational32, Rational64);
+
+macro_rules! impl_natively {
+    ($($n:ty),*) => {$(
+        impl SerdeCoefficient for $n {
+            fn serialize_coefficient<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                self.serialize(s)
+            }
+
+            fn deserialize_coefficient<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                <$n>::deserialize(d)
+            }
+        }
+    )*};
+}
+
+impl_natively!(f32, f64);
+
+/// Adapter running a coefficient through `SerdeCoefficient`.
+struct Coef<N>(N);
+
+impl<N: SerdeCoefficient> Serialize for Coef<&N> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_coefficient(s)
+    }
+}
+
+impl<'de, N: SerdeCoefficient> Deserialize<'de> for Coef<N> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        N::deserialize_coefficient(d).map(Coef)
+    }
+}
+
+/// `#[serde(with)]` module for a single coefficient.
+pub(crate) mod coefficient {
+    use super::*;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(n: &N, s: S) -> Result<S::Ok, S::Error> {
+        n.serialize_coefficient(s)
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<N, D::Error> {
+        N::deserialize_coefficient(d)
+    }
+}
+
+/// `#[serde(with)]` module for an optional coefficient.
+pub(crate) mod optional_coefficient {
+    use super::*;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        n: &Option<N>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        n.as_ref().map(Coef).serialize(s)
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<N>, D::Error> {
+        Ok(Option::<Coef<N>>::deserialize(d)?.map(|c| c.0))
+    }
+}
+
+/// `#[serde(with)]` module for a lower bound, `null` for `-inf`.
+pub(crate) mod lower_bound {
+    use super::*;
+    use crate::Bound;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        b: &Bound<N>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        b.finite().map(Coef).serialize(s)
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient + num::Num, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Bound<N>, D::Error> {
+        Ok(Bound::lower(
+            Option::<Coef<N>>::deserialize(d)?.map(|c| c.0),
+        ))
+    }
+}
+
+/// `#[serde(with)]` module for an upper bound, `null` for `+inf`.
+pub(crate) mod upper_bound {
+    use super::*;
+    use crate::Bound;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        b: &Bound<N>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        b.finite().map(Coef).serialize(s)
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient + num::Num, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Bound<N>, D::Error> {
+        Ok(Bound::upper(
+            Option::<Coef<N>>::deserialize(d)?.map(|c| c.0),
+        ))
+    }
+}
+
+/// `#[serde(with)]` module for a list of coefficients.
+pub(crate) mod coefficients {
+    use super::*;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        coefficients: &[N],
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        s.collect_seq(coefficients.iter().map(Coef))
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<N>, D::Error> {
+        Ok(Vec::<Coef<N>>::deserialize(d)?
+            .into_iter()
+            .map(|c| c.0)
+            .collect())
+    }
+}
+
+/// `#[serde(with)]` module for the terms of an expression.
+pub(crate) mod terms {
+    use super::*;
+
+    type Terms<N> = Vec<(N, Option<Variable<N>>)>;
+
+    pub fn serialize<N, S>(terms: &[(N, Option<Variable<N>>)], s: S) -> Result<S::Ok, S::Error>
+    where
+        N: SerdeCoefficient + num::Num + Clone,
+        S: Serializer,
+    {
+        s.collect_seq(terms.iter().map(|(w, v)| (Coef(w), v)))
+    }
+
+    pub fn deserialize<'de, N, D>(d: D) -> Result<Terms<N>, D::Error>
+    where
+        N: SerdeCoefficient + num::Num + Clone,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(Coef<N>, Option<Variable<N>>)>::deserialize(d)?
+            .into_iter()
+            .map(|(w, v)| (w.0, v))
+            .collect())
+    }
+}
+
+/// `#[serde(with)]` module for coefficients keyed by name.
+pub(crate) mod coefficient_map {
+    use super::*;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        map: &BTreeMap<String, N>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        s.collect_map(map.iter().map(|(k, v)| (k, Coef(v))))
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<BTreeMap<String, N>, D::Error> {
+        Ok(BTreeMap::<String, Coef<N>>::deserialize(d)?
+            .into_iter()
+            .map(|(k, v)| (k, v.0))
+            .collect())
+    }
+}
+
+/// `#[serde(with)]` module for optional coefficients keyed by name.
+pub(crate) mod optional_coefficient_map {
+    use super::*;
+
+    pub fn serialize<N: SerdeCoefficient, S: Serializer>(
+        map: &Option<BTreeMap<String, N>>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.as_ref()
+            .map(|map| {
+                map.iter()
+                    .map(|(k, v)| (k, Coef(v)))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, N: SerdeCoefficient, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<BTreeMap<String, N>>, D::Error> {
+        Ok(Option::<BTreeMap<String, Coef<N>>>::deserialize(d)?
+            .map(|map| map.into_iter().map(|(k, v)| (k, v.0)).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Model, Solution};
+
+    #[test]
+    fn test_model_round_trip() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(0).0).build();
+        let y = model
+            .add_var()
+            .integer()
+            .lb(BigRational::new(1.into(), 3.into()))
+            .ub(c(10).0)
+            .build();
+        let b = model.add_var().name("b").binary().build();
+        model.maximize();
+        model.add_command("set limits gap 0.1");
+        model.set_objective(c(2) * &x + BigRational::new(3.into(), 7.into()) * &y + c(1).0);
+        model.add_const((&x + c(4) * &y).le(c(24).0));
+        model
+            .add_const_named("range", (&x - &b).between(c(-1).0, c(5).0))
+            .unwrap();
+        model
+            .add_indicator(b.clone(), true, x.clone().le(c(3).0))
+            .unwrap();
+
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(json.contains("\"3/7\""), "{json}");
+        let restored: Model<BigRational> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.to_lp_string().unwrap(),
+            model.to_lp_string().unwrap()
+        );
+        assert_eq!(restored.commands, model.commands);
+        assert_eq!(restored.variable_name(1), "v1");
+        assert_eq!(restored.objective_constant(), c(1).0);
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").ub(2.5).build();
+        model.set_objective(&x * 0.5);
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(json.contains("2.5"), "{json}");
+        let restored: Model<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.to_lp_string().unwrap(),
+            model.to_lp_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solution_round_trip() {
+        let solution = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&solution).unwrap();
+        let restored: Solution<BigRational> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.status(), solution.status());
+        assert_eq!(restored.objective_value(), solution.objective_value());
+        assert!(restored.iter().eq(solution.iter()));
+        assert!(json.contains(r#""objective_value":""#), "{json}");
+
+        let solution = Solution::<f64>::parse_highs(
+            include_str!("../tests/fixtures/highs_optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&solution).unwrap();
+        assert!(json.contains(r#""status":"Optimal""#), "{json}");
+        assert!(json.contains(r#""objective_value":33.0"#), "{json}");
+        let restored: Solution<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.objective_value(), Some(&33.0));
+        assert!(restored.iter().eq(solution.iter()));
+    }
+}
@@ -0,0 +1,1743 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::BufRead,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        LazyLock,
+    },
+};
+
+use num::{BigInt, BigRational, Num, Signed, Zero};
+use regex::Regex;
+
+use crate::{
+    constraint::ConstraintId,
+    error::{IntegralityError, MissingVariables, SolveError},
+    expression::Expression,
+    options::ImportPolicy,
+    stats::SolveStats,
+    variable::Variable,
+    Model,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolveStatus {
+    Optimal,
+    Infeasible,
+    Unbounded,
+    TimeLimit,
+    /// The solver was stopped through `SolveHandle::cancel`, and the
+    /// solution is the best it had found.
+    Interrupted,
+    Unknown,
+}
+
+impl SolveStatus {
+    /// Status from the text SCIP writes after `solution status:`.
+    fn from_scip(status: &str) -> Self {
+        match status.trim() {
+            "optimal solution found" => SolveStatus::Optimal,
+            "infeasible" => SolveStatus::Infeasible,
+            "unbounded" => SolveStatus::Unbounded,
+            "time limit reached" => SolveStatus::TimeLimit,
+            "user interrupt" => SolveStatus::Interrupted,
+            _ => SolveStatus::Unknown,
+        }
+    }
+
+    /// Status from the model status line of a HiGHS solution file.
+    fn from_highs(status: &str) -> Self {
+        match status.trim() {
+            "Optimal" => SolveStatus::Optimal,
+            "Infeasible" => SolveStatus::Infeasible,
+            "Unbounded" => SolveStatus::Unbounded,
+            "Time limit reached" => SolveStatus::TimeLimit,
+            "Interrupted by user" => SolveStatus::Interrupted,
+            _ => SolveStatus::Unknown,
+        }
+    }
+
+    /// Status from the first line of a CBC solution file.
+    fn from_cbc(status: &str) -> Self {
+        match status.trim() {
+            "Optimal" => SolveStatus::Optimal,
+            "Infeasible" | "Integer infeasible" => SolveStatus::Infeasible,
+            "Unbounded" => SolveStatus::Unbounded,
+            s if s.starts_with("Stopped on time") => SolveStatus::TimeLimit,
+            s if s.starts_with("Stopped on ctrl-c") => SolveStatus::Interrupted,
+            _ => SolveStatus::Unknown,
+        }
+    }
+
+    /// Status from the `Status:` line of a glpsol report, and whether the
+    /// report holds a solution.
+    fn from_glpk(status: &str) -> (Self, bool) {
+        match status.trim() {
+            "OPTIMAL" | "INTEGER OPTIMAL" => (SolveStatus::Optimal, true),
+            "FEASIBLE" | "INTEGER NON-OPTIMAL" => (SolveStatus::Unknown, true),
+            "INFEASIBLE (FINAL)" | "INTEGER EMPTY" => (SolveStatus::Infeasible, false),
+            "UNBOUNDED" => (SolveStatus::Unbounded, false),
+            _ => (SolveStatus::Unknown, false),
+        }
+    }
+
+    /// Status from the `solutionStatusValue` of a CPLEX solution file,
+    /// CPLEX's `CPX_STAT_*` and `CPXMIP_*` codes.
+    fn from_cplex(code: &str) -> Self {
+        match code.trim() {
+            "1" | "101" | "102" => SolveStatus::Optimal,
+            "3" | "103" => SolveStatus::Infeasible,
+            "2" | "118" => SolveStatus::Unbounded,
+            "11" | "25" | "107" | "108" | "131" | "132" => SolveStatus::TimeLimit,
+            "13" | "113" | "114" => SolveStatus::Interrupted,
+            _ => SolveStatus::Unknown,
+        }
+    }
+
+    /// Status from the last result line of a CPLEX interactive optimizer
+    /// log, such as `MIP - Integer infeasible.` or `Dual simplex -
+    /// Optimal:  Objective = ...`, for solves that wrote no solution file.
+    pub(crate) fn from_cplex_log(log: &str) -> Self {
+        let Some(line) = log.lines().rev().find(|line| {
+            line.starts_with("MIP - ")
+                || line.contains("simplex - ")
+                || line.starts_with("Barrier - ")
+        }) else {
+            return SolveStatus::Unknown;
+        };
+        let line = line.to_ascii_lowercase();
+        if line.contains("infeasible or unbounded") {
+            SolveStatus::Unknown
+        } else if line.contains("unbounded") || line.contains("dual infeasible") {
+            SolveStatus::Unbounded
+        } else if line.contains("infeasible") {
+            SolveStatus::Infeasible
+        } else if line.contains("time limit") {
+            SolveStatus::TimeLimit
+        } else if line.contains("aborted") {
+            SolveStatus::Interrupted
+        } else if line.contains("optimal") {
+            SolveStatus::Optimal
+        } else {
+            SolveStatus::Unknown
+        }
+    }
+
+    /// Status from the log of `gurobi_cl`, whose solution files have none.
+    pub(crate) fn from_gurobi_log(log: &str) -> Self {
+        for line in log.lines().rev() {
+            let line = line.trim();
+            let status = if line.starts_with("Optimal solution found")
+                || line.starts_with("Optimal objective")
+            {
+                SolveStatus::Optimal
+            } else if line == "Model is infeasible or unbounded"
+                || line == "Infeasible or unbounded model"
+            {
+                SolveStatus::Unknown
+            } else if line == "Model is infeasible" || line == "Infeasible model" {
+                SolveStatus::Infeasible
+            } else if line == "Model is unbounded" || line == "Unbounded model" {
+                SolveStatus::Unbounded
+            } else if line.starts_with("Time limit reached") {
+                SolveStatus::TimeLimit
+            } else if line.starts_with("Interrupt request received")
+                || line.starts_with("Solve interrupted")
+            {
+                SolveStatus::Interrupted
+            } else {
+                continue;
+            };
+            return status;
+        }
+        SolveStatus::Unknown
+    }
+}
+
+/**
+ * Constraint given by its index in the model or its name
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstraintRef<'a> {
+    Index(usize),
+    Name(&'a str),
+}
+
+impl From<usize> for ConstraintRef<'_> {
+    fn from(i: usize) -> Self {
+        ConstraintRef::Index(i)
+    }
+}
+
+impl From<ConstraintId> for ConstraintRef<'_> {
+    fn from(id: ConstraintId) -> Self {
+        ConstraintRef::Index(id.index())
+    }
+}
+
+impl<'a> From<&'a str> for ConstraintRef<'a> {
+    fn from(name: &'a str) -> Self {
+        ConstraintRef::Name(name)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "N: crate::serialization::SerdeCoefficient")
+)]
+pub struct Solution<N>
+where
+    N: Num + Clone,
+{
+    status: SolveStatus,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::optional_coefficient")
+    )]
+    objective_value: Option<N>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::coefficient_map")
+    )]
+    values: BTreeMap<String, N>,
+    log: String,
+    /// Dual values by constraint name, with the names in constraint order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::coefficient_map")
+    )]
+    duals: BTreeMap<String, N>,
+    constraint_names: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::coefficient_map")
+    )]
+    reduced_costs: BTreeMap<String, N>,
+    /// Value of every objective of a multi-objective solve, by index.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serialization::coefficients")
+    )]
+    objective_values: Vec<N>,
+    artifact_dir: Option<PathBuf>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    stats: Option<SolveStats>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serialization::optional_coefficient")
+    )]
+    primal_bound: Option<N>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serialization::optional_coefficient")
+    )]
+    dual_bound: Option<N>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    approximate: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cut_rounds: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cut_limit_reached: bool,
+    /// Variables `get_value` counted as zero for lack of a value.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    missing_lookups: AtomicUsize,
+}
+
+impl<N> Solution<N>
+where
+    N: Num + Clone,
+{
+    /// Solution without values and with an unknown status.
+    fn empty() -> Self {
+        Solution {
+            status: SolveStatus::Unknown,
+            objective_value: None,
+            values: Default::default(),
+            log: String::new(),
+            duals: Default::default(),
+            constraint_names: Default::default(),
+            reduced_costs: Default::default(),
+            objective_values: Default::default(),
+            artifact_dir: None,
+            stats: None,
+            primal_bound: None,
+            dual_bound: None,
+            approximate: false,
+            cut_rounds: 0,
+            cut_limit_reached: false,
+            missing_lookups: AtomicUsize::new(0),
+        }
+    }
+
+    /// Solution with the given values, keyed by variable name, e.g. for a
+    /// custom `Backend`.
+    pub fn new(
+        status: SolveStatus,
+        objective_value: Option<N>,
+        values: BTreeMap<String, N>,
+    ) -> Self {
+        Solution {
+            status,
+            objective_value,
+            values,
+            ..Solution::empty()
+        }
+    }
+
+    pub fn status(&self) -> SolveStatus {
+        self.status
+    }
+
+    pub fn objective_value(&self) -> Option<&N> {
+        self.objective_value.as_ref()
+    }
+
+    /// Everything the solver printed while solving.
+    pub fn solver_log(&self) -> &str {
+        &self.log
+    }
+
+    pub(crate) fn values(&self) -> &BTreeMap<String, N> {
+        &self.values
+    }
+
+    /// Sets the value of the variable called `name`.
+    pub(crate) fn insert_value(&mut self, name: String, value: N) {
+        self.values.insert(name, value);
+    }
+
+    pub(crate) fn with_status(mut self, status: SolveStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub(crate) fn with_values(mut self, values: BTreeMap<String, N>) -> Self {
+        self.values = values;
+        self
+    }
+
+    pub(crate) fn with_log(mut self, log: String) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Directory the solve's files were kept in, see `DebugArtifacts`.
+    pub fn artifact_dir(&self) -> Option<&Path> {
+        self.artifact_dir.as_deref()
+    }
+
+    /// Solve time and SCIP's statistics, set by SCIP solves.
+    pub fn stats(&self) -> Option<&SolveStats> {
+        self.stats.as_ref()
+    }
+
+    pub(crate) fn with_stats(mut self, stats: SolveStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Objective value of the best solution SCIP found, in the model's
+    /// objective, or `None` if it found none or is not the solver. Exact
+    /// solves read it as exactly as SCIP prints it.
+    pub fn primal_bound(&self) -> Option<&N> {
+        self.primal_bound.as_ref()
+    }
+
+    /// Bound SCIP proved on the optimal objective value, a lower bound when
+    /// minimizing, e.g. to state how far from optimal a solve stopped by a
+    /// limit can be.
+    pub fn dual_bound(&self) -> Option<&N> {
+        self.dual_bound.as_ref()
+    }
+
+    /// Whether the numbers are rational approximations of floating-point
+    /// ones, from solving a rational model after `Model::set_exact(false)`.
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+
+    pub(crate) fn with_approximate(mut self, approximate: bool) -> Self {
+        self.approximate = approximate;
+        self
+    }
+
+    /// Rounds of cuts `Model::solve_with_cuts` added before the solve that
+    /// gave this solution, zero for other solves.
+    pub fn cut_rounds(&self) -> usize {
+        self.cut_rounds
+    }
+
+    /// Whether `Model::solve_with_cuts` stopped at `SolveOptions::cut_rounds`
+    /// with cuts left to add, so the solution may violate them.
+    pub fn cut_limit_reached(&self) -> bool {
+        self.cut_limit_reached
+    }
+
+    pub(crate) fn with_cut_rounds(mut self, rounds: usize, limit_reached: bool) -> Self {
+        self.cut_rounds = rounds;
+        self.cut_limit_reached = limit_reached;
+        self
+    }
+
+    pub(crate) fn with_bounds(mut self, primal_bound: Option<N>, dual_bound: Option<N>) -> Self {
+        self.primal_bound = primal_bound;
+        self.dual_bound = dual_bound;
+        self
+    }
+
+    pub(crate) fn with_objective_value(mut self, objective_value: Option<N>) -> Self {
+        self.objective_value = objective_value;
+        self
+    }
+
+    /// Value of every objective by index, set by `Model::solve_lexicographic`
+    /// and `Model::solve_weighted`. Empty for other solves.
+    pub fn objective_values(&self) -> &[N] {
+        &self.objective_values
+    }
+
+    pub(crate) fn with_objective_values(mut self, objective_values: Vec<N>) -> Self {
+        self.objective_values = objective_values;
+        self
+    }
+
+    /// Gives every variable in `names` without a value the value zero, for
+    /// solvers that only write nonzero values.
+    pub(crate) fn with_zeros_for(mut self, names: impl Iterator<Item = String>) -> Self {
+        for name in names {
+            self.values.entry(name).or_insert_with(N::zero);
+        }
+        self
+    }
+
+    /// Adds `offset` to the objective value, for objective terms the solver
+    /// did not see.
+    pub(crate) fn with_objective_offset(mut self, offset: N) -> Self {
+        let shift = |v: Option<N>| v.map(|v| v + offset.clone());
+        self.objective_value = shift(self.objective_value);
+        self.primal_bound = shift(self.primal_bound);
+        self.dual_bound = shift(self.dual_bound);
+        self
+    }
+
+    pub(crate) fn with_artifact_dir(mut self, dir: PathBuf) -> Self {
+        self.artifact_dir = Some(dir);
+        self
+    }
+
+    pub(crate) fn with_duals(
+        mut self,
+        duals: BTreeMap<String, N>,
+        constraint_names: Vec<String>,
+        reduced_costs: BTreeMap<String, N>,
+    ) -> Self {
+        self.duals = duals;
+        self.constraint_names = constraint_names;
+        self.reduced_costs = reduced_costs;
+        self
+    }
+
+    /// Copy of the solution with every number converted by `f`, failing
+    /// with the first one it cannot convert.
+    pub(crate) fn try_map<M>(&self, f: impl Fn(&N) -> Option<M>) -> Result<Solution<M>, N>
+    where
+        M: Num + Clone,
+    {
+        let one = |w: &N| f(w).ok_or_else(|| w.clone());
+        let optional = |w: &Option<N>| w.as_ref().map(one).transpose();
+        let map = |m: &BTreeMap<String, N>| {
+            m.iter()
+                .map(|(name, w)| Ok((name.clone(), one(w)?)))
+                .collect::<Result<BTreeMap<_, _>, N>>()
+        };
+        Ok(Solution {
+            status: self.status,
+            objective_value: optional(&self.objective_value)?,
+            values: map(&self.values)?,
+            log: self.log.clone(),
+            duals: map(&self.duals)?,
+            constraint_names: self.constraint_names.clone(),
+            reduced_costs: map(&self.reduced_costs)?,
+            objective_values: self
+                .objective_values
+                .iter()
+                .map(one)
+                .collect::<Result<_, _>>()?,
+            artifact_dir: self.artifact_dir.clone(),
+            stats: self.stats,
+            primal_bound: optional(&self.primal_bound)?,
+            dual_bound: optional(&self.dual_bound)?,
+            approximate: self.approximate,
+            cut_rounds: self.cut_rounds,
+            cut_limit_reached: self.cut_limit_reached,
+            missing_lookups: AtomicUsize::new(0),
+        })
+    }
+
+    /// Dual value of a constraint, given by its index or name. Only set when
+    /// solving with `SolveOptions::duals`.
+    pub fn dual_value<'a>(&self, constraint: impl Into<ConstraintRef<'a>>) -> Option<N> {
+        let name = match constraint.into() {
+            ConstraintRef::Index(i) => self.constraint_names.get(i)?.as_str(),
+            ConstraintRef::Name(name) => name,
+        };
+        self.duals.get(name).cloned()
+    }
+
+    /// Reduced cost of `v`, the objective coefficient minus the dual
+    /// weighted column. Only set when solving with `SolveOptions::duals`.
+    pub fn reduced_cost(&self, v: &Variable<N>) -> Option<N> {
+        self.reduced_costs.get(&v.name()).cloned()
+    }
+
+    /// Value of `e`, counting variables without a value as zero. How often
+    /// that happened is reported by `missing_lookups`. Converts `e` into an
+    /// expression first, see `get_value_ref` to evaluate one without.
+    pub fn get_value(&self, e: impl Into<Expression<N>>) -> N {
+        self.get_value_ref(&e.into())
+    }
+
+    /// Like `get_value`, but borrowing `e` and cloning only the numbers
+    /// that are multiplied, for evaluating many expressions.
+    pub fn get_value_ref(&self, e: &Expression<N>) -> N {
+        let mut missing = 0;
+        let value = e.0.iter().fold(N::zero(), |acc, (w, v)| match v {
+            Some(v) => match self.values.get(v.name_ref().as_ref()) {
+                Some(value) => acc + w.clone() * value.clone(),
+                None => {
+                    missing += 1;
+                    acc
+                }
+            },
+            None => acc + w.clone(),
+        });
+        if missing > 0 {
+            self.missing_lookups.fetch_add(missing, Ordering::Relaxed);
+        }
+        value
+    }
+
+    /// Values of `exprs` in order, each as `get_value_ref` computes it.
+    pub fn get_values<'a>(&self, exprs: impl IntoIterator<Item = &'a Expression<N>>) -> Vec<N>
+    where
+        N: 'a,
+    {
+        exprs.into_iter().map(|e| self.get_value_ref(e)).collect()
+    }
+
+    /// Value of `e`, failing with the names of its variables that have no
+    /// value.
+    pub fn try_get_value(&self, e: impl Into<Expression<N>>) -> Result<N, MissingVariables> {
+        let e = e.into();
+        let mut missing: Vec<String> = vec![];
+        for (_, v) in e.terms() {
+            let name = v.name_ref();
+            if !self.values.contains_key(name.as_ref()) && !missing.iter().any(|m| *m == name) {
+                missing.push(name.into_owned());
+            }
+        }
+        if !missing.is_empty() {
+            return Err(MissingVariables(missing));
+        }
+        Ok(e.evaluate_or_zero(&self.values))
+    }
+
+    /// Whether the solution has a value for `v`.
+    pub fn has(&self, v: &Variable<N>) -> bool {
+        self.values.contains_key(v.name_ref().as_ref())
+    }
+
+    /// Number of variables `get_value` counted as zero because the solution
+    /// has no value for them. Solves write every variable of the model, so
+    /// anything but zero points at a variable of another model or a
+    /// solution file that could not be read completely.
+    pub fn missing_lookups(&self) -> usize {
+        self.missing_lookups.load(Ordering::Relaxed)
+    }
+
+    /// Value of the variable called `name`, `None` if the solution file has
+    /// no entry for it.
+    pub fn get(&self, name: &str) -> Option<&N> {
+        self.values.get(name)
+    }
+
+    /// Variable names and values, sorted by name.
+    pub fn iter(&self) -> SolutionIter<'_, N> {
+        SolutionIter(self.values.iter())
+    }
+
+    /// Like `iter`, but skips variables whose value is exactly zero.
+    pub fn nonzero(&self) -> impl Iterator<Item = (&str, &N)> {
+        self.iter().filter(|(_, value)| !value.is_zero())
+    }
+
+    /// Number of variables with a value.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Reads a solution file as written by SCIP's `write solution`, parsing
+    /// every value with `parse_value`.
+    fn parse_with(
+        reader: impl std::io::Read,
+        parse_value: impl Fn(&str) -> Option<N>,
+    ) -> Result<Self, SolveError> {
+        static RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^(?<id>\S+)\s+(?<value>[+-]?(?:\d|\.\d|inf)\S*)").unwrap()
+        });
+
+        let mut result = Solution::empty();
+
+        let lines = std::io::BufReader::new(reader)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for line in lines.iter() {
+            if let Some(status) = line.strip_prefix("solution status:") {
+                result.status = SolveStatus::from_scip(status);
+                continue;
+            }
+            if let Some(objective) = line.strip_prefix("objective value:") {
+                let value = parse_value(objective.trim())
+                    .ok_or_else(|| SolveError::SolutionParse(line.clone()))?;
+                result.objective_value = Some(value);
+                continue;
+            }
+            let Some(caps) = RE.captures(line) else {
+                continue;
+            };
+            let id = caps["id"].to_string();
+            let value = parse_value(&caps["value"])
+                .ok_or_else(|| SolveError::SolutionParse(line.clone()))?;
+            result.values.insert(id, value);
+        }
+
+        Ok(result)
+    }
+
+    /// Reads the `variable,value` rows written by `write_csv`, parsing every
+    /// value with `parse_value`.
+    fn from_csv_with(
+        reader: impl std::io::Read,
+        parse_value: impl Fn(&str) -> Option<N>,
+    ) -> Result<Self, SolveError> {
+        let mut result = Solution::empty();
+        let mut lines = std::io::BufReader::new(reader).lines();
+        match lines.next().transpose()? {
+            Some(header) if header == CSV_HEADER => {}
+            Some(header) => return Err(SolveError::SolutionParse(header)),
+            None => return Ok(result),
+        }
+        for line in lines {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let (name, value) = split_csv_row(&line)
+                .and_then(|(name, value)| Some((name, parse_value(&value)?)))
+                .ok_or_else(|| SolveError::SolutionParse(line.clone()))?;
+            result.values.insert(name, value);
+        }
+        Ok(result)
+    }
+}
+
+impl<N> Solution<N>
+where
+    N: Num + Clone + std::fmt::Display,
+{
+    /// Writes the values as CSV with a `variable,value` header, one row per
+    /// variable in name order. Exact values are written as fractions like
+    /// `3/7`.
+    pub fn write_csv(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "{}", CSV_HEADER)?;
+        for (name, value) in self.values.iter() {
+            if name.contains([',', '"']) {
+                writeln!(w, "\"{}\",{}", name.replace('"', "\"\""), value)?;
+            } else {
+                writeln!(w, "{},{}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N> Solution<N>
+where
+    N: Num + Clone + Signed + PartialOrd,
+{
+    /// Relative gap `|primal - dual| / min(|primal|, |dual|)` between the
+    /// bounds, as SCIP computes it, e.g. `1/7` for 98 and 112. Zero when the
+    /// bounds are equal, as after proving optimality, and `None` if a bound
+    /// is missing or the gap is infinite because the bounds differ in sign.
+    pub fn gap(&self) -> Option<N> {
+        let (primal, dual) = (self.primal_bound.as_ref()?, self.dual_bound.as_ref()?);
+        if primal == dual {
+            return Some(N::zero());
+        }
+        if primal.is_zero() || dual.is_zero() || primal.is_positive() != dual.is_positive() {
+            return None;
+        }
+        let (primal_abs, dual_abs) = (primal.abs(), dual.abs());
+        let smaller = if primal_abs < dual_abs {
+            primal_abs
+        } else {
+            dual_abs
+        };
+        Some((primal.clone() - dual.clone()).abs() / smaller)
+    }
+}
+
+impl Solution<BigRational> {
+    /// Parses a SCIP solution file, reading every value exactly. Rounded
+    /// looking decimals are rejected, see `ImportPolicy::RejectRounded`.
+    pub fn parse(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        Self::parse_with_policy(reader, ImportPolicy::default())
+    }
+
+    /// Parses a SCIP solution file, failing on values `policy` rejects.
+    pub fn parse_with_policy(
+        reader: impl std::io::Read,
+        policy: ImportPolicy,
+    ) -> Result<Self, SolveError> {
+        Self::parse_with(reader, |s| {
+            is_accepted(s, policy).then(|| parse_rational(s)).flatten()
+        })
+    }
+
+    /// Reads values written by `write_csv`. The solution has no status or
+    /// objective value, but can be used as a start or to check feasibility.
+    pub fn from_csv(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        Self::from_csv_with(reader, parse_rational)
+    }
+}
+
+impl Solution<f64> {
+    pub fn parse(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        Self::parse_with(reader, |s| f64::from_str(s).ok())
+    }
+
+    /// Reads values written by `write_csv`, see
+    /// `Solution::<BigRational>::from_csv`.
+    pub fn from_csv(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        Self::from_csv_with(reader, |s| f64::from_str(s).ok())
+    }
+
+    /// Values of the integer, binary and semi-integer variables of `model`
+    /// that are in the solution, in variable order.
+    fn integral_values<'a>(
+        &'a self,
+        model: &'a Model<f64>,
+    ) -> impl Iterator<Item = (Cow<'a, str>, f64)> + 'a {
+        model
+            .live_variables()
+            .filter(|(_, v)| v.v_type.is_integral())
+            .filter_map(|(i, _)| {
+                let name = model.variable_name_ref(i);
+                let value = *self.values.get(name.as_ref())?;
+                Some((name, value))
+            })
+    }
+
+    /// Integral variables of `model` whose value is further than `tol` from
+    /// the nearest integer, or NaN.
+    pub fn integrality_violations(&self, model: &Model<f64>, tol: f64) -> Vec<IntegralityError> {
+        self.integral_values(model)
+            .filter(|(_, value)| !is_near_integer(*value, tol))
+            .map(|(name, value)| IntegralityError {
+                variable: name.into_owned(),
+                value,
+            })
+            .collect()
+    }
+
+    /// Copy of the solution with the values of the integer, binary and
+    /// semi-integer variables of `model` rounded to the nearest integer.
+    /// Fails on the first value further than `tol` from it. The objective
+    /// value is kept as the solver reported it.
+    pub fn rounded(&self, model: &Model<f64>, tol: f64) -> Result<Solution<f64>, IntegralityError> {
+        let mut values = self.values.clone();
+        for (name, value) in self.integral_values(model) {
+            if !is_near_integer(value, tol) {
+                return Err(IntegralityError {
+                    variable: name.into_owned(),
+                    value,
+                });
+            }
+            values.insert(name.into_owned(), value.round());
+        }
+        Ok(Solution {
+            status: self.status,
+            objective_value: self.objective_value,
+            values,
+            log: self.log.clone(),
+            duals: self.duals.clone(),
+            constraint_names: self.constraint_names.clone(),
+            reduced_costs: self.reduced_costs.clone(),
+            objective_values: self.objective_values.clone(),
+            artifact_dir: self.artifact_dir.clone(),
+            stats: self.stats,
+            primal_bound: self.primal_bound,
+            dual_bound: self.dual_bound,
+            approximate: self.approximate,
+            cut_rounds: self.cut_rounds,
+            cut_limit_reached: self.cut_limit_reached,
+            missing_lookups: AtomicUsize::new(0),
+        })
+    }
+
+    /// Parses a solution file written by CBC's `solution` command, which
+    /// lists the columns with a nonzero value.
+    pub fn parse_cbc(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        let mut result = Solution::empty();
+
+        let mut lines = std::io::BufReader::new(reader).lines();
+        let Some(header) = lines.next().transpose()? else {
+            return Ok(result);
+        };
+        let (status, objective) = header
+            .split_once(" - objective value ")
+            .ok_or_else(|| SolveError::SolutionParse(header.clone()))?;
+        result.status = SolveStatus::from_cbc(status);
+        result.objective_value = Some(
+            f64::from_str(objective.trim())
+                .map_err(|_| SolveError::SolutionParse(header.clone()))?,
+        );
+
+        for line in lines {
+            let line = line?;
+            // `**` marks values violating a bound or constraint
+            let fields = line
+                .trim_start_matches(|c: char| c == '*' || c.is_whitespace())
+                .split_whitespace()
+                .collect::<Vec<_>>();
+            match fields[..] {
+                [] => {}
+                [_, name, value, _] => {
+                    let value = f64::from_str(value)
+                        .map_err(|_| SolveError::SolutionParse(line.clone()))?;
+                    result.values.insert(name.to_string(), value);
+                }
+                _ => return Err(SolveError::SolutionParse(line)),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Parses a solution file written by HiGHS' `--solution_file`.
+    pub fn parse_highs(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        let mut result = Solution::empty();
+
+        let lines = std::io::BufReader::new(reader)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?;
+        let parse = |line: &String, value: &str| {
+            f64::from_str(value.trim()).map_err(|_| SolveError::SolutionParse(line.clone()))
+        };
+
+        let mut lines = lines.iter();
+        while let Some(line) = lines.next() {
+            if line == "Model status" {
+                if let Some(status) = lines.next() {
+                    result.status = SolveStatus::from_highs(status);
+                }
+            } else if let Some(objective) = line.strip_prefix("Objective ") {
+                if result.objective_value.is_none() {
+                    result.objective_value = Some(parse(line, objective)?);
+                }
+            } else if let Some(count) = line.strip_prefix("# Columns ") {
+                let count = usize::from_str(count.trim())
+                    .map_err(|_| SolveError::SolutionParse(line.clone()))?;
+                for line in lines.by_ref().take(count) {
+                    let Some((id, value)) = line.split_once(char::is_whitespace) else {
+                        return Err(SolveError::SolutionParse(line.clone()));
+                    };
+                    result.values.insert(id.to_string(), parse(line, value)?);
+                }
+                // only the primal values are needed
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Parses a solution file written by CPLEX's `write` command in its XML
+    /// format, reading only the status and objective value of the header
+    /// and the values of the variables.
+    pub fn parse_cplex(mut reader: impl std::io::Read) -> Result<Self, SolveError> {
+        static ELEMENT: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"<(?<tag>header|variable)\b(?<attributes>[^>]*)>").unwrap()
+        });
+        static ATTRIBUTE: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r#"(?<key>\w+)\s*=\s*"(?<value>[^"]*)""#).unwrap());
+
+        let mut result = Solution::empty();
+        let mut xml = String::new();
+        reader.read_to_string(&mut xml)?;
+        for element in ELEMENT.captures_iter(&xml) {
+            let attributes = ATTRIBUTE
+                .captures_iter(&element["attributes"])
+                .map(|a| (a.name("key").unwrap().as_str(), unescape_xml(&a["value"])))
+                .collect::<BTreeMap<_, _>>();
+            let number = |key: &str| {
+                let value = attributes
+                    .get(key)
+                    .ok_or_else(|| SolveError::SolutionParse(element[0].to_string()))?;
+                f64::from_str(value).map_err(|_| SolveError::SolutionParse(element[0].to_string()))
+            };
+            if &element["tag"] == "header" {
+                if let Some(code) = attributes.get("solutionStatusValue") {
+                    result.status = SolveStatus::from_cplex(code);
+                }
+                if attributes.contains_key("objectiveValue") {
+                    result.objective_value = Some(number("objectiveValue")?);
+                }
+            } else {
+                let name = attributes
+                    .get("name")
+                    .ok_or_else(|| SolveError::SolutionParse(element[0].to_string()))?;
+                result.values.insert(name.clone(), number("value")?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Parses a solution file written by `gurobi_cl ResultFile=...sol`:
+    /// comments, one with the objective value, and a `name value` line per
+    /// variable. The file has no status, so it is `Unknown`; solves take it
+    /// from the log.
+    pub fn parse_gurobi(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        let mut result = Solution::empty();
+
+        for line in std::io::BufReader::new(reader).lines() {
+            let line = line?;
+            let parse = |value: &str| {
+                f64::from_str(value.trim()).map_err(|_| SolveError::SolutionParse(line.clone()))
+            };
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some((_, objective)) = comment.split_once("Objective value =") {
+                    result.objective_value = Some(parse(objective)?);
+                }
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => {}
+                [name, value] => {
+                    result.values.insert(name.to_string(), parse(value)?);
+                }
+                _ => return Err(SolveError::SolutionParse(line)),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Parses the report glpsol writes with `-o`. Values in it have six
+    /// significant digits.
+    pub fn parse_glpk(reader: impl std::io::Read) -> Result<Self, SolveError> {
+        Ok(Self::parse_glpk_report(reader)?.0)
+    }
+
+    /// `parse_glpk`, also returning the column names in glpsol's order,
+    /// which the `-w` solution file refers to them by.
+    pub(crate) fn parse_glpk_report(
+        reader: impl std::io::Read,
+    ) -> Result<(Self, Vec<String>), SolveError> {
+        let mut result = Solution::empty();
+        let mut has_solution = false;
+        let mut columns = vec![];
+
+        let mut lines = std::io::BufReader::new(reader).lines();
+        while let Some(line) = lines.next().transpose()? {
+            if let Some(status) = line.strip_prefix("Status:") {
+                (result.status, has_solution) = SolveStatus::from_glpk(status);
+            } else if let Some(objective) = line.strip_prefix("Objective:") {
+                // `obj = 33 (MAXimum)`, without `obj = ` for unnamed ones
+                let value = objective.split_once('(').map_or(objective, |(v, _)| v);
+                let value = value.rsplit_once('=').map_or(value, |(_, v)| v);
+                result.objective_value = Some(
+                    f64::from_str(value.trim())
+                        .map_err(|_| SolveError::SolutionParse(line.clone()))?,
+                );
+            } else if line.trim_start().starts_with("No. Column name") {
+                // the line of dashes under the header
+                lines.next().transpose()?;
+                while let Some(mut line) = lines.next().transpose()? {
+                    match line.split_whitespace().count() {
+                        0 => break,
+                        // a name longer than 12 characters is on a line of
+                        // its own
+                        2 => match lines.next().transpose()? {
+                            Some(rest) => {
+                                line.push(' ');
+                                line.push_str(&rest);
+                            }
+                            None => return Err(SolveError::SolutionParse(line)),
+                        },
+                        _ => {}
+                    }
+                    let fields = line.split_whitespace().collect::<Vec<_>>();
+                    let [_, name, rest @ ..] = &fields[..] else {
+                        return Err(SolveError::SolutionParse(line));
+                    };
+                    // the basis status of LPs or `*` for integer columns
+                    // comes before the activity
+                    let activity = rest
+                        .iter()
+                        .find(|f| !matches!(**f, "B" | "NL" | "NU" | "NF" | "NS" | "*"));
+                    let value = activity
+                        .and_then(|a| f64::from_str(a).ok())
+                        .ok_or_else(|| SolveError::SolutionParse(line.clone()))?;
+                    columns.push(name.to_string());
+                    result.values.insert(name.to_string(), value);
+                }
+                break;
+            }
+        }
+
+        if !has_solution {
+            result.objective_value = None;
+            result.values.clear();
+        }
+        Ok((result, columns))
+    }
+
+    /// Column values of the solution file glpsol writes with `-w`, which
+    /// has them with full precision, named by the `columns` of the report.
+    /// `None` if the file is not in the format of GLPK 4.57 and later.
+    pub(crate) fn parse_glpk_values(
+        reader: impl std::io::Read,
+        columns: &[String],
+    ) -> Result<Option<BTreeMap<String, f64>>, SolveError> {
+        let mut kind = None;
+        let mut values = BTreeMap::new();
+        for line in std::io::BufReader::new(reader).lines() {
+            let line = line?;
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match fields[..] {
+                ["s", solution_kind, ..] => kind = Some(solution_kind.to_string()),
+                ["j", column, ref rest @ ..] => {
+                    // basic solutions have the column status first
+                    let value = match kind.as_deref() {
+                        Some("bas") => rest.get(1),
+                        Some(_) => rest.first(),
+                        None => return Ok(None),
+                    };
+                    let name = usize::from_str(column)
+                        .ok()
+                        .and_then(|j| columns.get(j.checked_sub(1)?));
+                    let (Some(name), Some(value)) =
+                        (name, value.and_then(|v| f64::from_str(v).ok()))
+                    else {
+                        return Err(SolveError::SolutionParse(line));
+                    };
+                    values.insert(name.clone(), value);
+                }
+                _ => {}
+            }
+        }
+        Ok(kind.map(|_| values))
+    }
+}
+
+/**
+ * Iterator over the `(name, value)` pairs of a solution
+ */
+pub struct SolutionIter<'a, N>(std::collections::btree_map::Iter<'a, String, N>);
+
+impl<'a, N> Iterator for SolutionIter<'a, N> {
+    type Item = (&'a str, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(name, value)| (name.as_str(), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, N> IntoIterator for &'a Solution<N>
+where
+    N: Num + Clone,
+{
+    type Item = (&'a str, &'a N);
+    type IntoIter = SolutionIter<'a, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+const CSV_HEADER: &str = "variable,value";
+
+/// Splits a `name,value` row, where the name can be quoted.
+fn split_csv_row(line: &str) -> Option<(String, String)> {
+    let Some(quoted) = line.strip_prefix('"') else {
+        let (name, value) = line.split_once(',')?;
+        return Some((name.to_string(), value.to_string()));
+    };
+    let mut name = String::new();
+    let mut chars = quoted.chars();
+    loop {
+        match chars.next()? {
+            '"' if chars.as_str().starts_with('"') => {
+                chars.next();
+                name.push('"');
+            }
+            '"' => break,
+            c => name.push(c),
+        }
+    }
+    let value = chars.as_str().strip_prefix(',')?;
+    Some((name, value.to_string()))
+}
+
+/// Whether the literal `s` is a value `policy` can be read from.
+fn is_accepted(s: &str, policy: ImportPolicy) -> bool {
+    let is_decimal = s.contains(['.', 'e', 'E']);
+    match policy {
+        ImportPolicy::AcceptDecimals => true,
+        ImportPolicy::RequireFractions => !is_decimal,
+        ImportPolicy::RejectRounded => {
+            let mantissa = s.split(['e', 'E']).next().unwrap_or(s);
+            let significant = mantissa
+                .trim_start_matches(['+', '-', '0', '.'])
+                .bytes()
+                .filter(u8::is_ascii_digit)
+                .count();
+            !is_decimal || significant < 12
+        }
+    }
+}
+
+/// Whether `value` is at most `tol` from the nearest integer, which NaN is
+/// not.
+fn is_near_integer(value: f64, tol: f64) -> bool {
+    (value - value.round()).abs() <= tol
+}
+
+/// `s` with the XML entities `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`
+/// replaced.
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads an `f64`, also accepting fractions like `1/3`.
+pub(crate) fn parse_f64(s: &str) -> Option<f64> {
+    match s.split_once('/') {
+        Some((numer, denom)) => Some(f64::from_str(numer).ok()? / f64::from_str(denom).ok()?),
+        None => f64::from_str(s).ok(),
+    }
+}
+
+/// Parses integers (`-3`), fractions (`-3/7`), decimals (`-0.375`) and
+/// scientific notation (`1e-05`) into an exact rational.
+pub(crate) fn parse_rational(s: &str) -> Option<BigRational> {
+    if s.contains('/') {
+        return BigRational::from_str(s).ok();
+    }
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, i16::from_str(exponent).ok()?),
+        None => (s, 0),
+    };
+    let (negative, digits) = match mantissa.as_bytes().first()? {
+        b'-' => (true, &mantissa[1..]),
+        b'+' => (false, &mantissa[1..]),
+        _ => (false, mantissa),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if int.is_empty() && frac.is_empty()
+        || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let numer = BigInt::from_str(&format!("0{int}{frac}")).ok()?;
+    let scale = i32::from(exponent) - frac.len() as i32;
+    let power = num::pow(BigInt::from(10), scale.unsigned_abs() as usize);
+    let value = if scale < 0 {
+        BigRational::new(numer, power)
+    } else {
+        BigRational::from_integer(numer * power)
+    };
+    if negative && !value.is_zero() {
+        Some(-value)
+    } else {
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use super::{parse_rational, Solution, SolveStatus};
+    use crate::{
+        c, ImportPolicy, IntegralityError, MissingSolutionValues, MissingVariables, Model,
+        SolveError,
+    };
+
+    fn r(n: i64, d: i64) -> BigRational {
+        BigRational::new(n.into(), d.into())
+    }
+
+    #[test]
+    fn test_parse_rational() {
+        assert_eq!(parse_rational("0"), Some(r(0, 1)));
+        assert_eq!(parse_rational("12"), Some(r(12, 1)));
+        assert_eq!(parse_rational("-3/7"), Some(r(-3, 7)));
+        assert_eq!(parse_rational("2.5"), Some(r(5, 2)));
+        assert_eq!(parse_rational("-0.375"), Some(r(-3, 8)));
+        assert_eq!(parse_rational(".5"), Some(r(1, 2)));
+        assert_eq!(parse_rational("1e-05"), Some(r(1, 100000)));
+        assert_eq!(parse_rational("-2.5E+03"), Some(r(-2500, 1)));
+        assert_eq!(parse_rational("1.25e1"), Some(r(25, 2)));
+        assert_eq!(parse_rational("-"), None);
+        assert_eq!(parse_rational("1.2.3"), None);
+        assert_eq!(parse_rational("1e"), None);
+        assert_eq!(parse_rational("inf"), None);
+    }
+
+    #[test]
+    fn test_parse_exact_values() {
+        let fixture = include_str!("../tests/fixtures/exact_values.sol");
+        let solution =
+            Solution::<BigRational>::parse_with_policy(fixture.as_bytes(), ImportPolicy::default())
+                .unwrap();
+        assert_eq!(solution.objective_value(), Some(&r(-7, 2)));
+        assert_eq!(solution.get("fraction"), Some(&r(5, 3)));
+        assert_eq!(solution.get("negative_fraction"), Some(&r(-1, 7)));
+        assert_eq!(solution.get("decimal"), Some(&r(5, 2)));
+        assert_eq!(solution.get("negative_decimal"), Some(&r(-3, 8)));
+        assert_eq!(solution.get("exponent"), Some(&r(1, 100000)));
+        assert_eq!(solution.get("negative_exponent"), Some(&r(-2500, 1)));
+
+        let strict = Solution::<BigRational>::parse_with_policy(
+            fixture.as_bytes(),
+            ImportPolicy::RequireFractions,
+        );
+        assert!(matches!(strict, Err(SolveError::SolutionParse(_))));
+
+        let rounded = "x 0.333333333333 \t(obj:0)\n";
+        assert!(matches!(
+            Solution::<BigRational>::parse(rounded.as_bytes()),
+            Err(SolveError::SolutionParse(_))
+        ));
+        let accepted = Solution::<BigRational>::parse_with_policy(
+            rounded.as_bytes(),
+            ImportPolicy::AcceptDecimals,
+        )
+        .unwrap();
+        assert_eq!(accepted.get("x"), Some(&r(333333333333, 1000000000000)));
+    }
+
+    #[test]
+    fn test_parse_values() {
+        let sol = "solution status: optimal solution found\n\
+                   objective value:                                   -3/7\n\
+                   x                                               -3/7 \t(obj:1)\n\
+                   y                                                  2 \t(obj:0)\n\
+                   z                                               -2.5 \t(obj:0)\n";
+        let solution = Solution::<BigRational>::parse(sol.as_bytes()).unwrap();
+        assert_eq!(solution.objective_value(), Some(&r(-3, 7)));
+        assert_eq!(solution.values.len(), 3);
+        assert_eq!(solution.values["x"], r(-3, 7));
+        assert_eq!(solution.values["y"], r(2, 1));
+        assert_eq!(solution.values["z"], r(-5, 2));
+
+        let sol = "solution status: optimal solution found\n\
+                   objective value:                                   -1e-05\n\
+                   x                                              -1e-05 \t(obj:1)\n\
+                   y                                             2.5E+03 \t(obj:0)\n";
+        let solution = Solution::<f64>::parse(sol.as_bytes()).unwrap();
+        assert_eq!(solution.objective_value(), Some(&-1e-05));
+        assert_eq!(solution.values["x"], -1e-05);
+        assert_eq!(solution.values["y"], 2500.0);
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let z = model.add_var().name("z").build();
+        let sol = "solution status: optimal solution found\n\
+                   objective value:                                      3\n\
+                   x                                                  3 \t(obj:1)\n\
+                   y                                                  0 \t(obj:0)\n";
+        let solution = Solution::<BigRational>::parse(sol.as_bytes()).unwrap();
+
+        assert!(solution.has(&x));
+        assert!(!solution.has(&z));
+        assert_eq!(solution.try_get_value(c(2) * &x + &y), Ok(c(6).0));
+        assert_eq!(
+            solution.try_get_value(&x + &z - &z * c(2).0),
+            Err(MissingVariables(vec!["z".to_string()]))
+        );
+        assert_eq!(
+            MissingVariables(vec!["z".to_string(), "w".to_string()]).to_string(),
+            "no value for `z`, `w`"
+        );
+
+        assert_eq!(solution.missing_lookups(), 0);
+        assert_eq!(solution.get_value(&x + &z), c(3).0);
+        assert_eq!(solution.get_value(z), c(0).0);
+        assert_eq!(solution.missing_lookups(), 2);
+    }
+
+    #[test]
+    fn test_get_values() {
+        let mut model = Model::<BigRational>::new();
+        let x = (0..10_000)
+            .map(|i| model.add_var().name(format!("x{i}")).build())
+            .collect::<Vec<_>>();
+        // big numerators, as left by long exact solves
+        let big = r(i64::MAX, 7) * r(i64::MAX, 11);
+        let values = (0..10_000)
+            .map(|i| (format!("x{i}"), big.clone() + r(i, 3)))
+            .collect();
+        let solution = Solution::new(SolveStatus::Optimal, None, values);
+        let exprs = (0..10_000)
+            .map(|i| &x[i] - &x[(i + 1) % 10_000] + c(3) * &x[(i * 7) % 10_000])
+            .collect::<Vec<_>>();
+
+        let start = std::time::Instant::now();
+        let values = solution.get_values(&exprs);
+        assert!(start.elapsed().as_secs() < 10, "{:?}", start.elapsed());
+        assert_eq!(values.len(), 10_000);
+        assert_eq!(values[0], big.clone() * c(3).0 - r(1, 3));
+        assert_eq!(values[1], big * c(3).0 + r(1 - 2 + 21, 3));
+        assert_eq!(solution.get_value_ref(&exprs[0]), values[0]);
+        assert_eq!(solution.get_value(exprs[1].clone()), values[1]);
+        assert_eq!(solution.missing_lookups(), 0);
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let optimal = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.objective_value(), Some(&r(33, 1)));
+        let infeasible = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/infeasible.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(infeasible.status(), SolveStatus::Infeasible);
+        assert_eq!(infeasible.objective_value(), None);
+        let unbounded = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/unbounded.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(unbounded.status(), SolveStatus::Unbounded);
+        let time_limit = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/time_limit.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(time_limit.status(), SolveStatus::TimeLimit);
+        assert_eq!(time_limit.values.len(), 2);
+
+        let optimal =
+            Solution::<f64>::parse(include_str!("../tests/fixtures/optimal.sol").as_bytes())
+                .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.values["x"], 4.0);
+        let infeasible =
+            Solution::<f64>::parse(include_str!("../tests/fixtures/infeasible.sol").as_bytes())
+                .unwrap();
+        assert_eq!(infeasible.status(), SolveStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_gap() {
+        let bounded = |primal: Option<BigRational>, dual| {
+            Solution::new(SolveStatus::Unknown, primal.clone(), Default::default())
+                .with_bounds(primal, dual)
+        };
+        assert_eq!(
+            bounded(Some(r(98, 1)), Some(r(112, 1))).gap(),
+            Some(r(1, 7))
+        );
+        assert_eq!(
+            bounded(Some(r(-112, 1)), Some(r(-98, 1))).gap(),
+            Some(r(1, 7))
+        );
+        assert_eq!(bounded(Some(r(7, 3)), Some(r(7, 3))).gap(), Some(r(0, 1)));
+        assert_eq!(bounded(Some(r(0, 1)), Some(r(0, 1))).gap(), Some(r(0, 1)));
+        assert_eq!(bounded(Some(r(-1, 1)), Some(r(2, 1))).gap(), None);
+        assert_eq!(bounded(Some(r(0, 1)), Some(r(2, 1))).gap(), None);
+        assert_eq!(bounded(None, Some(r(2, 1))).gap(), None);
+    }
+
+    #[test]
+    fn test_rounded() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").integer().build();
+        let y = model.add_var().name("y").binary().build();
+        let z = model.add_var().name("z").build();
+        let values = [("x", 2.9999999998), ("y", 1e-13), ("z", 0.5)]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        let solution = Solution::new(SolveStatus::Optimal, Some(3.5), values);
+
+        assert_eq!(solution.integrality_violations(&model, 1e-6), []);
+        let rounded = solution.rounded(&model, 1e-6).unwrap();
+        assert_eq!(rounded.get_value(&x), 3.0);
+        assert_eq!(rounded.get_value(&y), 0.0);
+        assert_eq!(rounded.get_value(&z), 0.5);
+        assert_eq!(rounded.objective_value(), Some(&3.5));
+
+        let violations = solution.integrality_violations(&model, 1e-12);
+        assert_eq!(
+            violations,
+            [IntegralityError {
+                variable: "x".to_string(),
+                value: 2.9999999998,
+            }]
+        );
+        assert_eq!(
+            solution.rounded(&model, 1e-12).err().as_ref(),
+            violations.first()
+        );
+
+        let values = [("x", 2.4), ("y", f64::NAN)]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        let solution = Solution::new(SolveStatus::Optimal, None, values);
+        let violations = solution.integrality_violations(&model, 0.1);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| v.variable.as_str())
+                .collect::<Vec<_>>(),
+            ["x", "y"]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "integral variable `x` has value 2.4"
+        );
+    }
+
+    #[test]
+    fn test_iter() {
+        let sol = "solution status: optimal solution found\n\
+                   objective value: 3\n\
+                   z 3 \t(obj:1)\n\
+                   x 0 \t(obj:0)\n\
+                   y -1/2 \t(obj:0)\n";
+        let solution = Solution::<BigRational>::parse(sol.as_bytes()).unwrap();
+        assert_eq!(solution.len(), 3);
+        assert_eq!(
+            solution.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["x", "y", "z"]
+        );
+        assert_eq!(
+            (&solution).into_iter().collect::<Vec<_>>(),
+            [("x", &r(0, 1)), ("y", &r(-1, 2)), ("z", &r(3, 1))]
+        );
+        assert_eq!(
+            solution.nonzero().collect::<Vec<_>>(),
+            [("y", &r(-1, 2)), ("z", &r(3, 1))]
+        );
+
+        assert_eq!(solution.get("x"), Some(&r(0, 1)));
+        assert_eq!(solution.get("w"), None);
+    }
+
+    #[test]
+    fn test_parse_highs() {
+        let optimal = Solution::<f64>::parse_highs(
+            include_str!("../tests/fixtures/highs_optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.objective_value(), Some(&33.0));
+        assert_eq!(optimal.values.len(), 2);
+        assert_eq!(optimal.values["x"], 4.0);
+        assert_eq!(optimal.values["y"], 5.0);
+
+        let infeasible = Solution::<f64>::parse_highs(
+            include_str!("../tests/fixtures/highs_infeasible.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(infeasible.status(), SolveStatus::Infeasible);
+        assert!(infeasible.values.is_empty());
+    }
+
+    #[test]
+    fn test_parse_cbc() {
+        let optimal = Solution::<f64>::parse_cbc(
+            include_str!("../tests/fixtures/cbc_optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.objective_value(), Some(&33.0));
+        assert_eq!(optimal.values.len(), 2);
+        assert_eq!(optimal.values["x"], 4.0);
+        assert_eq!(optimal.values["y"], 5.0);
+
+        let stopped = Solution::<f64>::parse_cbc(
+            "Stopped on time - objective value 12.5\n** 1 y 2.5 0\n".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(stopped.status(), SolveStatus::TimeLimit);
+        assert_eq!(stopped.values["y"], 2.5);
+
+        let infeasible =
+            Solution::<f64>::parse_cbc("Infeasible - objective value 0.00000000\n".as_bytes())
+                .unwrap();
+        assert_eq!(infeasible.status(), SolveStatus::Infeasible);
+        assert!(infeasible.values.is_empty());
+    }
+
+    #[test]
+    fn test_parse_cplex() {
+        let optimal = Solution::<f64>::parse_cplex(
+            include_str!("../tests/fixtures/cplex_optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.objective_value(), Some(&33.0));
+        assert_eq!(optimal.values.len(), 2);
+        assert_eq!(optimal.values["x"], 4.0);
+        assert_eq!(optimal.values["y"], 5.0);
+
+        let stopped = Solution::<f64>::parse_cplex(
+            "<CPLEXSolution>\n <header objectiveValue=\"12.5\" solutionStatusValue=\"107\"/>\n              <variables>\n  <variable name=\"a&amp;b\" index=\"0\" value=\"2.5\"/>\n              </variables>\n</CPLEXSolution>\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(stopped.status(), SolveStatus::TimeLimit);
+        assert_eq!(stopped.values["a&b"], 2.5);
+
+        assert!(matches!(
+            Solution::<f64>::parse_cplex(r#"<variable name="x" value="four"/>"#.as_bytes()),
+            Err(SolveError::SolutionParse(_))
+        ));
+        assert_eq!(
+            SolveStatus::from_cplex_log("Presolve time = 0.00 sec.\nMIP - Integer infeasible.\n"),
+            SolveStatus::Infeasible
+        );
+        assert_eq!(
+            SolveStatus::from_cplex_log("Dual simplex - Dual infeasible:  Problem unbounded.\n"),
+            SolveStatus::Unbounded
+        );
+        assert_eq!(
+            SolveStatus::from_cplex_log("MIP - Integer infeasible or unbounded.\n"),
+            SolveStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_parse_gurobi() {
+        let optimal = Solution::<f64>::parse_gurobi(
+            include_str!("../tests/fixtures/gurobi_optimal.sol").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Unknown);
+        assert_eq!(optimal.objective_value(), Some(&33.0));
+        assert_eq!(optimal.values.len(), 2);
+        assert_eq!(optimal.values["x"], 4.0);
+        assert_eq!(optimal.values["y"], 5.0);
+
+        assert!(matches!(
+            Solution::<f64>::parse_gurobi("x 1 2\n".as_bytes()),
+            Err(SolveError::SolutionParse(_))
+        ));
+        assert_eq!(
+            SolveStatus::from_gurobi_log(include_str!(
+                "../tests/fixtures/gurobi_infeasible_log.txt"
+            )),
+            SolveStatus::Infeasible
+        );
+        assert_eq!(
+            SolveStatus::from_gurobi_log(
+                "Optimal solution found (tolerance 1.00e-04)\nBest objective 3.3e+01\n"
+            ),
+            SolveStatus::Optimal
+        );
+        assert_eq!(
+            SolveStatus::from_gurobi_log("Time limit reached\nBest objective 1.2e+01\n"),
+            SolveStatus::TimeLimit
+        );
+    }
+
+    #[test]
+    fn test_parse_glpk() {
+        let (optimal, columns) = Solution::<f64>::parse_glpk_report(
+            include_str!("../tests/fixtures/glpk_optimal.txt").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(optimal.status(), SolveStatus::Optimal);
+        assert_eq!(optimal.objective_value(), Some(&33.0));
+        assert_eq!(optimal.values["x"], 4.0);
+        assert_eq!(optimal.values["y"], 5.0);
+        assert_eq!(columns, ["x", "y"]);
+        let values = Solution::<f64>::parse_glpk_values(
+            include_str!("../tests/fixtures/glpk_optimal.sol").as_bytes(),
+            &columns,
+        )
+        .unwrap();
+        assert_eq!(values, Some(optimal.values.clone()));
+
+        let (mip, columns) = Solution::<f64>::parse_glpk_report(
+            include_str!("../tests/fixtures/glpk_mip.txt").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(mip.status(), SolveStatus::Optimal);
+        assert_eq!(mip.objective_value(), Some(&1234570.0));
+        assert_eq!(mip.values["production_volume"], 1234570.0);
+        assert_eq!(mip.values["y"], 3.0);
+        assert_eq!(columns, ["production_volume", "y"]);
+        let values = Solution::<f64>::parse_glpk_values(
+            include_str!("../tests/fixtures/glpk_mip.sol").as_bytes(),
+            &columns,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(values["production_volume"], 1234567.0);
+        assert_eq!(values["y"], 3.0);
+        assert!(
+            Solution::<f64>::parse_glpk_values("j 3 1\n".as_bytes(), &columns)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            Solution::<f64>::parse_glpk_values("s mip 1 2 o 1\nj 3 1\n".as_bytes(), &columns)
+                .is_err()
+        );
+
+        let infeasible = Solution::<f64>::parse_glpk(
+            include_str!("../tests/fixtures/glpk_infeasible.txt").as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(infeasible.status(), SolveStatus::Infeasible);
+        assert_eq!(infeasible.objective_value(), None);
+        assert!(infeasible.values.is_empty());
+
+        for (status, expected) in [
+            ("INTEGER EMPTY", SolveStatus::Infeasible),
+            ("UNBOUNDED", SolveStatus::Unbounded),
+            ("INTEGER NON-OPTIMAL", SolveStatus::Unknown),
+            ("UNDEFINED", SolveStatus::Unknown),
+        ] {
+            let report = format!("Status:     {status}\nObjective:  12.5 (MINimum)\n");
+            let solution = Solution::<f64>::parse_glpk(report.as_bytes()).unwrap();
+            assert_eq!(solution.status(), expected);
+            let has_solution = status == "INTEGER NON-OPTIMAL";
+            assert_eq!(solution.objective_value().is_some(), has_solution);
+        }
+        assert!(Solution::<f64>::parse_glpk(
+            "   No. Column name\n------\n     1 x            B            abc\n".as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_csv() {
+        let solution = Solution::<BigRational>::parse(
+            include_str!("../tests/fixtures/exact_values.sol").as_bytes(),
+        )
+        .unwrap();
+        let mut csv = Vec::new();
+        solution.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("variable,value\n"), "{csv}");
+        let read = Solution::<BigRational>::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(read.values, solution.values);
+        assert_eq!(read.status(), SolveStatus::Unknown);
+
+        let mut solution = Solution::<f64>::empty();
+        solution.values.insert("x".to_string(), 0.1);
+        solution.values.insert("a,\"b\"".to_string(), -2.5e-30);
+        let mut csv = Vec::new();
+        solution.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv,
+            "variable,value\n\"a,\"\"b\"\"\",-0.0000000000000000000000000000025\nx,0.1\n"
+        );
+        let read = Solution::<f64>::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(read.values, solution.values);
+
+        assert!(matches!(
+            Solution::<f64>::from_csv("variable,value\nx;1\n".as_bytes()),
+            Err(SolveError::SolutionParse(line)) if line == "x;1"
+        ));
+        assert!(matches!(
+            Solution::<f64>::from_csv("name,value\n".as_bytes()),
+            Err(SolveError::SolutionParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_sol() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let z = model.add_var().name("z").build();
+        let values = [("x", r(1, 3)), ("y", r(-22, 7))]
+            .map(|(name, value)| (name.to_string(), value))
+            .into();
+        let solution = Solution::new(SolveStatus::Optimal, Some(r(5, 2)), values);
+
+        let mut sol = Vec::new();
+        model
+            .write_sol(&solution, &mut sol, MissingSolutionValues::Omit)
+            .unwrap();
+        let sol = String::from_utf8(sol).unwrap();
+        assert_eq!(sol, "objective value: 5/2\nx 1/3\ny -22/7\n");
+        let read = Solution::<BigRational>::parse_with_policy(
+            sol.as_bytes(),
+            ImportPolicy::RequireFractions,
+        )
+        .unwrap();
+        assert_eq!(read.values, solution.values);
+        assert_eq!(read.objective_value(), Some(&r(5, 2)));
+        assert!(!read.has(&z));
+
+        let mut sol = Vec::new();
+        model
+            .write_sol(&solution, &mut sol, MissingSolutionValues::Zero)
+            .unwrap();
+        let read = Solution::<BigRational>::parse(sol.as_slice()).unwrap();
+        assert_eq!(read.get_value(x), r(1, 3));
+        assert_eq!(read.get_value(y), r(-22, 7));
+        assert_eq!(read.try_get_value(z), Ok(c(0).0));
+
+        let mut model = Model::<f64>::new();
+        model.add_var().name("a").build();
+        model.add_var().name("b").build();
+        model.add_var().name("c").build();
+        let values = [("a", 0.1), ("b", -2.5e-300), ("c", 1.0 / 3.0)]
+            .map(|(name, value)| (name.to_string(), value))
+            .into();
+        let solution = Solution::new(SolveStatus::Unknown, None, values);
+        let mut sol = Vec::new();
+        model
+            .write_sol(&solution, &mut sol, MissingSolutionValues::Omit)
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&sol).contains("objective"));
+        let read = Solution::<f64>::parse(sol.as_slice()).unwrap();
+        assert_eq!(read.values, solution.values);
+    }
+}
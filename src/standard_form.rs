@@ -0,0 +1,324 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use num::{Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintSense},
+    expression::Expression,
+    merge::unused_name,
+    options::EqualityRows,
+    quadratic::QuadExpression,
+    solution::Solution,
+    variable::Variable,
+    InternalConstraint, Model, VariableType,
+};
+
+/**
+ * How the variables of a model are expressed in its standard form, from
+ * `Model::to_standard_form`
+ */
+#[derive(Clone, Debug)]
+pub struct StandardFormMap<N>
+where
+    N: Num + Clone,
+{
+    /// Name and value in terms of the standard-form variables of every
+    /// variable, by id, `None` for removed ones.
+    variables: Vec<Option<(String, Expression<N>)>>,
+}
+
+impl<N> StandardFormMap<N>
+where
+    N: Num + Clone,
+{
+    /// The value of `v` in terms of the standard-form variables, `None` if
+    /// `v` was removed from the model.
+    pub fn expression(&self, v: &Variable<N>) -> Option<&Expression<N>> {
+        let (_, e) = self.variables.get(v.id())?.as_ref()?;
+        Some(e)
+    }
+
+    /// Solution of the original model from one of the standard form, with
+    /// the same status, objective value and bounds. Variables whose
+    /// standard-form variables have no value are left out.
+    pub fn recover(&self, sol: &Solution<N>) -> Solution<N> {
+        let values = self
+            .variables
+            .iter()
+            .flatten()
+            .filter_map(|(name, e)| Some((name.clone(), sol.try_get_value(e.clone()).ok()?)))
+            .collect();
+        Solution::new(sol.status(), sol.objective_value().cloned(), values)
+            .with_log(sol.solver_log().to_string())
+            .with_bounds(sol.primal_bound().cloned(), sol.dual_bound().cloned())
+            .with_approximate(sol.is_approximate())
+    }
+}
+
+/// Smallest integer at least `x`.
+fn ceil<N>(x: &N) -> N
+where
+    N: Num + Clone + PartialOrd,
+{
+    // the remainder has the sign of `x`
+    let fraction = x.clone() % N::one();
+    if fraction > N::zero() {
+        x.clone() - fraction + N::one()
+    } else {
+        x.clone() - fraction
+    }
+}
+
+/// `e` with every variable replaced by its expression in `variables`.
+fn replace<N>(e: &Expression<N>, variables: &[Option<(String, Expression<N>)>]) -> Expression<N>
+where
+    N: Num + Clone,
+{
+    let mut replaced = Expression::default();
+    for (w, v) in &e.0 {
+        match v {
+            Some(v) => {
+                let (_, x) = variables[v.id()].as_ref().expect("variable is live");
+                replaced += x.clone() * w.clone();
+            }
+            None => replaced.0.push((w.clone(), None)),
+        }
+    }
+    replaced
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// `to_standard_form_with(EqualityRows::Split)`.
+    pub fn to_standard_form(&self) -> (Model<N>, StandardFormMap<N>) {
+        self.to_standard_form_with(EqualityRows::Split)
+    }
+
+    /// Equivalent model whose constraints are all `<=` and whose variables
+    /// are all at least zero, with the same objective and direction.
+    /// Variables with a lower bound are shifted by it, others become the
+    /// difference of `{name}_pos` and `{name}_neg`, and upper bounds become
+    /// `{name}_ub` rows. `>=` rows are negated, and ranges and equalities
+    /// that are split become `{name}_lb` and `{name}_ub` or `{name}_le` and
+    /// `{name}_ge` rows. Binary and semi-continuous variables are kept
+    /// as they are, integer variables stay integer and are shifted by their
+    /// lower bound rounded up, and objectives added with `add_objective` are
+    /// left out. Rows keep the group of their constraint.
+    pub fn to_standard_form_with(
+        &self,
+        equalities: EqualityRows,
+    ) -> (Model<N>, StandardFormMap<N>) {
+        let mut standard = Model::new();
+        standard.solver_paths = self.solver_paths.clone();
+        standard.solver_args = self.solver_args.clone();
+        standard.commands = self.commands.clone();
+        standard.params = self.params.clone();
+        standard.exact = self.exact;
+        standard.disabled_groups = self.disabled_groups.clone();
+        standard.direction = self.direction;
+
+        let mut variables = vec![None; self.variables.len()];
+        let mut bounds = vec![];
+        for (i, v) in self.live_variables() {
+            let name = self.variable_name(i);
+            let expression = match v.v_type {
+                VariableType::Binary | VariableType::SemiContinuous | VariableType::SemiInteger => {
+                    let mut builder = standard.add_var().name(name.clone());
+                    builder = match v.v_type {
+                        VariableType::Binary => builder.binary(),
+                        VariableType::SemiContinuous => builder.semi_continuous(),
+                        _ => builder.semi_integer(),
+                    };
+                    builder = match v.lb.finite() {
+                        Some(lb) => builder.lb(lb.clone()),
+                        None => builder.lb_neg_inf(),
+                    };
+                    if let Some(ub) = v.ub.finite() {
+                        builder = builder.ub(ub.clone());
+                    }
+                    Expression::from(builder.build())
+                }
+                v_type => {
+                    let nonnegative = |standard: &mut Model<N>, name: String| {
+                        let builder = standard.add_var().name(name).lb(N::zero());
+                        match v_type {
+                            VariableType::Integer => builder.integer(),
+                            _ => builder,
+                        }
+                        .build()
+                    };
+                    let expression = match v.lb.finite() {
+                        // integral values of the shifted variable stay integral
+                        Some(lb) if v_type == VariableType::Integer => {
+                            Expression::from(nonnegative(&mut standard, name.clone()))
+                                + Expression::from(ceil(lb))
+                        }
+                        Some(lb) => {
+                            Expression::from(nonnegative(&mut standard, name.clone()))
+                                + Expression::from(lb.clone())
+                        }
+                        None => {
+                            let pos = nonnegative(&mut standard, format!("{name}_pos"));
+                            let neg = nonnegative(&mut standard, format!("{name}_neg"));
+                            Expression::from(pos) - Expression::from(neg)
+                        }
+                    };
+                    if let Some(ub) = v.ub.finite() {
+                        bounds.push((
+                            format!("{name}_ub"),
+                            expression.clone().le(Expression::from(ub.clone())),
+                        ));
+                    }
+                    expression
+                }
+            };
+            variables[i] = Some((name, expression));
+        }
+
+        let terms = self.full_objective_terms();
+        let mut objective = QuadExpression::from(
+            replace(terms.linear(), &variables) + Expression::from(self.objective_constant()),
+        );
+        for (w, a, b) in terms.quadratic_terms() {
+            let a = replace(&Expression::from(a.clone()), &variables);
+            let b = replace(&Expression::from(b.clone()), &variables);
+            objective = objective + (a * w.clone()) * b;
+        }
+        standard.set_quadratic_objective(objective);
+
+        let mut taken = BTreeSet::new();
+        let mut add = |standard: &mut Model<N>,
+                       name: String,
+                       c: Constraint<N>,
+                       indicator: Option<(Variable<N>, bool)>,
+                       group: Option<String>| {
+            let name = unused_name(&taken, name);
+            taken.insert(name.clone());
+            standard.push_const(InternalConstraint {
+                name: Some(name),
+                constraint: c.to_normalized(),
+                indicator,
+                group,
+            });
+        };
+        for (i, c) in self.live_constraints() {
+            let name = self.constraint_name(i);
+            let group = &c.group;
+            let indicator = c.indicator.as_ref().map(|(y, active)| {
+                let (_, y) = variables[y.id()].as_ref().expect("indicator is live");
+                let (_, y) = y.terms().next().expect("binary variables are kept");
+                (y.clone(), *active)
+            });
+            let row = c.constraint.normalized();
+            let lhs = replace(&row.lhs, &variables);
+            let rhs = row.rhs.constant();
+            let le = |lhs: &Expression<N>, rhs: N| lhs.clone().le(Expression::from(rhs));
+            let ge = |lhs: &Expression<N>, rhs: N| (-lhs.clone()).le(Expression::from(-rhs));
+            let rows = match row.sense {
+                ConstraintSense::Le => vec![(name, le(&lhs, rhs))],
+                ConstraintSense::Ge => vec![(name, ge(&lhs, rhs))],
+                ConstraintSense::Eq => match equalities {
+                    EqualityRows::Keep => vec![(name, lhs.eq(Expression::from(rhs)))],
+                    EqualityRows::Split => vec![
+                        (format!("{name}_le"), le(&lhs, rhs.clone())),
+                        (format!("{name}_ge"), ge(&lhs, rhs)),
+                    ],
+                },
+                ConstraintSense::Range { lb, ub } => vec![
+                    (format!("{name}_lb"), ge(&lhs, lb)),
+                    (format!("{name}_ub"), le(&lhs, ub)),
+                ],
+            };
+            for (name, c) in rows {
+                add(&mut standard, name, c, indicator.clone(), group.clone());
+            }
+        }
+        for (name, c) in bounds {
+            add(&mut standard, name, c, None, None);
+        }
+        (standard, StandardFormMap { variables })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, rat_frac, DebugArtifacts, EqualityRows, Model};
+
+    #[test]
+    fn test_standard_form() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").lb(c(-2).0).ub(c(5).0).build();
+        let y = model.add_var().name("y").lb_neg_inf().ub(c(3).0).build();
+        let z = model.add_var().name("z").lb(c(1).0).integer().build();
+        model.set_objective(&x - c(2) * &y + &z + c(1).0);
+        model.add_const_named("sum", (&x + &y).ge(c(-4).0)).unwrap();
+        model
+            .add_const_named("link", (&y - &z).eq(rat_frac(1, 2)))
+            .unwrap();
+        model
+            .add_const_named("band", (&x + &z).between(c(-1).0, c(6).0))
+            .unwrap();
+
+        let (standard, map) = model.to_standard_form();
+        assert_eq!(
+            standard.to_lp_string().unwrap(),
+            "Minimize\n obj: x - 2 y_pos + 2 y_neg + z\n\
+             Subject To\n \
+             sum: -x - y_pos + y_neg <= 2\n \
+             link_le: y_pos - y_neg - z <= 3/2\n \
+             link_ge: -y_pos + y_neg + z <= -3/2\n \
+             band_lb: -x - z <= 0\n \
+             band_ub: x + z <= 7\n \
+             x_ub: x <= 7\n \
+             y_ub: y_pos - y_neg <= 3\n\
+             Bounds\n 0 <= x <= +inf\n 0 <= y_pos <= +inf\n 0 <= y_neg <= +inf\n 0 <= z <= +inf\n\
+             General\n z\nBinary\nEnd\n"
+        );
+        assert_eq!(map.expression(&x).unwrap().to_string(), "x - 2");
+
+        let (kept, _) = model.to_standard_form_with(EqualityRows::Keep);
+        let lp = kept.to_lp_string().unwrap();
+        assert!(lp.contains(" link: y_pos - y_neg - z = 3/2\n"), "{lp}");
+
+        let solution = model.solve(DebugArtifacts::None).unwrap();
+        let recovered = map.recover(&standard.solve(DebugArtifacts::None).unwrap());
+        assert_eq!(recovered.objective_value(), solution.objective_value());
+        for v in ["x", "y", "z"] {
+            assert_eq!(recovered.get(v), solution.get(v), "{v}");
+        }
+    }
+
+    #[test]
+    fn test_fractional_integer_bound() {
+        let mut model = Model::<BigRational>::new();
+        let n = model
+            .add_var()
+            .name("n")
+            .integer()
+            .lb(rat_frac(3, 2))
+            .ub(c(4).0)
+            .build();
+        let m = model
+            .add_var()
+            .name("m")
+            .integer()
+            .lb(rat_frac(-5, 2))
+            .build();
+        model.set_objective(&n + &m);
+
+        let (standard, map) = model.to_standard_form();
+        assert_eq!(map.expression(&n).unwrap().to_string(), "n + 2");
+        assert_eq!(map.expression(&m).unwrap().to_string(), "m - 2");
+        assert_eq!(
+            standard.to_lp_string().unwrap(),
+            "Minimize\n obj: n + m\n\
+             Subject To\n n_ub: n <= 2\n\
+             Bounds\n 0 <= n <= +inf\n 0 <= m <= +inf\n\
+             General\n n\n m\nBinary\nEnd\n"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use std::time::Duration;
+
+use num::Num;
+
+use crate::{Model, VariableType};
+
+/// Magnitude from which SCIP prints values as infinite.
+const SCIP_INFINITY: f64 = 1e20;
+
+/**
+ * Size of a model, as reported by `Model::stats`
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModelStats {
+    pub variables: usize,
+    pub binary_variables: usize,
+    /// Integer and semi-integer variables.
+    pub integer_variables: usize,
+    pub constraints: usize,
+    pub nonzeros: usize,
+}
+
+impl std::fmt::Display for ModelStats {
+    /// One line in the style of SCIP's problem summary, e.g. `problem has 3
+    /// variables (1 bin, 1 int, 1 cont) and 2 constraints with 5 nonzeros`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "problem has {} variables ({} bin, {} int, {} cont) and {} constraints with {} nonzeros",
+            self.variables,
+            self.binary_variables,
+            self.integer_variables,
+            self.variables - self.binary_variables - self.integer_variables,
+            self.constraints,
+            self.nonzeros
+        ))
+    }
+}
+
+/**
+ * Figures of a solver run, as reported by `Solution::stats`
+ *
+ * Apart from the wall-clock time, the fields are read from the summary SCIP
+ * prints after solving. They are `None` where the log has no such line,
+ * e.g. at a low verbosity or with another version, and bounds are `None`
+ * while SCIP reports them as infinite.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats {
+    /// Time from starting the solver until it exited.
+    pub wall_time: Duration,
+    /// `Solving Time (sec)`.
+    pub solving_time: Option<Duration>,
+    /// `Solving Nodes`, of the last run if the solve restarted.
+    pub nodes: Option<u64>,
+    pub primal_bound: Option<f64>,
+    pub dual_bound: Option<f64>,
+    /// Relative gap between the bounds, e.g. 0.1429 for `14.29 %`, and
+    /// infinite while there is no primal bound.
+    pub gap: Option<f64>,
+}
+
+impl SolveStats {
+    /// Reads the solve summary from the log of a SCIP run that took
+    /// `wall_time`. The last summary wins if there are several.
+    pub(crate) fn from_scip_log(log: &str, wall_time: Duration) -> Self {
+        let mut stats = SolveStats {
+            wall_time,
+            ..Default::default()
+        };
+        for line in log.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            // values can be followed by notes like `(3 solutions)`
+            let value = value.split_whitespace().next();
+            match key.trim() {
+                "Solving Time (sec)" => {
+                    stats.solving_time = value
+                        .and_then(|t| t.parse().ok())
+                        .and_then(|t| Duration::try_from_secs_f64(t).ok())
+                }
+                "Solving Nodes" => stats.nodes = value.and_then(|n| n.parse().ok()),
+                "Primal Bound" => stats.primal_bound = value.and_then(parse_bound),
+                "Dual Bound" => stats.dual_bound = value.and_then(parse_bound),
+                "Gap" => {
+                    stats.gap = match value {
+                        Some("infinite") => Some(f64::INFINITY),
+                        value => value.and_then(|g| g.parse::<f64>().ok()).map(|g| g / 100.0),
+                    }
+                }
+                _ => {}
+            }
+        }
+        stats
+    }
+}
+
+/// A bound as SCIP prints it, e.g. `+3.30000000000000e+01`, if finite.
+fn parse_bound(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|b| b.abs() < SCIP_INFINITY)
+}
+
+/// `Primal Bound` and `Dual Bound` of the last summary in a SCIP log, read
+/// with `parse`, each `None` while SCIP reports it as infinite.
+pub(crate) fn scip_bounds<N>(
+    log: &str,
+    parse: impl Fn(&str) -> Option<N>,
+) -> (Option<N>, Option<N>) {
+    let mut bounds = (None, None);
+    for line in log.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value
+            .split_whitespace()
+            .next()
+            .filter(|b| parse_bound(b).is_some());
+        match key.trim() {
+            "Primal Bound" => bounds.0 = value.and_then(&parse),
+            "Dual Bound" => bounds.1 = value.and_then(&parse),
+            _ => {}
+        }
+    }
+    bounds
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone,
+{
+    /// Number of variables, not counting eliminated ones.
+    pub fn num_vars(&self) -> usize {
+        self.live_variables().count()
+    }
+
+    /// Number of constraints, not counting removed ones.
+    pub fn num_constraints(&self) -> usize {
+        self.live_constraints().count()
+    }
+
+    /// Number of integer and semi-integer variables.
+    pub fn num_integer_vars(&self) -> usize {
+        self.live_variables()
+            .filter(|(_, v)| matches!(v.v_type, VariableType::Integer | VariableType::SemiInteger))
+            .count()
+    }
+
+    pub fn num_binary_vars(&self) -> usize {
+        self.live_variables()
+            .filter(|(_, v)| matches!(v.v_type, VariableType::Binary))
+            .count()
+    }
+
+    /// Number of nonzero coefficients over all constraints, with the terms
+    /// of a variable on either side of a constraint merged first.
+    pub fn num_nonzeros(&self) -> usize {
+        self.live_constraints()
+            .map(|(_, c)| {
+                let mut row = c.constraint.lhs.clone() - c.constraint.rhs.clone();
+                row.simplify();
+                row.num_terms()
+            })
+            .sum()
+    }
+
+    pub fn stats(&self) -> ModelStats {
+        ModelStats {
+            variables: self.num_vars(),
+            binary_variables: self.num_binary_vars(),
+            integer_variables: self.num_integer_vars(),
+            constraints: self.num_constraints(),
+            nonzeros: self.num_nonzeros(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use std::time::Duration;
+
+    use super::{scip_bounds, ModelStats, SolveStats};
+    use crate::{
+        c,
+        solution::{parse_f64, parse_rational},
+        Model,
+    };
+
+    #[test]
+    fn test_stats() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").integer().build();
+        let b = model.add_var().name("b").binary().build();
+        let s = model.add_var().semi_integer().ub(c(4).0).build();
+        let z = model.add_var().name("z").free().build();
+
+        // x appears twice and y cancels out
+        model.add_const((&x + &y + c(2) * &x).le(&y + c(3).0));
+        model.add_const((&b + &s + &z).ge(c(1).0));
+        let removed = model.add_const((&x + &z).eq(c(0).0));
+        model
+            .add_indicator(b.clone(), true, (&x + &s).le(c(2).0))
+            .unwrap();
+        model.remove_const(removed);
+        model.substitute_variable(&z, &(c(2) * &x)).unwrap();
+
+        let stats = model.stats();
+        assert_eq!(
+            stats,
+            ModelStats {
+                variables: 4,
+                binary_variables: 1,
+                integer_variables: 2,
+                constraints: 3,
+                nonzeros: 1 + 3 + 2,
+            }
+        );
+        assert_eq!(model.num_vars(), 4);
+        assert_eq!(model.num_constraints(), 3);
+        assert_eq!(model.num_integer_vars(), 2);
+        assert_eq!(model.num_binary_vars(), 1);
+        assert_eq!(model.num_nonzeros(), 6);
+        assert_eq!(
+            stats.to_string(),
+            "problem has 4 variables (1 bin, 2 int, 1 cont) and 3 constraints with 6 nonzeros"
+        );
+    }
+
+    #[test]
+    fn test_scip_log_stats() {
+        let wall_time = Duration::from_millis(20);
+        let stats =
+            SolveStats::from_scip_log(include_str!("../tests/fixtures/scip6_log.txt"), wall_time);
+        assert_eq!(
+            stats,
+            SolveStats {
+                wall_time,
+                solving_time: Some(Duration::from_millis(10)),
+                nodes: Some(1),
+                primal_bound: Some(33.0),
+                dual_bound: Some(33.0),
+                gap: Some(0.0),
+            }
+        );
+
+        let stats = SolveStats::from_scip_log(
+            include_str!("../tests/fixtures/scip9_time_limit_log.txt"),
+            wall_time,
+        );
+        assert_eq!(stats.solving_time, Some(Duration::from_secs(10)));
+        assert_eq!(stats.nodes, Some(18243));
+        assert_eq!(stats.primal_bound, Some(98.0));
+        assert_eq!(stats.dual_bound, Some(112.0));
+        assert!((stats.gap.unwrap() - 0.1429).abs() < 1e-12);
+
+        let stats = SolveStats::from_scip_log(
+            include_str!("../tests/fixtures/scip9_no_solution_log.txt"),
+            wall_time,
+        );
+        assert_eq!((stats.primal_bound, stats.dual_bound), (None, None));
+        assert_eq!(stats.gap, Some(f64::INFINITY));
+
+        let bounds = scip_bounds(
+            include_str!("../tests/fixtures/scip9_time_limit_log.txt"),
+            parse_rational,
+        );
+        assert_eq!(bounds, (Some(c(98).0), Some(c(112).0)));
+        let bounds = scip_bounds(
+            include_str!("../tests/fixtures/scip9_no_solution_log.txt"),
+            parse_f64,
+        );
+        assert_eq!(bounds, (None, None));
+
+        // a quiet SCIP prints no summary
+        assert_eq!(
+            SolveStats::from_scip_log("SCIP> quit\n", wall_time),
+            SolveStats {
+                wall_time,
+                ..Default::default()
+            }
+        );
+    }
+}
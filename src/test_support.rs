@@ -0,0 +1,15 @@
+//! Helpers shared by the unit tests.
+
+use std::{
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+/// Writes an executable shell script with `body` to `dir`, to stand in for
+/// a solver.
+pub(crate) fn write_stub(dir: &Path, body: &str) -> PathBuf {
+    let stub = dir.join("solver-stub");
+    std::fs::write(&stub, format!("#!/bin/sh\n{body}")).unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+    stub
+}
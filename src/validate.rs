@@ -0,0 +1,503 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use num::{BigRational, Num, Signed};
+
+use crate::{
+    constraint::{Constraint, ConstraintId, ConstraintSense},
+    error::ModelError,
+    expression::Expression,
+    variable::Variable,
+    Model,
+};
+
+/**
+ * Warnings about a model from `Model::validation_report`
+ *
+ * Unlike the errors of `Model::validate` none of them stops a solve, but
+ * they usually point at a mistake where the model is generated.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationReport<N>
+where
+    N: Num + Clone,
+{
+    unused_variables: Vec<Variable<N>>,
+    empty_constraints: Vec<ConstraintId>,
+    duplicate_names: Vec<String>,
+}
+
+impl<N> ValidationReport<N>
+where
+    N: Num + Clone,
+{
+    /// Variables that appear nowhere, see `Model::unused_variables`.
+    pub fn unused_variables(&self) -> &[Variable<N>] {
+        &self.unused_variables
+    }
+
+    /// Constraints without variables once repeated terms are merged, which
+    /// always or never hold.
+    pub fn empty_constraints(&self) -> &[ConstraintId] {
+        &self.empty_constraints
+    }
+
+    /// Names written to model files for more than one constraint or more
+    /// than one variable, e.g. a constraint named `c1` next to an unnamed
+    /// one with index 1, in order.
+    pub fn duplicate_names(&self) -> &[String] {
+        &self.duplicate_names
+    }
+
+    /// Whether there is nothing to warn about.
+    pub fn is_empty(&self) -> bool {
+        self.unused_variables.is_empty()
+            && self.empty_constraints.is_empty()
+            && self.duplicate_names.is_empty()
+    }
+}
+
+/// Whether `w` is infinite or NaN: `w - w` is NaN for those and zero for
+/// any other number, whatever the coefficient type.
+pub(crate) fn is_non_finite<N>(w: &N) -> bool
+where
+    N: Num + Clone,
+{
+    !(w.clone() - w.clone()).is_zero()
+}
+
+/// Whether `w` is infinite, non-finite but not NaN.
+pub(crate) fn is_infinite<N>(w: &N) -> bool
+where
+    N: Num + Clone,
+{
+    is_non_finite(w) && !is_nan(w)
+}
+
+/// Whether `w` is NaN, the only number that differs from itself.
+#[allow(clippy::eq_op)]
+pub(crate) fn is_nan<N>(w: &N) -> bool
+where
+    N: PartialEq,
+{
+    w != w
+}
+
+/// Terms of `expr` with an infinite or NaN coefficient, by their variable
+/// or as `constant`, as coefficients need not be printable where
+/// expressions are added.
+fn non_finite_terms<N>(expr: &Expression<N>) -> Vec<String>
+where
+    N: Num + Clone,
+{
+    expr.0
+        .iter()
+        .filter(|(w, _)| is_non_finite(w))
+        .map(|(_, v)| match v {
+            Some(v) => v.to_string(),
+            None => "constant".to_string(),
+        })
+        .collect()
+}
+
+/// `ModelError::NonFiniteCoefficients` for `terms` of `row`, unless there
+/// are none.
+fn check_terms(row: impl FnOnce() -> String, terms: Vec<String>) -> Result<(), ModelError> {
+    if terms.is_empty() {
+        Ok(())
+    } else {
+        Err(ModelError::NonFiniteCoefficients { row: row(), terms })
+    }
+}
+
+/// Fails if `w`, the coefficient of `term` in `row`, is infinite or NaN.
+pub(crate) fn check_coefficient<N>(
+    row: impl FnOnce() -> String,
+    term: impl FnOnce() -> String,
+    w: &N,
+) -> Result<(), ModelError>
+where
+    N: Num + Clone,
+{
+    match is_non_finite(w) {
+        true => check_terms(row, vec![term()]),
+        false => Ok(()),
+    }
+}
+
+impl<N> Constraint<N>
+where
+    N: Num + Clone,
+{
+    /// Fails if a coefficient is infinite or NaN, or a bound of a range is
+    /// NaN, calling the constraint `row()` in the error. An infinite bound
+    /// of a range is no bound.
+    pub(crate) fn check_finite(&self, row: impl FnOnce() -> String) -> Result<(), ModelError> {
+        let mut terms = non_finite_terms(&self.lhs);
+        terms.extend(non_finite_terms(&self.rhs));
+        if let ConstraintSense::Range { lb, ub } = &self.sense {
+            let bounds = [(lb, "lower bound"), (ub, "upper bound")];
+            terms.extend(
+                bounds
+                    .into_iter()
+                    .filter(|(b, _)| is_nan(*b))
+                    .map(|(_, bound)| bound.to_string()),
+            );
+        }
+        check_terms(row, terms)
+    }
+}
+
+impl<N> Expression<N>
+where
+    N: Num + Clone,
+{
+    /// Fails if a coefficient of the objective `self` is infinite or NaN.
+    pub(crate) fn check_finite_objective(&self) -> Result<(), ModelError> {
+        check_terms(|| "the objective".to_string(), non_finite_terms(self))
+    }
+}
+
+/// Names that occur more than once in `names`.
+fn duplicates(names: impl Iterator<Item = String>) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    names.filter(|name| !seen.insert(name.clone())).collect()
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed + PartialOrd,
+{
+    /// First problem `validate` finds.
+    fn first_problem(&self) -> Result<(), ModelError> {
+        let mut terms = non_finite_terms(&self.objective_expression());
+        terms.extend(
+            self.full_objective_terms()
+                .quadratic_terms()
+                .filter(|(w, _, _)| is_non_finite(*w))
+                .map(|(_, a, b)| format!("{} * {}", a, b)),
+        );
+        check_terms(|| "the objective".to_string(), terms)?;
+
+        for (i, c) in self.live_constraints() {
+            c.constraint
+                .check_finite(|| format!("constraint `{}`", self.constraint_name(i)))?;
+        }
+
+        // bounds can have changed since the variables were built
+        for (i, v) in self.live_variables() {
+            v.check_bounds(self.variable_name(i))?;
+        }
+        Ok(())
+    }
+}
+
+impl<N> Model<N>
+where
+    N: Num + Clone + Display + Signed,
+{
+    /// Variables without a nonzero coefficient in an objective or a
+    /// constraint once repeated terms are merged, that are not the
+    /// indicator of a constraint either. Solvers report them at whatever
+    /// value they like, and free ones can even make a model unbounded in
+    /// their eyes. Constraints of disabled groups count; see
+    /// `prune_unused` to leave these variables out.
+    pub fn unused_variables(&self) -> Vec<Variable<N>> {
+        let mut used = BTreeSet::new();
+        let mut objectives = vec![self.objective_expression()];
+        objectives.extend(self.objectives.iter().map(|o| o.expression.clone()));
+        for mut objective in objectives {
+            objective.simplify();
+            used.extend(objective.terms().map(|(_, v)| v.id()));
+        }
+        for (w, a, b) in self.full_objective_terms().quadratic_terms() {
+            if !w.is_zero() {
+                used.extend([a.id(), b.id()]);
+            }
+        }
+        for (_, c) in self.live_constraints() {
+            let row = c.constraint.normalized();
+            used.extend(row.lhs.terms().map(|(_, v)| v.id()));
+            if let Some((y, _)) = &c.indicator {
+                used.insert(y.id());
+            }
+        }
+        self.live_variables()
+            .filter(|(i, _)| !used.contains(i))
+            .map(|(i, v)| Variable::new(i, v.name.clone()))
+            .collect()
+    }
+
+    /// Unused variables, empty constraints and duplicate names, which
+    /// `validate` lets through.
+    pub fn validation_report(&self) -> ValidationReport<N> {
+        let empty_constraints = self
+            .live_constraints()
+            .filter(|(_, c)| c.constraint.normalized().lhs.num_terms() == 0)
+            .map(|(i, _)| ConstraintId(i))
+            .collect();
+        let mut duplicate_names =
+            duplicates(self.live_variables().map(|(i, _)| self.variable_name(i)));
+        duplicate_names.extend(duplicates(
+            self.live_constraints()
+                .map(|(i, _)| self.constraint_name(i)),
+        ));
+        ValidationReport {
+            unused_variables: self.unused_variables(),
+            empty_constraints,
+            duplicate_names: duplicate_names.into_iter().collect(),
+        }
+    }
+}
+
+impl Model<BigRational> {
+    /// Checks the bounds of every variable as `VariableBuilder::try_build`
+    /// does, which covers models read with `from_bytes` or deserialized.
+    /// Solves do this before starting the solver.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        self.first_problem()
+    }
+}
+
+impl Model<f64> {
+    /// Checks that no coefficient, right-hand side or range is infinite or
+    /// NaN, and the bounds of every variable as `VariableBuilder::try_build`
+    /// does. Solves do this before starting the solver.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        self.first_problem()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{c, Bound, Expression, Model, ModelError, SolveError, SolveOptions};
+
+    #[test]
+    fn test_division_by_zero() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        assert_eq!(
+            (&x + c(2).0).try_div(c(0).0).err(),
+            Some(ModelError::DivisionByZero("x + 2".to_string()))
+        );
+        assert_eq!((c(3) * &x).try_div(c(2).0).unwrap().to_string(), "3/2 x");
+        let panic = std::panic::catch_unwind(|| {
+            let _ = &x / c(0).0;
+        })
+        .unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("division of an expression in `x` by zero")
+        );
+
+        let mut model = Model::<f64>::new();
+        let y = model.add_var().name("y").build();
+        assert!(Expression::from(&y).try_div(0.0).is_err());
+        assert!(Expression::from(&y).try_div(-0.0).is_err());
+    }
+
+    #[test]
+    fn test_validation_report() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").build();
+        let u = model.add_var().name("u").lb(c(2).0).build();
+        let d = model.add_var().name("d").free().build();
+        let b = model.add_var().name("b").binary().build();
+        let g = model.add_var().name("g").build();
+        model.set_objective(&x - &y + &y);
+        model.add_const((&x + &d - &d).le(c(4).0));
+        let empty = model.add_const((&x - &x).ge(c(-1).0));
+        model.add_indicator(b, true, x.clone().le(c(1).0)).unwrap();
+        model.begin_group("extra");
+        model.add_const(g.clone().le(c(3).0));
+        model.end_group();
+        model.set_group_enabled("extra", false);
+        // names the model would reject, e.g. from an older serialized model
+        let renamed = model.add_const(x.clone().ge(c(0).0));
+        model.constraints[renamed.index()].as_mut().unwrap().name = Some("c1".to_string());
+
+        assert_eq!(model.unused_variables(), [y, u, d]);
+        let report = model.validation_report();
+        assert_eq!(report.unused_variables(), model.unused_variables());
+        assert_eq!(report.empty_constraints(), [empty]);
+        assert_eq!(report.duplicate_names(), ["c1".to_string()]);
+        assert!(!report.is_empty());
+        assert_eq!(model.validate(), Ok(()));
+
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        model.add_const(x.clone().le(c(1).0));
+        assert!(model.validation_report().is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().name("y").lb(0.0).ub(4.0).build();
+        model.set_objective(&x + &y);
+        model.add_const((&x + &y).le(4.0));
+        assert_eq!(model.validate(), Ok(()));
+
+        assert_eq!(
+            model.try_set_objective(&x * f64::INFINITY + &y * f64::NAN),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the objective".to_string(),
+                terms: vec!["x".to_string(), "y".to_string()],
+            })
+        );
+        assert_eq!(
+            model.set_objective_coefficient(&y, f64::NAN),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the objective".to_string(),
+                terms: vec!["y".to_string()],
+            })
+        );
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            model.add_const((&x * f64::INFINITY).le(4.0));
+        }))
+        .unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("non-finite coefficients in constraint `c1`: x")
+        );
+        assert_eq!(model.num_constraints(), 1);
+        assert_eq!(model.validate(), Ok(()));
+
+        assert_eq!(
+            model.add_var().name("v").obj(f64::INFINITY).try_build(),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the objective".to_string(),
+                terms: vec!["v".to_string()],
+            })
+        );
+        let row = model.add_const((&x + &y).ge(1.0));
+        assert_eq!(
+            model.add_var().in_constraint(row, f64::NAN).try_build(),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "constraint `c1`".to_string(),
+                terms: vec!["v2".to_string()],
+            })
+        );
+        assert_eq!(model.num_vars(), 2);
+
+        // only models that were read can hold such coefficients
+        model.variables[x.id()].obj = Some(f64::INFINITY);
+        assert_eq!(
+            model.validate(),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the objective".to_string(),
+                terms: vec!["x".to_string()],
+            })
+        );
+        let error = model.solve_with(&SolveOptions::default()).err();
+        assert_eq!(
+            error.map(|e| e.to_string()),
+            Some("invalid model: non-finite coefficients in the objective: x".to_string())
+        );
+
+        let mut model = Model::<f64>::new();
+        let x = model.add_var().name("x").build();
+        model.set_objective((&x).into());
+        assert_eq!(
+            model.add_const_named("limit", (&x + f64::NAN).between(f64::NAN, 1.0)),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "constraint `limit`".to_string(),
+                terms: vec!["constant".to_string(), "lower bound".to_string()],
+            })
+        );
+        model
+            .add_const_named("limit", (&x + 0.0).between(0.0, f64::INFINITY))
+            .unwrap();
+        model.add_const((&x + 0.0).between(f64::NEG_INFINITY, f64::INFINITY));
+        assert_eq!(model.validate(), Ok(()));
+        assert!(model.to_lp_string().unwrap().contains(" limit: x >= 0\n"));
+
+        let mut model = Model::<BigRational>::new();
+        let z = model.add_var().name("z").build();
+        // only models that were read can hold such bounds
+        model.variables[z.id()].lb = Bound::Finite(c(3).0);
+        model.variables[z.id()].ub = Bound::Finite(c(2).0);
+        assert_eq!(
+            model.validate(),
+            Err(ModelError::InvertedBounds("z".to_string()))
+        );
+        assert!(matches!(
+            model.solve_with(&SolveOptions::default()),
+            Err(SolveError::InvalidModel(ModelError::InvertedBounds(name))) if name == z.name()
+        ));
+
+        let mut model = Model::<f64>::new();
+        let w = model.add_var().name("w").build();
+        model.variables[w.id()].ub = Bound::Finite(f64::NAN);
+        assert_eq!(
+            model.validate(),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the bounds of `w`".to_string(),
+                terms: vec!["NaN".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_variable_bounds() {
+        let mut model = Model::<f64>::new();
+        let error = |result: Result<_, ModelError>| result.err().map(|e| e.to_string());
+        assert_eq!(
+            error(model.add_var().name("a").lb(5.0).ub(2.0).try_build()),
+            Some("lower bound of `a` exceeds its upper bound".to_string())
+        );
+        assert_eq!(
+            error(model.add_var().name("b").lb(f64::NEG_INFINITY).try_build()),
+            Some("non-finite coefficients in the bounds of `b`: -inf".to_string())
+        );
+        assert_eq!(
+            error(model.add_var().name("c").ub(f64::NAN).try_build()),
+            Some("non-finite coefficients in the bounds of `c`: NaN".to_string())
+        );
+        assert_eq!(
+            error(
+                model
+                    .add_var()
+                    .name("d")
+                    .binary()
+                    .lb(1.0)
+                    .ub(0.0)
+                    .try_build()
+            ),
+            Some("lower bound of `d` exceeds its upper bound".to_string())
+        );
+        assert_eq!(model.num_vars(), 0);
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            model.add_var().name("e").lb(1.0).ub(f64::INFINITY).build();
+        }))
+        .unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("non-finite coefficients in the bounds of `e`: inf")
+        );
+
+        let f = model.add_var().name("f").fix(2.0).free().build();
+        assert_eq!(model.var_bounds(&f), Ok((None, None)));
+        assert_eq!(
+            model.set_var_bounds(&f, Some(1.0), Some(-1.0)),
+            Err(ModelError::InvertedBounds("f".to_string()))
+        );
+        assert_eq!(
+            model.unfix_variable(&f, Some(f64::INFINITY), None),
+            Err(ModelError::NonFiniteCoefficients {
+                row: "the bounds of `f`".to_string(),
+                terms: vec!["inf".to_string()],
+            })
+        );
+        assert_eq!(model.var_bounds(&f), Ok((None, None)));
+        model.variables[f.id()].lb = Bound::Finite(f64::INFINITY);
+        assert_eq!(
+            model.validate().err().map(|e| e.to_string()),
+            Some("non-finite coefficients in the bounds of `f`: inf".to_string())
+        );
+    }
+}
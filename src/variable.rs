@@ -4,13 +4,23 @@ use num::Num;
 
 use crate::{constraint::Constraint, expression::Expression};
 
+/**
+ * Handle to a variable of a model
+ *
+ * Handles compare, hash and sort by id only, so handles of different models
+ * can be equal. The impls are written out as derives would require `N` to
+ * implement the traits too.
+ */
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct Variable<N>
 where
     N: Num + Clone,
 {
     id: usize,
     name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pd: std::marker::PhantomData<N>,
 }
 
@@ -30,6 +40,22 @@ where
         self.id
     }
 
+    /// The same variable of a model with coefficients of type `M`.
+    pub(crate) fn cast<M>(&self) -> Variable<M>
+    where
+        M: Num + Clone,
+    {
+        Variable::new(self.id, self.name.clone())
+    }
+
+    /// Like `name`, borrowing the name when the variable has one.
+    pub(crate) fn name_ref(&self) -> std::borrow::Cow<'_, str> {
+        match &self.name {
+            Some(name) => std::borrow::Cow::Borrowed(name),
+            None => std::borrow::Cow::Owned(format!("v{}", self.id)),
+        }
+    }
+
     pub fn name(&self) -> String {
         if let Some(name) = &self.name {
             name.clone()
@@ -49,6 +75,10 @@ where
     pub fn ge(self, expr: impl Into<Expression<N>>) -> Constraint<N> {
         Expression::<N>::from(self).ge(expr)
     }
+
+    pub fn between(self, lb: N, ub: N) -> Constraint<N> {
+        Expression::<N>::from(self).between(lb, ub)
+    }
 }
 
 impl<N> std::fmt::Display for Variable<N>
@@ -64,6 +94,56 @@ where
     }
 }
 
+impl<N> std::fmt::Debug for Variable<N>
+where
+    N: Num + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Variable")
+            .field("id", &self.id)
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl<N> PartialEq for Variable<N>
+where
+    N: Num + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<N> Eq for Variable<N> where N: Num + Clone {}
+
+impl<N> std::hash::Hash for Variable<N>
+where
+    N: Num + Clone,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<N> PartialOrd for Variable<N>
+where
+    N: Num + Clone,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Variable<N>
+where
+    N: Num + Clone,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
 impl<IntoExpression, N> Add<IntoExpression> for Variable<N>
 where
     IntoExpression: Into<Expression<N>>,
@@ -92,7 +172,17 @@ where
 {
     type Output = Expression<N>;
     fn neg(self) -> Self::Output {
-        Expression::from(self)
+        Expression::from(self).neg()
+    }
+}
+
+impl<N> Neg for &Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn neg(self) -> Self::Output {
+        self.clone().neg()
     }
 }
 
@@ -115,3 +205,86 @@ where
         Expression::from(self).div(rhs)
     }
 }
+
+impl<IntoExpression, N> Add<IntoExpression> for &Variable<N>
+where
+    IntoExpression: Into<Expression<N>>,
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn add(self, rhs: IntoExpression) -> Self::Output {
+        self.clone().add(rhs)
+    }
+}
+
+impl<IntoExpression, N> Sub<IntoExpression> for &Variable<N>
+where
+    IntoExpression: Into<Expression<N>>,
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn sub(self, rhs: IntoExpression) -> Self::Output {
+        self.clone().sub(rhs)
+    }
+}
+
+impl<N> Mul<N> for &Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn mul(self, rhs: N) -> Self::Output {
+        self.clone().mul(rhs)
+    }
+}
+
+impl<N> Div<N> for &Variable<N>
+where
+    N: Num + Clone,
+{
+    type Output = Expression<N>;
+    fn div(self, rhs: N) -> Self::Output {
+        self.clone().div(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
+    use num::BigRational;
+
+    use crate::{Model, Variable};
+
+    #[test]
+    fn test_handle_traits() {
+        let mut model = Model::<BigRational>::new();
+        let x = model.add_var().name("x").build();
+        let y = model.add_var().build();
+
+        let mut set = HashSet::new();
+        assert!(set.insert(x.clone()));
+        assert!(set.insert(y.clone()));
+        assert!(!set.insert(Variable::new(0, None)));
+        assert_eq!(set.len(), 2);
+        assert_eq!(x, Variable::new(0, Some("renamed".to_string())));
+        assert_ne!(x, y);
+
+        let index = [(y.clone(), 1), (x.clone(), 0)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(index.keys().collect::<Vec<_>>(), [&x, &y]);
+        assert!(x < y);
+
+        assert_eq!(format!("{:?}", x), r#"Variable { id: 0, name: "x" }"#);
+        assert_eq!(format!("{:?}", y), r#"Variable { id: 1, name: "v1" }"#);
+
+        let mut model = Model::<f64>::new();
+        let z = model.add_var().name("z").build();
+        assert_eq!(
+            format!("{:?}", (&z + 1.0).le(2.0)),
+            "Constraint { lhs: Expression([(1.0, Some(Variable { id: 0, name: \"z\" })), \
+             (1.0, None)]), sense: Le, rhs: Expression([(2.0, None)]) }"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Exercises the public API from outside the crate, so that it keeps
+//! compiling on the stable toolchain.
+
+use exact_lp::{c, Expression, Model, Solution, SolveStatus};
+use num::BigRational;
+
+#[test]
+fn test_public_api() {
+    let mut model = Model::<BigRational>::new();
+    let x = model.add_var().name("x").lb(c(0).0).build();
+    let y = model
+        .add_var()
+        .name("y")
+        .integer()
+        .lb(c(0).0)
+        .ub(c(10).0)
+        .build();
+
+    model.maximize();
+    model.set_objective(c(2) * x.clone() + c(5) * y.clone());
+    model.add_const((x.clone() + c(4) * y.clone()).le(c(24).0));
+    model
+        .add_const_named("total", (x.clone() + y.clone()).le(c(9).0))
+        .unwrap();
+
+    let solution =
+        Solution::<BigRational>::parse(include_str!("fixtures/optimal.sol").as_bytes()).unwrap();
+    assert_eq!(solution.status(), SolveStatus::Optimal);
+    assert_eq!(solution.get_value(x.clone()), c(4).0);
+    assert_eq!(solution.get_value(Expression::from(x) + y), c(9).0);
+}
@@ -0,0 +1,29 @@
+//! Solves with an installed `cbc`, run with `--features cbc-tests`.
+#![cfg(feature = "cbc-tests")]
+
+use exact_lp::{Model, SolveOptions, SolveStatus, Solver};
+
+#[test]
+fn test_cbc() {
+    let mut model = Model::<f64>::new();
+    let x = model.add_var().name("x").lb(0.0).build();
+    let y = model.add_var().name("y").integer().lb(0.0).ub(10.0).build();
+    model.maximize();
+    model.set_objective(&x * 2.0 + &y * 5.0 + 1.0);
+    model.add_const((&x + &y * 4.0).le(24.0));
+    model.add_const((&x + &y).le(9.0));
+
+    let solution = model
+        .solve_with_solver(Solver::Cbc, &SolveOptions::default())
+        .unwrap();
+    assert_eq!(solution.status(), SolveStatus::Optimal);
+    assert_eq!(solution.get_value(x), 4.0);
+    assert_eq!(solution.get_value(&y), 5.0);
+    assert_eq!(solution.objective_value(), Some(&34.0));
+
+    model.add_const(y.ge(11.0));
+    let solution = model
+        .solve_with_solver(Solver::Cbc, &SolveOptions::default())
+        .unwrap();
+    assert_eq!(solution.status(), SolveStatus::Infeasible);
+}
@@ -0,0 +1,3 @@
+Optimal - objective value 33.00000000
+      0 x                      4                       0
+      1 y                      5                       0
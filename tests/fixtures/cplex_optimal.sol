@@ -0,0 +1,34 @@
+<?xml version = "1.0" encoding="UTF-8" standalone="yes"?>
+<CPLEXSolution version="1.2">
+ <header
+   problemName="formulation.lp"
+   solutionName="incumbent"
+   solutionIndex="-1"
+   objectiveValue="33"
+   solutionTypeValue="3"
+   solutionTypeString="primal"
+   solutionStatusValue="101"
+   solutionStatusString="integer optimal solution"
+   solutionMethodString="mip"
+   primalFeasible="1"
+   dualFeasible="1"
+   MIPNodes="0"
+   MIPIterations="2"
+   writeLevel="1"/>
+ <quality
+   epInt="1.0000000000000001e-05"
+   epRHS="9.9999999999999995e-07"
+   maxIntInfeas="0"
+   maxPrimalInfeas="0"
+   maxX="5"
+   maxSlack="7"/>
+ <linearConstraints>
+  <constraint name="c0" index="0" slack="0"/>
+  <constraint name="c1" index="1" slack="7"/>
+  <constraint name="c2" index="2" slack="0"/>
+ </linearConstraints>
+ <variables>
+  <variable name="x" index="0" value="4"/>
+  <variable name="y" index="1" value="5"/>
+ </variables>
+</CPLEXSolution>
@@ -0,0 +1,8 @@
+solution status: optimal solution found
+objective value:                                   -7/2
+fraction                                            5/3 	(obj:0)
+negative_fraction                                  -1/7 	(obj:0)
+decimal                                             2.5 	(obj:-1)
+negative_decimal                                 -0.375 	(obj:0)
+exponent                                          1e-05 	(obj:0)
+negative_exponent                              -2.5E+03 	(obj:0)
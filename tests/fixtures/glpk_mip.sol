@@ -0,0 +1,12 @@
+c Problem:    
+c Rows:       1
+c Columns:    2
+c Non-zeros:  2
+c Status:     INTEGER OPTIMAL
+c Objective:  obj = 1234570 (MAXimum)
+c
+s mip 1 2 o 1234570
+i 1 1234570
+j 1 1234567
+j 2 3
+e o f
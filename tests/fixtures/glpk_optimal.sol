@@ -0,0 +1,14 @@
+c Problem:    
+c Rows:       3
+c Columns:    2
+c Non-zeros:  6
+c Status:     OPTIMAL
+c Objective:  obj = 33 (MAXimum)
+c
+s bas 3 2 f f 33
+i 1 u 24 1
+i 2 b 17 0
+i 3 u 9 1
+j 1 b 4 0
+j 2 b 5 0
+e o f
@@ -0,0 +1,4 @@
+# Solution for model formulation
+# Objective value = 3.3000000000000000e+01
+x 4.0000000000000000e+00
+y 5
@@ -0,0 +1,31 @@
+Model status
+Optimal
+
+# Primal solution values
+Feasible
+Objective 33
+# Columns 2
+x 4
+y 5
+# Rows 3
+c0 24
+c1 17
+c2 9
+
+# Dual solution values
+Feasible
+# Columns 2
+x 0
+y 0
+# Rows 3
+c0 1
+c1 0
+c2 1
+
+# Basis
+HiGHS v1
+Valid
+# Columns 2
+1 1
+# Rows 3
+0 1 0
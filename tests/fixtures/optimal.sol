@@ -0,0 +1,4 @@
+solution status: optimal solution found
+objective value:                                   33
+x                                                   4 	(obj:2)
+y                                                   5 	(obj:5)
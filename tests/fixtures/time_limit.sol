@@ -0,0 +1,4 @@
+solution status: time limit reached
+objective value:                                   30
+x                                                   5 	(obj:2)
+y                                                   4 	(obj:5)
@@ -0,0 +1,2 @@
+solution status: unbounded
+no solution available
@@ -0,0 +1,43 @@
+//! Solves with the licensed `cplex` and `gurobi_cl`, run only when
+//! `EXACT_LP_CPLEX_TESTS` or `EXACT_LP_GUROBI_TESTS` is set, as CI has no
+//! licenses.
+
+use exact_lp::{Model, SolveOptions, SolveStatus, Solver};
+
+fn check(solver: Solver) {
+    let mut model = Model::<f64>::new();
+    let x = model.add_var().name("x").build();
+    let y = model.add_var().name("y").integer().ub(10.0).build();
+    model.maximize();
+    model.set_objective(&x * 2.0 + &y * 5.0 + 1.0);
+    model.add_const((&x + &y * 4.0).le(24.0));
+    model.add_const((&x + &y).le(9.0));
+
+    let solution = model
+        .solve_with_solver(solver, &SolveOptions::default())
+        .unwrap();
+    assert_eq!(solution.status(), SolveStatus::Optimal);
+    assert_eq!(solution.get_value(x), 4.0);
+    assert_eq!(solution.get_value(&y), 5.0);
+    assert_eq!(solution.objective_value(), Some(&34.0));
+
+    model.add_const(y.ge(11.0));
+    let solution = model
+        .solve_with_solver(solver, &SolveOptions::default())
+        .unwrap();
+    assert_eq!(solution.status(), SolveStatus::Infeasible);
+}
+
+#[test]
+fn test_cplex() {
+    if std::env::var_os("EXACT_LP_CPLEX_TESTS").is_some() {
+        check(Solver::Cplex);
+    }
+}
+
+#[test]
+fn test_gurobi() {
+    if std::env::var_os("EXACT_LP_GUROBI_TESTS").is_some() {
+        check(Solver::Gurobi);
+    }
+}
@@ -0,0 +1,23 @@
+//! The end-to-end example written with nothing but the prelude. Needs an
+//! installed `scip`.
+
+use exact_lp::prelude::*;
+
+#[test]
+fn test_prelude() {
+    let mut model = Model::<BigRational>::new();
+    let x = model.add_var().name("x").lb(rat(0)).build();
+    let y = model.add_var().name("y").lb(rat(0)).build();
+
+    model.maximize();
+    model.set_objective(&x * 2 + &y * 5);
+    model.add_const((&x + &y * 4).le(24));
+    model.add_const((&x * 3 + &y).le(21));
+    model.add_const((&x + &y).le(9));
+
+    let solution = model.solve_with(&SolveOptions::default()).unwrap();
+    assert_eq!(solution.status(), SolveStatus::Optimal);
+    assert_eq!(solution.get_value(&x), rat(4));
+    assert_eq!(solution.get_value(&y), rat(5));
+    assert_eq!(solution.objective_value(), Some(&rat(33)));
+}